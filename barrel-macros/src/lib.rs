@@ -0,0 +1,91 @@
+//! Procedural macros for barrel
+//!
+//! These are re-exported by barrel behind its `macros` feature,
+//! so this crate doesn't need to be used directly.
+
+extern crate proc_macro;
+
+use proc_macro::{TokenStream, TokenTree};
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const USAGE: &str =
+    "Expected the path of a migrations directory, e.g. `embed_migrations!(\"migrations\")`";
+
+/// Include a directory of migration files into the binary
+///
+/// The directory is relative to the `Cargo.toml` of the crate using
+/// the macro. Each `.rs` file in it provides an `up` and a `down`
+/// function (see `barrel::build`) and becomes an `EmbeddedMigration`,
+/// ordered by file name.
+///
+/// ```norun
+/// static MIGRATIONS: &[EmbeddedMigration] = embed_migrations!("migrations");
+/// ```
+#[proc_macro]
+pub fn embed_migrations(input: TokenStream) -> TokenStream {
+    let code = match expand(input) {
+        Ok(code) => code,
+        Err(msg) => format!("compile_error!({:?})", msg),
+    };
+    code.parse().unwrap()
+}
+
+fn expand(input: TokenStream) -> Result<String, String> {
+    let dir =
+        Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap_or_default()).join(directory(input)?);
+
+    let mut mods = String::new();
+    let mut list = String::new();
+    for (i, file) in migration_files(&dir)?.iter().enumerate() {
+        let name = file
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        mods.push_str(&format!(
+            "#[allow(unused_variables)] #[path = {:?}] mod m{};\n",
+            file.display().to_string(),
+            i
+        ));
+        list.push_str(&format!(
+            "::barrel::embedded::EmbeddedMigration {{ name: {:?}, up: m{}::up, down: m{}::down }},\n",
+            name, i, i
+        ));
+    }
+
+    Ok(format!("{{\n{}&[\n{}]\n}}", mods, list))
+}
+
+/// Read the directory from the single string literal given to the macro
+fn directory(input: TokenStream) -> Result<String, String> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let lit = match tokens.as_slice() {
+        [TokenTree::Literal(lit)] => lit.to_string(),
+        _ => return Err(USAGE.into()),
+    };
+
+    match lit.len() >= 2 && lit.starts_with('"') && lit.ends_with('"') {
+        true => Ok(lit[1..lit.len() - 1].replace("\\\\", "\\")),
+        false => Err(USAGE.into()),
+    }
+}
+
+/// All migration files in a directory, ordered by their name
+fn migration_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let err =
+        |e: std::io::Error| format!("Failed to read migrations from `{}`: {}", dir.display(), e);
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).map_err(err)? {
+        let path = entry.map_err(err)?.path();
+        if path.extension().is_some_and(|e| e == "rs") {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,38 @@
+use barrel::backend::Pg;
+use barrel::embedded::{self, EmbeddedMigration};
+
+static MIGRATIONS: &[EmbeddedMigration] = barrel::embed_migrations!("tests/migrations");
+
+#[test]
+fn ordered_by_name() {
+    let names: Vec<_> = MIGRATIONS.iter().map(|m| m.name).collect();
+    assert_eq!(names, vec!["0001_create_users", "0002_rename_users"]);
+}
+
+#[test]
+fn builds_migrations() {
+    let m = MIGRATIONS[0].migration();
+    assert_eq!(
+        m.make::<Pg>(),
+        "CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY, \"name\" TEXT)"
+    );
+    assert_eq!(m.make_down::<Pg>(), "DROP TABLE \"users\"");
+
+    let m = MIGRATIONS[1].migration();
+    assert_eq!(
+        m.make_down::<Pg>(),
+        "ALTER TABLE \"people\" RENAME TO \"users\""
+    );
+}
+
+#[test]
+fn run_pending() {
+    let mut conn = rusqlite::Connection::open_in_memory().unwrap();
+    let applied = embedded::run::<_, barrel::backend::Sqlite>(MIGRATIONS, &mut conn).unwrap();
+    assert_eq!(applied, vec!["0001_create_users", "0002_rename_users"]);
+
+    let applied = embedded::run::<_, barrel::backend::Sqlite>(MIGRATIONS, &mut conn).unwrap();
+    assert!(applied.is_empty());
+    conn.execute("INSERT INTO people (name) VALUES ('Alice')", [])
+        .unwrap();
+}
@@ -0,0 +1,9 @@
+use barrel::*;
+
+pub fn up(m: &mut Migration) {
+    m.create_table("users", |t| {
+        t.add_column("name", Type::Text);
+    });
+}
+
+pub fn down(m: &mut Migration) {}
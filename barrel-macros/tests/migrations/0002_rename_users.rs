@@ -0,0 +1,9 @@
+use barrel::*;
+
+pub fn up(m: &mut Migration) {
+    m.rename_table("users", "people");
+}
+
+pub fn down(m: &mut Migration) {
+    m.rename_table("people", "users");
+}
@@ -16,7 +16,6 @@ fn main() {
 
     // println!("{:#?}", container);
 
-
     // use Type::*;
     // let mut m = Migration::new();
     // // A new table is automatically created with an "id" primary key
@@ -25,9 +24,9 @@ fn main() {
     //     t.add_column("name", Varchar(255)).default("Anonymous"); // Default name is "Anonymous"
     //     t.add_column("description", Text).nullable(); // Can be null
     //     t.add_column("age", Integer);
-    //     t.add_column("posts", Foreign("posts"));
+    //     t.add_column("posts", Foreign("posts".into()));
     //     t.add_column("owns_plushy_sharks", Boolean);
     // });
-    
+
     // println!("{}", m.make::<Pg>());
 }
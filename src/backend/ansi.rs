@@ -0,0 +1,256 @@
+//! ANSI SQL implementation of a generator
+//!
+//! This generator only emits statements which are part of the SQL
+//! standard and is meant as a fallback for databases that barrel has
+//! no dedicated backend for (such as H2 or other embedded engines).
+//!
+//! Operations and types that only exist as vendor extensions (such as
+//! renaming tables or `IF [NOT] EXISTS` guards) are rejected by the
+//! `validate_*` functions instead of being generated in some other
+//! database's dialect.
+
+use super::{
+    bytes, checks, collate, constraint, custom_for, custom_given, enum_check, identity_type,
+    insert, longest, mysql_only, mysql_table_only, no_storage, no_storage_parameters,
+    postgres_table_only, precision, sqlite_table_only, unpartitioned, Column, Constraint,
+    DatabaseChange, Error, Index, SqlGenerator, SqlVariant, TableChange, TableMeta, Type, Value,
+};
+
+/// A generator for standard SQL
+#[derive(Clone, Debug, Default)]
+pub struct Ansi;
+impl SqlGenerator for Ansi {
+    fn create_table(&self, name: &str) -> String {
+        format!("CREATE TABLE \"{}\"", name)
+    }
+
+    fn create_table_if_not_exists(&self, _: &str) -> String {
+        Ansi::unsupported("CREATE TABLE IF NOT EXISTS")
+    }
+
+    fn drop_table(&self, name: &str) -> String {
+        format!("DROP TABLE \"{}\"", name)
+    }
+
+    fn drop_table_if_exists(&self, _: &str) -> String {
+        Ansi::unsupported("DROP TABLE IF EXISTS")
+    }
+
+    fn rename_table(&self, _: &str, _: &str) -> String {
+        Ansi::unsupported("Renaming a table")
+    }
+
+    fn alter_table(&self, name: &str) -> String {
+        format!("ALTER TABLE \"{}\"", name)
+    }
+
+    fn add_column(&self, ex: bool, name: &str, column: &Column) -> String {
+        format!(
+            "{}\"{}\" {}{}{}{}{}{}{}{}",
+            match ex {
+                true => "ADD COLUMN ",
+                false => "",
+            },
+            name,
+            match column.identity {
+                Some(ref id) => identity_type(&column._type, id.sql(), Ansi::print_type),
+                None => Ansi::print_type(column._type.clone()),
+            },
+            collate(column, |c| format!("\"{}\"", c)),
+            match column.generated {
+                Some((ref expr, _)) => format!(" GENERATED ALWAYS AS ({})", expr),
+                None => String::new(),
+            },
+            match column.def {
+                Some(ref m) => format!(" DEFAULT {}", m.sql()),
+                _ => String::new(),
+            },
+            match column.nullable {
+                true => " NOT NULL",
+                false => "",
+            },
+            match column.primary {
+                true => " PRIMARY KEY",
+                false => "",
+            },
+            enum_check(self, format!("\"{}\"", name), &column._type),
+            checks(column, |c| format!("\"{}\"", c))
+        )
+    }
+
+    fn change_column(&self, _: &str, _: &Column) -> String {
+        Ansi::unsupported("Changing a column")
+    }
+
+    fn drop_column(&self, name: &str) -> String {
+        format!("DROP COLUMN \"{}\"", name)
+    }
+
+    fn add_constraint(&self, ex: bool, name: Option<&str>, c: &Constraint) -> String {
+        constraint(ex, name, c, |c| format!("\"{}\"", c))
+    }
+
+    fn create_index(&self, _: &str, _: &str, _: &Index) -> String {
+        Ansi::unsupported("CREATE INDEX")
+    }
+
+    fn insert(&self, table: &str, row: &[(String, Value)]) -> String {
+        insert(self, format!("\"{}\"", table), row, |c| {
+            format!("\"{}\"", c)
+        })
+    }
+
+    fn begin_transaction(&self) -> String {
+        "START TRANSACTION".into()
+    }
+
+    fn rename_column(&self, _: &str, _: &str) -> String {
+        Ansi::unsupported("Renaming a column")
+    }
+
+    /// Standard SQL has no `IF [NOT] EXISTS`
+    fn table_guards(&self) -> bool {
+        false
+    }
+
+    fn validate_change(&self, change: &DatabaseChange) -> Result<(), Error> {
+        use crate::DatabaseChange::*;
+        match *change {
+            CreateTableIfNotExists(_, _) => Ansi::error("CREATE TABLE IF NOT EXISTS"),
+            DropTableIfExists(_) => Ansi::error("DROP TABLE IF EXISTS"),
+            RenameTable(_, _) => Ansi::error("Renaming a table"),
+            CreatePartition(_, _, _) => Ansi::error("Creating a partition"),
+            CreatePolicy(_, _, _) => Ansi::error("Row-level security policies"),
+            RenameIndex(_, _) => Ansi::error("Renaming an index"),
+            CreateTable(ref t, _) if t.meta.auto_increment_start.is_some() => {
+                Ansi::error("Auto-increment start values")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn validate_table(&self, meta: &TableMeta) -> Result<(), Error> {
+        mysql_table_only("ANSI", meta)?;
+        no_storage_parameters("ANSI", meta)?;
+        postgres_table_only("ANSI", meta)?;
+        sqlite_table_only("ANSI", meta)?;
+        unpartitioned("ANSI", meta)?;
+        match (&meta.comment, meta.temporary) {
+            (Some(_), _) => Err(Error::Unsupported("ANSI", "Table comments".into())),
+            (_, true) => Err(Error::Unsupported("ANSI", "Temporary tables".into())),
+            _ => Ok(()),
+        }
+    }
+
+    fn validate_table_change(&self, change: &TableChange) -> Result<(), Error> {
+        use crate::TableChange::*;
+        custom_given("ANSI", &[SqlVariant::Ansi], change)?;
+        mysql_only("ANSI", change)?;
+        no_storage("ANSI", change)?;
+        match *change {
+            AddColumn(_, ref c) if c.unsigned => Ansi::error("Unsigned integers"),
+            AddColumn(_, ref c) => Ansi::validate_type(&c._type),
+            ChangeColumn(_, _, _) => Ansi::error("Changing a column"),
+            RenameColumn(_, _) => Ansi::error("Renaming a column"),
+            RenameConstraint(_, _) => Ansi::error("Renaming a constraint"),
+            AddIndex(_, _) => Ansi::error("Indexes"),
+            AddConstraint(_, Constraint::Exclusion { .. }) => Ansi::error("Exclusion constraints"),
+            DropColumn(_)
+            | CustomLine(_)
+            | AddConstraint(_, _)
+            | DropConstraint(_)
+            | SetDefault(_, _)
+            | SetNotNull(_, _, _) => Ok(()),
+        }
+    }
+}
+
+impl Ansi {
+    fn error(what: &str) -> Result<(), Error> {
+        Err(Error::Unsupported("ANSI", what.into()))
+    }
+
+    fn unsupported(what: &str) -> String {
+        panic!("{}", Error::Unsupported("ANSI", what.into()))
+    }
+
+    fn validate_type(t: &Type) -> Result<(), Error> {
+        match *t {
+            Type::Varchar(0) => Ansi::error("VARCHAR without a length"),
+            Type::Interval(None) => Ansi::error("INTERVAL without a fields qualifier"),
+            Type::Set(_) => Ansi::error("SET types"),
+            Type::TsVector | Type::TsQuery => Ansi::error("Full-text search types"),
+            Type::Range(_) => Ansi::error("Range types"),
+            Type::Point => Ansi::error("POINT types"),
+            #[cfg(feature = "postgis")]
+            Type::Geometry(_, _) | Type::Geography(_, _) => Ansi::error("Spatial types"),
+            Type::Array(ref inner) => Ansi::validate_type(inner),
+            _ => Ok(()),
+        }
+    }
+
+    fn print_type(t: Type) -> String {
+        use crate::Type::*;
+        match t {
+            Primary => "INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY".into(),
+            Text => "CLOB".into(),
+            TinyText => "VARCHAR(255)".into(),
+            MediumText | LongText => "CLOB".into(),
+            Varchar(0) => Ansi::unsupported("VARCHAR without a length"),
+            Varchar(l) => format!("VARCHAR({})", l),
+            Char(l) => format!("CHAR({})", l),
+            Citext => "CLOB".into(),
+            Integer => "INTEGER".into(),
+            SmallInt => "SMALLINT".into(),
+            BigInt => "BIGINT".into(),
+            Serial => "INTEGER GENERATED BY DEFAULT AS IDENTITY".into(),
+            BigSerial => "BIGINT GENERATED BY DEFAULT AS IDENTITY".into(),
+            Float => "REAL".into(),
+            Double => "DOUBLE PRECISION".into(),
+            Boolean => "BOOLEAN".into(),
+            Bit(l) => format!("BINARY({})", bytes(l)),
+            Varbit(0) => "BLOB".into(),
+            Varbit(l) => format!("VARBINARY({})", bytes(l)),
+            Binary | Varbinary(0) => "BLOB".into(),
+            Varbinary(l) => format!("VARBINARY({})", l),
+            Uuid => "CHAR(36)".into(),
+            Inet | Cidr => "VARCHAR(43)".into(),
+            MacAddr => "VARCHAR(17)".into(),
+            Json | Jsonb => "CLOB".into(),
+            Hstore => "CLOB".into(),
+            TsVector | TsQuery => Ansi::unsupported("Full-text search types"),
+            Xml => "XML".into(),
+            Date => "DATE".into(),
+            Time(p) => precision("TIME", p),
+            DateTime(p) => precision("TIMESTAMP", p),
+            TimestampTz(p) => format!("{} WITH TIME ZONE", precision("TIMESTAMP", p)),
+            Interval(Some(f)) => format!("INTERVAL {}", f),
+            Interval(None) => Ansi::unsupported("INTERVAL without a fields qualifier"),
+            Decimal(p, s) => format!("NUMERIC({}, {})", p, s),
+            Range(_) => Ansi::unsupported("Range types"),
+            Money => "NUMERIC(19, 4)".into(),
+            Point => Ansi::unsupported("POINT types"),
+            #[cfg(feature = "postgis")]
+            Geometry(_, _) | Geography(_, _) => Ansi::unsupported("Spatial types"),
+            Enum(_, values) => format!("VARCHAR({})", longest(&values)),
+            Set(_) => Ansi::unsupported("SET types"),
+            Custom(t) => t,
+            CustomPer(types) => custom_for(&types, &[SqlVariant::Ansi])
+                .expect("Custom type without an entry for ANSI SQL"),
+            Foreign(fk) => format!(
+                "INTEGER {}REFERENCES \"{}\"{}{}",
+                match fk.name {
+                    Some(ref n) => format!("CONSTRAINT \"{}\" ", n),
+                    None => String::new(),
+                },
+                fk.table,
+                match fk.column {
+                    Some(ref c) => format!("(\"{}\")", c),
+                    None => String::new(),
+                },
+                fk.actions()
+            ),
+            Array(meh) => format!("{} ARRAY", Ansi::print_type(*meh)),
+        }
+    }
+}
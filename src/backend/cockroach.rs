@@ -0,0 +1,261 @@
+//! CockroachDB implementation of a generator
+//!
+//! Cockroach speaks the Postgres wire protocol and mostly
+//! understands the same SQL, so this generator falls back to the
+//! Postgres one wherever the two dialects agree. Where they don't
+//! (row ids instead of sequences, type names, nested arrays) the
+//! Cockroach behaviour is implemented here.
+
+use crate::statement::Operation;
+
+use super::pg::Pg;
+use super::{
+    base_type, checks, collate, column_of, constraint, custom_for, custom_given, fraction_digits,
+    generated, identity_type, mysql_only, mysql_table_only, no_deferrable, no_partitions,
+    no_policies, no_storage, postgres_table_only, sqlite_table_only, types_of, unpartitioned,
+    using, Column, Constraint, DatabaseChange, Error, Index, SqlGenerator, SqlVariant, Table,
+    TableChange, TableMeta, Type, Value,
+};
+
+/// A generator for CockroachDB
+///
+/// Like `Pg` it can be configured with a schema that all
+/// table names get qualified with.
+#[derive(Clone, Debug, Default)]
+pub struct Cockroach {
+    pg: Pg,
+}
+
+impl SqlGenerator for Cockroach {
+    fn create_table(&self, name: &str) -> String {
+        self.pg.create_table(name)
+    }
+
+    fn create_table_if_not_exists(&self, name: &str) -> String {
+        format!("CREATE TABLE IF NOT EXISTS {}", self.pg.table(name))
+    }
+
+    fn create_table_from(&self, meta: &TableMeta, guarded: bool) -> String {
+        self.pg.create_table_from(meta, guarded)
+    }
+
+    fn create_table_like(&self, name: &str, source: &str, guarded: bool) -> String {
+        self.pg.create_table_like(name, source, guarded)
+    }
+
+    fn drop_table(&self, name: &str) -> String {
+        self.pg.drop_table(name)
+    }
+
+    fn drop_table_if_exists(&self, name: &str) -> String {
+        format!("DROP TABLE IF EXISTS {}", self.pg.table(name))
+    }
+
+    fn rename_table(&self, old: &str, new: &str) -> String {
+        self.pg.rename_table(old, new)
+    }
+
+    fn alter_table(&self, name: &str) -> String {
+        self.pg.alter_table(name)
+    }
+
+    fn add_column(&self, ex: bool, name: &str, column: &Column) -> String {
+        format!(
+            "{}\"{}\" {}{}{}{}{}{}{}",
+            Pg::prefix(ex),
+            name,
+            match column.identity {
+                Some(ref id) => identity_type(&column._type, id.sql(), Cockroach::print_type),
+                None => Cockroach::print_type(column._type.clone()),
+            },
+            collate(column, |c| format!("\"{}\"", c)),
+            generated(column),
+            match column.def {
+                Some(ref m) => format!(" DEFAULT {}", m.sql()),
+                _ => String::new(),
+            },
+            match column.nullable {
+                true => " NOT NULL",
+                false => "",
+            },
+            match column.primary {
+                true => " PRIMARY KEY",
+                false => "",
+            },
+            checks(column, |c| format!("\"{}\"", c))
+        )
+    }
+
+    fn change_column(&self, name: &str, column: &Column) -> String {
+        format!(
+            "ALTER COLUMN \"{}\" TYPE {}{}",
+            name,
+            Cockroach::print_type(base_type(&column._type)),
+            using(column)
+        )
+    }
+
+    fn rename_index(&self, old: &str, new: &str) -> String {
+        self.pg.rename_index(old, new)
+    }
+
+    fn drop_column(&self, name: &str) -> String {
+        self.pg.drop_column(name)
+    }
+
+    fn add_constraint(&self, ex: bool, name: Option<&str>, c: &Constraint) -> String {
+        constraint(ex, name, c, |c| format!("\"{}\"", c))
+    }
+
+    fn create_index(&self, table: &str, name: &str, i: &Index) -> String {
+        self.pg.create_index(table, name, i)
+    }
+
+    fn create_index_if_not_exists(&self, table: &str, name: &str, i: &Index) -> String {
+        self.pg.create_index_if_not_exists(table, name, i)
+    }
+
+    fn add_column_if_not_exists(&self, name: &str, column: &Column) -> String {
+        format!(
+            "ADD COLUMN IF NOT EXISTS {}",
+            self.add_column(false, name, column)
+        )
+    }
+
+    fn drop_column_if_exists(&self, name: &str) -> String {
+        self.pg.drop_column_if_exists(name)
+    }
+
+    fn insert(&self, table: &str, row: &[(String, Value)]) -> String {
+        self.pg.insert(table, row)
+    }
+
+    fn rename_column(&self, old: &str, new: &str) -> String {
+        format!("RENAME COLUMN \"{}\" TO \"{}\"", old, new)
+    }
+
+    /// Cockroach has no extensions, the types it supports are built in
+    fn prepare_column(&self, column: &Column) -> Vec<(Operation, String)> {
+        self.pg
+            .prepare_column(column)
+            .into_iter()
+            .filter(|(op, _)| *op != Operation::CreateExtension)
+            .collect()
+    }
+
+    fn drop_type(&self, name: &str, guarded: bool) -> Option<String> {
+        self.pg.drop_type(name, guarded)
+    }
+
+    /// Only storage parameters pass `validate_table`
+    fn table_options(&self, meta: &TableMeta) -> String {
+        self.pg.table_options(meta)
+    }
+
+    fn finish_table(&self, t: &Table) -> Vec<(Operation, String)> {
+        self.pg.finish_table(t)
+    }
+
+    fn alter_table_options(&self, meta: &TableMeta) -> Vec<(Operation, String)> {
+        self.pg.alter_table_options(meta)
+    }
+
+    /// Cockroach numbers rows with `unique_rowid()`, which can't be
+    /// restarted, and doesn't create partitions as tables
+    fn validate_change(&self, change: &DatabaseChange) -> Result<(), Error> {
+        no_policies("CockroachDB", change)?;
+        no_partitions("CockroachDB", change)?;
+        match *change {
+            DatabaseChange::CreateTable(ref t, _)
+            | DatabaseChange::CreateTableIfNotExists(ref t, _)
+                if t.meta.auto_increment_start.is_some() =>
+            {
+                Err(Error::Unsupported(
+                    "CockroachDB",
+                    "Auto-increment start values".into(),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn validate_table(&self, meta: &TableMeta) -> Result<(), Error> {
+        mysql_table_only("CockroachDB", meta)?;
+        postgres_table_only("CockroachDB", meta)?;
+        sqlite_table_only("CockroachDB", meta)?;
+        unpartitioned("CockroachDB", meta)
+    }
+
+    fn validate_table_change(&self, change: &TableChange) -> Result<(), Error> {
+        custom_given(
+            "CockroachDB",
+            &[SqlVariant::Cockroach, SqlVariant::Pg],
+            change,
+        )?;
+        mysql_only("CockroachDB", change)?;
+        fraction_digits("CockroachDB", 6, change)?;
+        no_deferrable("CockroachDB", change)?;
+        no_storage("CockroachDB", change)?;
+        let unsupported = |what: &str| Err(Error::Unsupported("CockroachDB", what.into()));
+        match *change {
+            TableChange::AddConstraint(_, Constraint::Exclusion { .. }) => {
+                return unsupported("Exclusion constraints")
+            }
+            TableChange::AddIndex(_, ref i) if i.tablespace.is_some() => {
+                return unsupported("Tablespaces")
+            }
+            _ => {}
+        }
+
+        if matches!(column_of(change), Some(c) if c.unsigned) {
+            return unsupported("Unsigned integers");
+        }
+        types_of(change).into_iter().try_for_each(|t| match *t {
+            Type::Set(_) => unsupported("SET types"),
+            Type::Hstore => unsupported("HSTORE types"),
+            Type::Range(_) => unsupported("Range types"),
+            Type::Point => unsupported("POINT types"),
+            Type::Array(ref inner) if matches!(**inner, Type::Array(_)) => {
+                unsupported("Nested array types")
+            }
+            _ => Ok(()),
+        })
+    }
+}
+
+impl Cockroach {
+    /// Qualify all table names with a schema
+    pub fn schema<S: Into<String>>(self, schema: S) -> Self {
+        Cockroach {
+            pg: self.pg.schema(schema),
+        }
+    }
+
+    fn print_type(t: Type) -> String {
+        use crate::Type::*;
+        match t {
+            /* SERIAL is only an alias for this, make it explicit */
+            Primary => "INT8 DEFAULT unique_rowid() PRIMARY KEY".into(),
+            Serial | BigSerial => "INT8 DEFAULT unique_rowid()".into(),
+            Citext => "STRING COLLATE \"und-u-ks-level2\"".into(),
+            Float => "FLOAT4".into(),
+            Double => "FLOAT8".into(),
+            Binary | Varbinary(_) => "BYTES".into(),
+            Hstore => panic!("CockroachDB does not support HSTORE types"),
+            Xml => "STRING".into(),
+            Point => panic!("CockroachDB does not support POINT types"),
+            Range(_) => panic!("CockroachDB does not support range types"),
+            Money => "DECIMAL(19, 4)".into(),
+            CustomPer(types) => custom_for(&types, &[SqlVariant::Cockroach, SqlVariant::Pg])
+                .expect("Custom type without an entry for CockroachDB"),
+            /* Only host addresses have a native type */
+            Cidr => "INET".into(),
+            MacAddr => "STRING".into(),
+            Array(inner) => match *inner {
+                Array(_) => panic!("CockroachDB does not support nested array types"),
+                inner => format!("{}[]", Cockroach::print_type(inner)),
+            },
+            t => Pg::print_type(t),
+        }
+    }
+}
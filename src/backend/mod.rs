@@ -1,50 +1,1070 @@
 //! A backend module which provides a few generic traits
 //! to implement SQL generation for different databases.
-//! 
+//!
 //! It also re-exports the generators for existing databases
 //! so they can be used more conveniently.
 
+// Most helpers are only shared by some of the generators
+#![cfg_attr(
+    not(all(
+        feature = "pg",
+        feature = "mysql",
+        feature = "sqlite3",
+        feature = "mssql",
+        feature = "ansi"
+    )),
+    allow(dead_code)
+)]
+
 #[cfg(feature = "pg")]
 mod pg;
 #[cfg(feature = "pg")]
 pub use self::pg::Pg;
 
+#[cfg(feature = "ansi")]
+mod ansi;
+#[cfg(feature = "ansi")]
+pub use self::ansi::Ansi;
+
+#[cfg(feature = "cockroach")]
+mod cockroach;
+#[cfg(feature = "cockroach")]
+pub use self::cockroach::Cockroach;
+
+#[cfg(feature = "mssql")]
+mod mssql;
+#[cfg(feature = "mssql")]
+pub use self::mssql::MsSql;
+
+#[cfg(feature = "mysql")]
+mod mysql;
+#[cfg(feature = "mysql")]
+pub use self::mysql::MySql;
+
 #[cfg(feature = "sqlite3")]
 mod sqlite3;
 #[cfg(feature = "sqlite3")]
 pub use self::sqlite3::Sqlite;
 
+use crate::schema::TableSchema;
+use crate::statement::{self, Operation, Statement};
 #[allow(unused_imports)]
-use {Type, Column};
+use crate::{
+    Column, Constraint, DatabaseChange, Error, Generated, Identity, Index, Migration, Policy,
+    Table, TableChange, TableMeta, Type, Value,
+};
+
+use std::str::FromStr;
+
+/// Build an `INSERT` from a quoted table name and a row
+///
+/// Rows without any values insert the column defaults.
+pub(crate) fn insert<G: SqlGenerator + ?Sized>(
+    gen: &G,
+    table: String,
+    row: &[(String, Value)],
+    quote: fn(&str) -> String,
+) -> String {
+    match row.is_empty() {
+        true => format!("INSERT INTO {} DEFAULT VALUES", table),
+        false => format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            table,
+            row.iter()
+                .map(|(c, _)| quote(c))
+                .collect::<Vec<_>>()
+                .join(", "),
+            row.iter()
+                .map(|(_, v)| gen.value(v))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// A type name with an optional precision, like `TIME(3)`
+pub(crate) fn precision(name: &str, p: Option<u8>) -> String {
+    match p {
+        Some(p) => format!("{}({})", name, p),
+        None => name.to_string(),
+    }
+}
+
+/// A comma-separated list of strings, rendered as literals by a generator
+pub(crate) fn value_list<G: SqlGenerator + ?Sized>(gen: &G, values: &[String]) -> String {
+    values
+        .iter()
+        .map(|v| gen.value(&Value::Text(v.clone())))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The length of the longest string, to size a column holding enum values
+pub(crate) fn longest(values: &[String]) -> usize {
+    values
+        .iter()
+        .map(|v| v.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(1)
+}
+
+/// The number of bytes needed to store some bits
+pub(crate) fn bytes(bits: usize) -> usize {
+    bits.div_ceil(8)
+}
+
+/// A `CHECK` that limits a column to the values of an enum type,
+/// for backends which have no enums
+///
+/// Empty for all other types.
+pub(crate) fn enum_check<G: SqlGenerator + ?Sized>(gen: &G, name: String, t: &Type) -> String {
+    match *t {
+        Type::Enum(_, ref values) => format!(" CHECK ({} IN ({}))", name, value_list(gen, values)),
+        _ => String::new(),
+    }
+}
+
+/// The `GENERATED ALWAYS AS` clause of a computed column
+///
+/// Empty for all other columns.
+pub(crate) fn generated(column: &Column) -> String {
+    match column.generated {
+        Some((ref expr, Generated::Stored)) => format!(" GENERATED ALWAYS AS ({}) STORED", expr),
+        Some((ref expr, Generated::Virtual)) => format!(" GENERATED ALWAYS AS ({}) VIRTUAL", expr),
+        None => String::new(),
+    }
+}
+
+/// The type of an identity column, followed by its identity clause
+///
+/// Auto-incrementing types are replaced by plain integers, which
+/// the identity numbers instead.
+pub(crate) fn identity_type(t: &Type, clause: String, print: fn(Type) -> String) -> String {
+    let (base, primary) = match *t {
+        Type::Primary => (Type::Integer, true),
+        Type::Serial => (Type::Integer, false),
+        Type::BigSerial => (Type::BigInt, false),
+        ref t => (t.clone(), false),
+    };
+
+    format!(
+        "{} {}{}",
+        print(base),
+        clause,
+        match primary {
+            true => " PRIMARY KEY",
+            false => "",
+        }
+    )
+}
+
+/// The `COLLATE` clause of a column, with the collation quoted by a backend
+///
+/// Empty if no collation is set.
+pub(crate) fn collate(column: &Column, quote: fn(&str) -> String) -> String {
+    match column.collation {
+        Some(ref c) => format!(" COLLATE {}", quote(c)),
+        None => String::new(),
+    }
+}
+
+/// The inline `CHECK` constraints of a column, with names quoted by a backend
+pub(crate) fn checks(column: &Column, quote: fn(&str) -> String) -> String {
+    column
+        .checks
+        .iter()
+        .map(|(name, expr)| match name {
+            Some(name) => format!(" CONSTRAINT {} CHECK ({})", quote(name), expr),
+            None => format!(" CHECK ({})", expr),
+        })
+        .collect()
+}
+
+/// The start of a statement that creates a table of some kind,
+/// e.g. `CREATE TEMPORARY TABLE IF NOT EXISTS `
+pub(crate) fn create_kind(meta: &TableMeta, guarded: bool) -> String {
+    format!(
+        "CREATE {}TABLE {}",
+        match (meta.temporary, meta.unlogged) {
+            (true, _) => "TEMPORARY ",
+            (false, true) => "UNLOGGED ",
+            (false, false) => "",
+        },
+        match guarded {
+            true => "IF NOT EXISTS ",
+            false => "",
+        }
+    )
+}
+
+/// A constraint of a table, with names quoted by a backend
+///
+/// On existing tables the constraint is added with `ADD`.
+pub(crate) fn constraint(
+    ex: bool,
+    name: Option<&str>,
+    c: &Constraint,
+    quote: fn(&str) -> String,
+) -> String {
+    let columns = |cols: &[String]| cols.iter().map(|c| quote(c)).collect::<Vec<_>>().join(", ");
+    format!(
+        "{}{}{}",
+        match ex {
+            true => "ADD ",
+            false => "",
+        },
+        match name {
+            Some(name) => format!("CONSTRAINT {} ", quote(name)),
+            None => String::new(),
+        },
+        match *c {
+            Constraint::PrimaryKey(ref cols) => format!("PRIMARY KEY ({})", columns(cols)),
+            Constraint::Unique(ref cols) => format!("UNIQUE ({})", columns(cols)),
+            Constraint::Check(ref expr) => format!("CHECK ({})", expr),
+            Constraint::Exclusion {
+                ref method,
+                ref elements,
+            } => format!(
+                "EXCLUDE USING {} ({})",
+                method,
+                elements
+                    .iter()
+                    .map(|(col, op)| format!("{} WITH {}", quote(col), op))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Constraint::ForeignKey {
+                columns: ref cols,
+                ref table,
+                ref references,
+            } => format!(
+                "FOREIGN KEY ({}) REFERENCES {} ({})",
+                columns(cols),
+                quote(table),
+                columns(references)
+            ),
+        }
+    )
+}
+
+/// An index on a table, with names quoted by a backend
+///
+/// The table is expected to be quoted already, since backends
+/// qualify it with their schema.
+pub(crate) fn index(
+    guard: &str,
+    name: &str,
+    table: &str,
+    index: &Index,
+    quote: fn(&str) -> String,
+) -> String {
+    let columns: Vec<_> = match index.expression {
+        Some(ref expr) => vec![expr.clone()],
+        None => index.columns.iter().map(|c| quote(c)).collect(),
+    };
+    let include: Vec<_> = index.include.iter().map(|c| quote(c)).collect();
+    format!(
+        "CREATE {}INDEX {}{} ON {} ({}){}{}{}",
+        match (index.kind, index.unique) {
+            (Some(kind), _) => format!("{} ", kind.sql()),
+            (None, true) => "UNIQUE ".into(),
+            (None, false) => String::new(),
+        },
+        guard,
+        quote(name),
+        table,
+        columns.join(", "),
+        match include.is_empty() {
+            true => String::new(),
+            false => format!(" INCLUDE ({})", include.join(", ")),
+        },
+        match index.tablespace {
+            Some(ref tablespace) => format!(" TABLESPACE {}", quote(tablespace)),
+            None => String::new(),
+        },
+        match index.where_clause {
+            Some(ref expr) => format!(" WHERE {}", expr),
+            None => String::new(),
+        }
+    )
+}
+
+/// A `CREATE POLICY` statement, with the name and table already quoted
+pub(crate) fn policy(name: &str, table: &str, policy: &Policy) -> String {
+    let mut sql = format!("CREATE POLICY {} ON {}", name, table);
+    if policy.restrictive {
+        sql.push_str(" AS RESTRICTIVE");
+    }
+    if let Some(command) = policy.command {
+        sql.push_str(&format!(" FOR {}", command.sql()));
+    }
+    if !policy.roles.is_empty() {
+        sql.push_str(&format!(" TO {}", policy.roles.join(", ")));
+    }
+    if let Some(ref expr) = policy.using {
+        sql.push_str(&format!(" USING ({})", expr));
+    }
+    if let Some(ref expr) = policy.check {
+        sql.push_str(&format!(" WITH CHECK ({})", expr));
+    }
+    sql
+}
+
+/// The `USING` clause which converts the values of a changed column, if any
+pub(crate) fn using(column: &Column) -> String {
+    match column.using {
+        Some(ref expr) => format!(" USING {}", expr),
+        None => String::new(),
+    }
+}
+
+/// The auto-incrementing columns of a table that start where the table does
+///
+/// Identity columns with a start of their own are left out.
+pub(crate) fn incrementing(t: &Table) -> Vec<(&str, &Column)> {
+    t.changes
+        .iter()
+        .filter_map(|change| match *change {
+            TableChange::AddColumn(ref name, ref c) => match (&c._type, c.identity) {
+                (_, Some(Identity { start: Some(_), .. })) => None,
+                (Type::Primary, _) | (Type::Serial, _) | (Type::BigSerial, _) | (_, Some(_)) => {
+                    Some((name.as_str(), c))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// The definition of a column that is added or changed
+pub(crate) fn column_of(change: &TableChange) -> Option<&Column> {
+    match *change {
+        TableChange::AddColumn(_, ref c) | TableChange::ChangeColumn(_, ref c, _) => Some(c),
+        _ => None,
+    }
+}
+
+/// The type of a column that a change renders, followed by the types
+/// of its elements if it's an array
+///
+/// Types are validated with all their elements, so that a backend
+/// never gets to render an element type it doesn't know.
+pub(crate) fn types_of(change: &TableChange) -> Vec<&Type> {
+    let mut next = match *change {
+        TableChange::SetNotNull(_, _, Some(ref c)) => Some(&c._type),
+        _ => column_of(change).map(|c| &c._type),
+    };
+
+    let mut types = Vec::new();
+    while let Some(t) = next {
+        types.push(t);
+        next = match *t {
+            Type::Array(ref inner) => Some(&**inner),
+            _ => None,
+        };
+    }
+    types
+}
+
+/// Reject column types and constraints which only exist in Postgres
+pub(crate) fn postgres_only(backend: &'static str, change: &TableChange) -> Result<(), Error> {
+    match *change {
+        TableChange::AddConstraint(_, Constraint::Exclusion { .. }) => {
+            return Err(Error::Unsupported(backend, "Exclusion constraints".into()))
+        }
+        TableChange::AddIndex(_, ref i) if i.tablespace.is_some() => {
+            return Err(Error::Unsupported(backend, "Tablespaces".into()))
+        }
+        _ => {}
+    }
+
+    types_of(change).into_iter().try_for_each(|t| match *t {
+        Type::TsVector | Type::TsQuery => {
+            Err(Error::Unsupported(backend, "Full-text search types".into()))
+        }
+        Type::Range(_) => Err(Error::Unsupported(backend, "Range types".into())),
+        _ => Ok(()),
+    })
+}
+
+/// Reject column and index options which only exist in MySQL
+pub(crate) fn mysql_only(backend: &'static str, change: &TableChange) -> Result<(), Error> {
+    if let TableChange::AddIndex(_, ref i) = *change {
+        if let Some(kind) = i.kind {
+            return Err(Error::Unsupported(
+                backend,
+                format!("{} indexes", kind.sql()),
+            ));
+        }
+    }
+
+    match column_of(change) {
+        Some(c) if c.charset.is_some() => {
+            Err(Error::Unsupported(backend, "Column character sets".into()))
+        }
+        Some(c) if c.on_update.is_some() => {
+            Err(Error::Unsupported(backend, "ON UPDATE expressions".into()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Reject nullability changes of columns whose definition isn't known
+pub(crate) fn needs_definition(backend: &'static str, change: &TableChange) -> Result<(), Error> {
+    match *change {
+        TableChange::SetNotNull(_, _, None) => Err(Error::Unsupported(
+            backend,
+            "Changing the nullability of a column whose definition isn't known".into(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// The type of an existing column without its keys, to declare it again
+///
+/// Primary and foreign keys are constraints of their own, which stay
+/// in place when only the type of the column is declared.
+pub(crate) fn base_type(t: &Type) -> Type {
+    match *t {
+        Type::Primary | Type::Serial | Type::Foreign(_) => Type::Integer,
+        Type::BigSerial => Type::BigInt,
+        ref t => t.clone(),
+    }
+}
+
+/// An existing column as it's declared again with a new nullability
+///
+/// Keys and `CHECK` constraints stay in place, so they're left out.
+pub(crate) fn redeclared(column: &Column, not_null: bool) -> Column {
+    Column {
+        _type: base_type(&column._type),
+        primary: false,
+        nullable: not_null,
+        checks: Vec::new(),
+        ..column.clone()
+    }
+}
+
+/// Reject table options which only exist in MySQL
+pub(crate) fn mysql_table_only(backend: &'static str, meta: &TableMeta) -> Result<(), Error> {
+    let options = [
+        &meta.engine,
+        &meta.charset,
+        &meta.collation,
+        &meta.row_format,
+    ];
+    if options.iter().any(|o| o.is_some()) {
+        return Err(Error::Unsupported(backend, "MySQL table options".into()));
+    }
+
+    match meta.partitions.is_empty() && meta.partition_count.is_none() {
+        true => Ok(()),
+        false => Err(Error::Unsupported(backend, "Partition definitions".into())),
+    }
+}
+
+/// Reject table options which only exist in Postgres
+pub(crate) fn postgres_table_only(backend: &'static str, meta: &TableMeta) -> Result<(), Error> {
+    match (meta.unlogged, meta.inherits.is_empty(), &meta.tablespace) {
+        (true, _, _) => Err(Error::Unsupported(backend, "Unlogged tables".into())),
+        (_, false, _) => Err(Error::Unsupported(backend, "Table inheritance".into())),
+        (_, _, Some(_)) => Err(Error::Unsupported(backend, "Tablespaces".into())),
+        _ if meta.row_level_security => {
+            Err(Error::Unsupported(backend, "Row-level security".into()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Reject storage parameters, which only Postgres and CockroachDB have
+pub(crate) fn no_storage_parameters(backend: &'static str, meta: &TableMeta) -> Result<(), Error> {
+    match meta.storage_parameters.is_empty() {
+        true => Ok(()),
+        false => Err(Error::Unsupported(backend, "Storage parameters".into())),
+    }
+}
+
+/// Storage parameters of a table, e.g. `fillfactor=70, autovacuum_enabled=false`
+pub(crate) fn storage_parameters(meta: &TableMeta) -> String {
+    let options: Vec<_> = meta
+        .storage_parameters
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect();
+    options.join(", ")
+}
+
+/// Reject table options which only exist in Sqlite
+pub(crate) fn sqlite_table_only(backend: &'static str, meta: &TableMeta) -> Result<(), Error> {
+    match (meta.without_rowid, meta.strict) {
+        (true, _) => Err(Error::Unsupported(backend, "WITHOUT ROWID tables".into())),
+        (_, true) => Err(Error::Unsupported(backend, "STRICT tables".into())),
+        _ => Ok(()),
+    }
+}
+
+/// Reject partitioned tables
+pub(crate) fn unpartitioned(backend: &'static str, meta: &TableMeta) -> Result<(), Error> {
+    match meta.partition_by {
+        Some(_) => Err(Error::Unsupported(backend, "Partitioned tables".into())),
+        None => Ok(()),
+    }
+}
+
+/// Reject tables that copy the columns of another table
+pub(crate) fn no_table_like(backend: &'static str, change: &DatabaseChange) -> Result<(), Error> {
+    match *change {
+        DatabaseChange::CreateTableLike(_, _) => {
+            Err(Error::Unsupported(backend, "CREATE TABLE LIKE".into()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Reject tables created from the results of a query
+pub(crate) fn no_table_as(backend: &'static str, change: &DatabaseChange) -> Result<(), Error> {
+    match *change {
+        DatabaseChange::CreateTableAs(_, _) => {
+            Err(Error::Unsupported(backend, "CREATE TABLE AS".into()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Reject row-level security policies, which only Postgres has
+pub(crate) fn no_policies(backend: &'static str, change: &DatabaseChange) -> Result<(), Error> {
+    match *change {
+        DatabaseChange::CreatePolicy(_, _, _) => Err(Error::Unsupported(
+            backend,
+            "Row-level security policies".into(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Reject renaming constraints, which only Postgres and CockroachDB can do
+pub(crate) fn no_constraint_renames(
+    backend: &'static str,
+    change: &TableChange,
+) -> Result<(), Error> {
+    match *change {
+        TableChange::RenameConstraint(_, _) => {
+            Err(Error::Unsupported(backend, "Renaming a constraint".into()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Reject renaming an index on its own, without its table
+pub(crate) fn no_index_renames(
+    backend: &'static str,
+    change: &DatabaseChange,
+) -> Result<(), Error> {
+    match *change {
+        DatabaseChange::RenameIndex(_, _) => {
+            Err(Error::Unsupported(backend, "Renaming an index".into()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Reject partitions, which only Postgres creates as tables of their own
+pub(crate) fn no_partitions(backend: &'static str, change: &DatabaseChange) -> Result<(), Error> {
+    match *change {
+        DatabaseChange::CreatePartition(_, _, _) => {
+            Err(Error::Unsupported(backend, "Creating a partition".into()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Reject times with more fractional second digits than a backend keeps
+pub(crate) fn fraction_digits(
+    backend: &'static str,
+    max: u8,
+    change: &TableChange,
+) -> Result<(), Error> {
+    types_of(change).into_iter().try_for_each(|t| match *t {
+        Type::Time(Some(p)) | Type::DateTime(Some(p)) | Type::TimestampTz(Some(p)) if p > max => {
+            Err(Error::Unsupported(
+                backend,
+                format!("More than {} fractional second digits", max),
+            ))
+        }
+        _ => Ok(()),
+    })
+}
+
+/// Reject storage options, for backends other than Postgres
+pub(crate) fn no_storage(backend: &'static str, change: &TableChange) -> Result<(), Error> {
+    match column_of(change) {
+        Some(c) if c.storage.is_some() || c.compression.is_some() => {
+            Err(Error::Unsupported(backend, "Column storage options".into()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Reject array columns, for backends which have no arrays
+pub(crate) fn no_arrays(backend: &'static str, change: &TableChange) -> Result<(), Error> {
+    match types_of(change).first() {
+        Some(&&Type::Array(_)) => Err(Error::Unsupported(backend, "Array types".into())),
+        _ => Ok(()),
+    }
+}
+
+/// Reject identity columns, for backends which only auto-increment
+pub(crate) fn no_identity(backend: &'static str, change: &TableChange) -> Result<(), Error> {
+    match column_of(change).and_then(|c| c.identity) {
+        Some(_) => Err(Error::Unsupported(backend, "Identity columns".into())),
+        None => Ok(()),
+    }
+}
+
+/// Reject deferrable constraints, for backends which always check right away
+pub(crate) fn no_deferrable(backend: &'static str, change: &TableChange) -> Result<(), Error> {
+    types_of(change).into_iter().try_for_each(|t| match *t {
+        Type::Foreign(ref fk) if fk.deferrable.is_some() => {
+            Err(Error::Unsupported(backend, "Deferrable constraints".into()))
+        }
+        _ => Ok(()),
+    })
+}
+
+/// The SQL of a `Type::CustomPer` for the first backend that has an entry
+pub(crate) fn custom_for(
+    types: &[(SqlVariant, String)],
+    variants: &[SqlVariant],
+) -> Option<String> {
+    variants
+        .iter()
+        .find_map(|v| types.iter().find(|(k, _)| k == v).map(|(_, t)| t.clone()))
+}
+
+/// Reject custom types without an entry for a backend
+pub(crate) fn custom_given(
+    backend: &'static str,
+    variants: &[SqlVariant],
+    change: &TableChange,
+) -> Result<(), Error> {
+    types_of(change).into_iter().try_for_each(|t| match *t {
+        Type::CustomPer(ref types) if custom_for(types, variants).is_none() => Err(
+            Error::Unsupported(backend, "A custom type without an entry for it".into()),
+        ),
+        _ => Ok(()),
+    })
+}
+
+/// Check if a statement starts with any of the given keywords
+pub(crate) fn starts_with_any(stmt: &str, keywords: &[&str]) -> bool {
+    let stmt = stmt.trim_start().to_uppercase();
+    keywords.iter().any(|k| stmt.starts_with(k))
+}
+
+/// A database backend that can be selected at runtime
+///
+/// This is useful for tools which only know the target database after
+/// reading their configuration. Pass it to `Migration::make_from`
+/// instead of calling `Migration::make` with a generator type.
+///
+/// Variants are only available when the matching feature is enabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SqlVariant {
+    #[cfg(feature = "pg")]
+    Pg,
+    #[cfg(feature = "mysql")]
+    MySql,
+    #[cfg(feature = "sqlite3")]
+    Sqlite,
+    #[cfg(feature = "mssql")]
+    MsSql,
+    #[cfg(feature = "cockroach")]
+    Cockroach,
+    #[cfg(feature = "ansi")]
+    Ansi,
+}
+
+impl SqlVariant {
+    /// Generate the SQL for a migration with the matching generator
+    #[allow(unused_variables)] // Without any backend enabled
+    pub(crate) fn run_for(self, migr: &Migration) -> Result<String, Error> {
+        self.statements_for(migr).map(|s| statement::join(&s))
+    }
+
+    /// Generate the statements of a migration with the matching generator
+    #[allow(unused_variables)] // Without any backend enabled
+    pub(crate) fn statements_for(self, migr: &Migration) -> Result<Vec<Statement>, Error> {
+        match self {
+            #[cfg(feature = "pg")]
+            SqlVariant::Pg => migr.try_statements_with(&Pg::default()),
+            #[cfg(feature = "mysql")]
+            SqlVariant::MySql => migr.try_statements_with(&MySql::default()),
+            #[cfg(feature = "sqlite3")]
+            SqlVariant::Sqlite => migr.try_statements_with(&Sqlite::default()),
+            #[cfg(feature = "mssql")]
+            SqlVariant::MsSql => migr.try_statements_with(&MsSql::default()),
+            #[cfg(feature = "cockroach")]
+            SqlVariant::Cockroach => migr.try_statements_with(&Cockroach::default()),
+            #[cfg(feature = "ansi")]
+            SqlVariant::Ansi => migr.try_statements_with(&Ansi),
+        }
+    }
+}
+
+impl FromStr for SqlVariant {
+    type Err = Error;
+
+    /// Parse a backend name, as it might appear in a config file
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            #[cfg(feature = "pg")]
+            "pg" | "postgres" | "postgresql" => Ok(SqlVariant::Pg),
+            #[cfg(feature = "mysql")]
+            "mysql" => Ok(SqlVariant::MySql),
+            #[cfg(feature = "sqlite3")]
+            "sqlite" | "sqlite3" => Ok(SqlVariant::Sqlite),
+            #[cfg(feature = "mssql")]
+            "mssql" | "sqlserver" => Ok(SqlVariant::MsSql),
+            #[cfg(feature = "cockroach")]
+            "cockroach" | "cockroachdb" => Ok(SqlVariant::Cockroach),
+            #[cfg(feature = "ansi")]
+            "ansi" => Ok(SqlVariant::Ansi),
+            _ => Err(Error::UnknownBackend(s.into())),
+        }
+    }
+}
 
 /// A generic SQL generator trait
+///
+/// Generators are used as values so that they can carry configuration,
+/// such as a schema to qualify table names with. All generators in
+/// this module implement `Default`, which gives you a generator with
+/// no special configuration.
 pub trait SqlGenerator {
-    
     /// Create a new table with a name
-    fn create_table(name: &str) -> String;
+    fn create_table(&self, name: &str) -> String;
 
     /// Create a new table with a name, only if it doesn't exist
-    fn create_table_if_not_exists(name: &str) -> String;
+    fn create_table_if_not_exists(&self, name: &str) -> String;
+
+    /// Create a new table of the kind its options ask for, such as a
+    /// temporary table, only if it doesn't exist if `guarded` is set
+    ///
+    /// Backends without special kinds of tables reject them in
+    /// `validate_table`, so by default a plain table is created.
+    fn create_table_from(&self, meta: &TableMeta, guarded: bool) -> String {
+        match guarded {
+            true => self.create_table_if_not_exists(&meta.name),
+            false => self.create_table(&meta.name),
+        }
+    }
 
-    /// Drop a table with a name 
-    fn drop_table(name: &str) -> String;
+    /// Drop a table with a name
+    fn drop_table(&self, name: &str) -> String;
+
+    /// Quote the name of a column, constraint or other object
+    ///
+    /// By default names are quoted as in standard SQL.
+    fn quote(&self, name: &str) -> String {
+        format!("\"{}\"", name)
+    }
+
+    /// Create a new table with the columns of an existing one, only
+    /// if it doesn't exist if `guarded` is set
+    ///
+    /// By default this is rendered in standard SQL.
+    fn create_table_like(&self, name: &str, source: &str, guarded: bool) -> String {
+        format!(
+            "CREATE TABLE {}{} (LIKE {})",
+            match guarded {
+                true => "IF NOT EXISTS ",
+                false => "",
+            },
+            self.quote(name),
+            self.quote(source)
+        )
+    }
+
+    /// Create a new table from the results of a query, only if it
+    /// doesn't exist if `guarded` is set
+    fn create_table_as(&self, name: &str, query: &str, guarded: bool) -> String {
+        format!(
+            "{} AS {}",
+            match guarded {
+                true => self.create_table_if_not_exists(name),
+                false => self.create_table(name),
+            },
+            query
+        )
+    }
+
+    /// Create a row-level security policy for a table
+    ///
+    /// Backends without policies reject them in `validate_change`.
+    fn create_policy(&self, name: &str, table: &str, p: &Policy) -> String {
+        policy(&self.quote(name), &self.quote(table), p)
+    }
+
+    /// Create a partition of a partitioned table, only if it
+    /// doesn't exist if `guarded` is set
+    ///
+    /// Backends without declarative partitions reject them in
+    /// `validate_change`. By default standard SQL names are used.
+    fn create_partition(&self, name: &str, parent: &str, bounds: &str, guarded: bool) -> String {
+        format!(
+            "CREATE TABLE {}{} PARTITION OF {} {}",
+            match guarded {
+                true => "IF NOT EXISTS ",
+                false => "",
+            },
+            self.quote(name),
+            self.quote(parent),
+            bounds
+        )
+    }
 
     /// Drop a table with a name, only if it exists
-    fn drop_table_if_exists(name: &str) -> String;
+    fn drop_table_if_exists(&self, name: &str) -> String;
 
     /// Rename a table from <old> to <new>
-    fn rename_table(old: &str, new: &str) -> String;
+    fn rename_table(&self, old: &str, new: &str) -> String;
 
     /// Modify a table in some other way
-    fn alter_table(name: &str) -> String;
-
+    fn alter_table(&self, name: &str) -> String;
 
     /// Create a new column with a type
-    fn add_column(ex: bool, name: &str, column: &Column) -> String;
+    fn add_column(&self, ex: bool, name: &str, column: &Column) -> String;
+
+    /// Create a new column in a table with the given options
+    ///
+    /// By default the options don't change how the column is rendered.
+    fn add_table_column(&self, _: &TableMeta, ex: bool, name: &str, column: &Column) -> String {
+        self.add_column(ex, name, column)
+    }
 
     /// Drop an existing column from the table
-    fn drop_column(name: &str) -> String;
+    fn drop_column(&self, name: &str) -> String;
+
+    /// Change the type of an existing column
+    fn change_column(&self, name: &str, column: &Column) -> String;
+
+    /// Set the default of an existing column to an expression, or drop it
+    ///
+    /// By default this is rendered in standard SQL.
+    fn set_default(&self, name: &str, expr: Option<&str>) -> String {
+        match expr {
+            Some(expr) => format!("ALTER COLUMN {} SET DEFAULT {}", self.quote(name), expr),
+            None => format!("ALTER COLUMN {} DROP DEFAULT", self.quote(name)),
+        }
+    }
+
+    /// Rename a constraint of an existing table
+    ///
+    /// Backends which can't rename constraints reject it in
+    /// `validate_table_change`.
+    fn rename_constraint(&self, old: &str, new: &str) -> String {
+        format!(
+            "RENAME CONSTRAINT {} TO {}",
+            self.quote(old),
+            self.quote(new)
+        )
+    }
+
+    /// Remove a named constraint from an existing table
+    ///
+    /// By default this is rendered in standard SQL.
+    fn drop_constraint(&self, name: &str) -> String {
+        format!("DROP CONSTRAINT {}", self.quote(name))
+    }
+
+    /// Rename an index
+    ///
+    /// Backends which can't rename indexes reject it in `validate_change`.
+    fn rename_index(&self, old: &str, new: &str) -> String {
+        format!(
+            "ALTER INDEX {} RENAME TO {}",
+            self.quote(old),
+            self.quote(new)
+        )
+    }
+
+    /// Make an existing column `NOT NULL`, or nullable again
+    ///
+    /// Backends which declare the column again get its definition, if
+    /// known. By default this is rendered in standard SQL.
+    fn set_not_null(&self, name: &str, not_null: bool, _: Option<&Column>) -> String {
+        format!(
+            "ALTER COLUMN {} {} NOT NULL",
+            self.quote(name),
+            match not_null {
+                true => "SET",
+                false => "DROP",
+            }
+        )
+    }
+
+    /// Add a constraint over columns of the table, with an optional name
+    fn add_constraint(&self, ex: bool, name: Option<&str>, c: &Constraint) -> String;
+
+    /// Create a named index on a table
+    fn create_index(&self, table: &str, name: &str, index: &Index) -> String;
+
+    /// Create a named index on a table, only if it doesn't exist
+    ///
+    /// Backends without such a guard create the index unconditionally.
+    fn create_index_if_not_exists(&self, table: &str, name: &str, index: &Index) -> String {
+        self.create_index(table, name, index)
+    }
+
+    /// Create a new column on an existing table, only if it doesn't exist
+    ///
+    /// Used by idempotent migrations. Backends without such a
+    /// guard add the column unconditionally.
+    fn add_column_if_not_exists(&self, name: &str, column: &Column) -> String {
+        self.add_column(true, name, column)
+    }
+
+    /// Drop an existing column, only if it exists
+    ///
+    /// Backends without such a guard drop the column unconditionally.
+    fn drop_column_if_exists(&self, name: &str) -> String {
+        self.drop_column(name)
+    }
+
+    /// Insert a row of values into a table
+    fn insert(&self, table: &str, row: &[(String, Value)]) -> String;
+
+    /// Render a value as a literal
+    ///
+    /// By default values are rendered as in standard SQL.
+    fn value(&self, value: &Value) -> String {
+        value.literal()
+    }
+
+    /// Start a transaction
+    fn begin_transaction(&self) -> String {
+        "BEGIN".into()
+    }
+
+    /// Commit a transaction
+    fn commit_transaction(&self) -> String {
+        "COMMIT".into()
+    }
+
+    /// Drop a type which columns were declared with, guarded with
+    /// `IF EXISTS` if `guarded` is set
+    ///
+    /// Backends which don't create types in `prepare_column` return
+    /// `None`, which is the default.
+    fn drop_type(&self, _name: &str, _guarded: bool) -> Option<String> {
+        None
+    }
+
+    /// Check whether tables can be created and dropped with
+    /// `IF [NOT] EXISTS` guards
+    ///
+    /// Idempotent migrations create and drop tables unconditionally on
+    /// backends which can't. By default all can.
+    fn table_guards(&self) -> bool {
+        true
+    }
+
+    /// Check whether a change to an existing table is made by creating
+    /// the table again, instead of with `ALTER TABLE`
+    ///
+    /// The rows are then copied into a new table with the changed
+    /// columns, which replaces the old one. This needs the definition
+    /// of the table (see `Schema::complete`). By default no change is.
+    fn rebuilds_table(&self, _: &TableChange) -> bool {
+        false
+    }
+
+    /// Check whether a statement can run inside a transaction
+    ///
+    /// Statements which can't are emitted after the transaction by
+    /// `Migration::make_transactional`. By default all statements can.
+    fn transactional(&self, _stmt: &str) -> bool {
+        true
+    }
 
     /// Rename an existing column
-    fn rename_column(old: &str, new: &str) -> String;
+    fn rename_column(&self, old: &str, new: &str) -> String;
+
+    /// Rename a column of a table in a statement of its own
+    ///
+    /// By default the column is renamed in an `ALTER TABLE`.
+    fn rename_table_column(&self, table: &str, old: &str, new: &str) -> String {
+        format!(
+            "{} {}",
+            self.alter_table(table),
+            self.rename_column(old, new)
+        )
+    }
+
+    /// Statements which need to run before a column can be added,
+    /// such as creating the type or extension it uses
+    ///
+    /// They are emitted ahead of the table that the column is added to.
+    /// By default no statements are needed.
+    fn prepare_column(&self, _: &Column) -> Vec<(Operation, String)> {
+        Vec::new()
+    }
+
+    /// Options that follow the columns of a new table
+    ///
+    /// By default there are none.
+    fn table_options(&self, _: &TableMeta) -> String {
+        String::new()
+    }
+
+    /// Statements which need to run after a new table is created,
+    /// such as setting the start of its sequence
+    ///
+    /// By default no statements are needed.
+    fn finish_table(&self, _: &Table) -> Vec<(Operation, String)> {
+        Vec::new()
+    }
+
+    /// Statements which change the options of an existing table,
+    /// such as its comment
+    ///
+    /// By default no statements are needed.
+    fn alter_table_options(&self, _: &TableMeta) -> Vec<(Operation, String)> {
+        Vec::new()
+    }
+
+    /// Check that a change on the database can be expressed by this backend
+    ///
+    /// Generators that can't express certain operations should return an
+    /// error here instead of generating invalid SQL. By default all changes
+    /// are accepted.
+    fn validate_change(&self, _: &DatabaseChange) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Check that the options of a table can be expressed by this backend
+    ///
+    /// Unlike `validate_change` this sees options that were set
+    /// in the callback of the table.
+    fn validate_table(&self, _: &TableMeta) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Check that a change on a table (including column types)
+    /// can be expressed by this backend
+    fn validate_table_change(&self, _: &TableChange) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Check that a change can be made to an existing table,
+    /// in addition to `validate_table_change`
+    ///
+    /// The definition of the table before the change is passed along
+    /// if it's known.
+    fn validate_alter_table(&self, _: Option<&TableSchema>, _: &TableChange) -> Result<(), Error> {
+        Ok(())
+    }
 }
@@ -0,0 +1,394 @@
+//! MSSQL (SQL Server) implementation of a generator
+//!
+//! This module generates T-SQL strings. Identifiers are quoted with
+//! `[brackets]`, text is stored as unicode (`NVARCHAR`) and primary
+//! keys use `IDENTITY(1,1)`. Because older SQL Server versions have no
+//! `IF [NOT] EXISTS` on tables, the conditional variants are guarded
+//! with `OBJECT_ID(...)` checks instead.
+
+use crate::ReferentialAction;
+
+use super::{
+    base_type, bytes, checks, collate, column_of, constraint, custom_for, custom_given, enum_check,
+    fraction_digits, identity_type, incrementing, index, insert, longest, mysql_only,
+    mysql_table_only, needs_definition, no_arrays, no_constraint_renames, no_deferrable,
+    no_index_renames, no_partitions, no_policies, no_storage, no_storage_parameters, no_table_as,
+    no_table_like, postgres_only, postgres_table_only, precision, sqlite_table_only,
+    starts_with_any, types_of, unpartitioned, Column, Constraint, DatabaseChange, Error, Generated,
+    Index, Operation, SqlGenerator, SqlVariant, Table, TableChange, TableMeta, Type, Value,
+};
+
+/// We call this struct MsSql after the `mssql` feature it's hidden behind
+///
+/// Tables are created in the default schema of the user, usually `dbo`.
+/// Use `MsSql::default().schema("...")` to qualify table names instead.
+#[derive(Clone, Debug, Default)]
+pub struct MsSql {
+    schema: Option<String>,
+}
+
+impl SqlGenerator for MsSql {
+    fn create_table(&self, name: &str) -> String {
+        format!("CREATE TABLE {}", self.table(name))
+    }
+
+    fn create_table_if_not_exists(&self, name: &str) -> String {
+        format!(
+            "IF OBJECT_ID(N'{}', N'U') IS NULL CREATE TABLE {}",
+            self.object(name),
+            self.table(name)
+        )
+    }
+
+    fn drop_table(&self, name: &str) -> String {
+        format!("DROP TABLE {}", self.table(name))
+    }
+
+    fn quote(&self, name: &str) -> String {
+        format!("[{}]", name)
+    }
+
+    fn drop_table_if_exists(&self, name: &str) -> String {
+        format!(
+            "IF OBJECT_ID(N'{}', N'U') IS NOT NULL DROP TABLE {}",
+            self.object(name),
+            self.table(name)
+        )
+    }
+
+    fn rename_table(&self, old: &str, new: &str) -> String {
+        format!("EXEC sp_rename N'{}', N'{}'", self.object(old), new)
+    }
+
+    fn alter_table(&self, name: &str) -> String {
+        format!("ALTER TABLE {}", self.table(name))
+    }
+
+    fn add_column(&self, ex: bool, name: &str, column: &Column) -> String {
+        // Computed columns take their type from the expression
+        if let Some((ref expr, kind)) = column.generated {
+            return format!(
+                "{}[{}] AS ({}){}",
+                MsSql::prefix(ex),
+                name,
+                expr,
+                match kind {
+                    Generated::Stored => " PERSISTED",
+                    Generated::Virtual => "",
+                }
+            );
+        }
+
+        format!(
+            "{}[{}] {}{}{}{}{}{}{}",
+            MsSql::prefix(ex),
+            name,
+            match column.identity {
+                Some(ref id) => identity_type(
+                    &column._type,
+                    format!(
+                        "IDENTITY({},{})",
+                        id.start.unwrap_or(1),
+                        id.increment.unwrap_or(1)
+                    ),
+                    MsSql::print_type
+                ),
+                None => MsSql::print_type(column._type.clone()),
+            },
+            collate(column, |c| c.to_string()),
+            match column.def {
+                Some(ref m) => format!(" DEFAULT {}", m.sql()),
+                _ => String::new(),
+            },
+            match column.nullable {
+                true => " NOT NULL",
+                false => "",
+            },
+            match column.primary {
+                true => " PRIMARY KEY",
+                false => "",
+            },
+            enum_check(self, format!("[{}]", name), &column._type),
+            checks(column, |c| format!("[{}]", c))
+        )
+    }
+
+    fn change_column(&self, name: &str, column: &Column) -> String {
+        format!(
+            "ALTER COLUMN [{}] {}{}",
+            name,
+            MsSql::print_type(base_type(&column._type)),
+            match column.nullable {
+                true => " NOT NULL",
+                false => "",
+            }
+        )
+    }
+
+    /// Defaults are constraints in SQL Server, which can only be
+    /// dropped by their generated name
+    fn set_default(&self, name: &str, expr: Option<&str>) -> String {
+        match expr {
+            Some(expr) => format!("ADD DEFAULT {} FOR {}", expr, self.quote(name)),
+            None => panic!(
+                "{}",
+                Error::Unsupported("MSSQL", "Dropping a default".into())
+            ),
+        }
+    }
+
+    /// The column is declared again with its type, which is validated
+    /// to be known
+    fn set_not_null(&self, name: &str, not_null: bool, column: Option<&Column>) -> String {
+        format!(
+            "ALTER COLUMN {}{} {}NULL",
+            self.quote(name),
+            match column {
+                Some(c) => format!(" {}", MsSql::print_type(base_type(&c._type))),
+                None => String::new(),
+            },
+            match not_null {
+                true => "NOT ",
+                false => "",
+            }
+        )
+    }
+
+    fn drop_column(&self, name: &str) -> String {
+        format!("DROP COLUMN [{}]", name)
+    }
+
+    fn add_constraint(&self, ex: bool, name: Option<&str>, c: &Constraint) -> String {
+        constraint(ex, name, c, |c| format!("[{}]", c))
+    }
+
+    fn create_index(&self, table: &str, name: &str, i: &Index) -> String {
+        index("", name, &self.table(table), i, |c| format!("[{}]", c))
+    }
+
+    fn drop_column_if_exists(&self, name: &str) -> String {
+        format!("DROP COLUMN IF EXISTS [{}]", name)
+    }
+
+    fn insert(&self, table: &str, row: &[(String, Value)]) -> String {
+        insert(self, self.table(table), row, |c| format!("[{}]", c))
+    }
+
+    /// Text is inserted as unicode and booleans are `BIT`s
+    fn value(&self, value: &Value) -> String {
+        match *value {
+            Value::Text(ref t) => format!("N{}", Value::quote(t)),
+            Value::Boolean(b) => (b as i64).to_string(),
+            _ => value.literal(),
+        }
+    }
+
+    fn begin_transaction(&self) -> String {
+        "BEGIN TRANSACTION".into()
+    }
+
+    fn commit_transaction(&self) -> String {
+        "COMMIT TRANSACTION".into()
+    }
+
+    /// The first row of a new table gets the value an identity is reseeded to
+    fn finish_table(&self, t: &Table) -> Vec<(Operation, String)> {
+        match (t.meta.auto_increment_start, incrementing(t).is_empty()) {
+            (Some(start), false) => vec![(
+                Operation::SetSequence,
+                format!(
+                    "DBCC CHECKIDENT ('{}', RESEED, {})",
+                    self.object(&t.meta.name),
+                    start
+                ),
+            )],
+            _ => Vec::new(),
+        }
+    }
+
+    fn transactional(&self, stmt: &str) -> bool {
+        !starts_with_any(
+            stmt,
+            &[
+                "CREATE DATABASE",
+                "ALTER DATABASE",
+                "DROP DATABASE",
+                "CREATE FULLTEXT",
+                "ALTER FULLTEXT",
+                "DROP FULLTEXT",
+            ],
+        )
+    }
+
+    /// Columns in SQL Server are renamed via `sp_rename`, which can't be
+    /// part of an `ALTER TABLE`. Without a table the column is looked up
+    /// by its name alone, see `rename_table_column`.
+    fn rename_column(&self, old: &str, new: &str) -> String {
+        format!("EXEC sp_rename N'{}', N'{}', N'COLUMN'", old, new)
+    }
+
+    fn rename_table_column(&self, table: &str, old: &str, new: &str) -> String {
+        self.rename_column(&format!("{}.{}", self.object(table), old), new)
+    }
+
+    fn validate_table(&self, meta: &TableMeta) -> Result<(), Error> {
+        mysql_table_only("MSSQL", meta)?;
+        no_storage_parameters("MSSQL", meta)?;
+        postgres_table_only("MSSQL", meta)?;
+        sqlite_table_only("MSSQL", meta)?;
+        unpartitioned("MSSQL", meta)?;
+        match (&meta.comment, meta.temporary) {
+            (Some(_), _) => Err(Error::Unsupported("MSSQL", "Table comments".into())),
+            (_, true) => Err(Error::Unsupported("MSSQL", "Temporary tables".into())),
+            _ => Ok(()),
+        }
+    }
+
+    fn validate_change(&self, change: &DatabaseChange) -> Result<(), Error> {
+        no_policies("MSSQL", change)?;
+        no_index_renames("MSSQL", change)?;
+        no_partitions("MSSQL", change)?;
+        no_table_like("MSSQL", change)?;
+        no_table_as("MSSQL", change)
+    }
+
+    fn validate_table_change(&self, change: &TableChange) -> Result<(), Error> {
+        custom_given("MSSQL", &[SqlVariant::MsSql], change)?;
+        postgres_only("MSSQL", change)?;
+        no_constraint_renames("MSSQL", change)?;
+        needs_definition("MSSQL", change)?;
+        match *change {
+            TableChange::ChangeColumn(_, ref c, _) if c.using.is_some() => {
+                return Err(Error::Unsupported("MSSQL", "USING expressions".into()))
+            }
+            TableChange::SetDefault(_, None) => {
+                return Err(Error::Unsupported("MSSQL", "Dropping a default".into()))
+            }
+            _ => {}
+        }
+        mysql_only("MSSQL", change)?;
+        no_deferrable("MSSQL", change)?;
+        no_storage("MSSQL", change)?;
+        fraction_digits("MSSQL", 7, change)?;
+        no_arrays("MSSQL", change)?;
+        if let TableChange::AddIndex(_, ref i) = *change {
+            if i.expression.is_some() {
+                return Err(Error::Unsupported("MSSQL", "Expression indexes".into()));
+            }
+        }
+
+        if types_of(change).iter().any(|t| matches!(t, Type::Set(_))) {
+            return Err(Error::Unsupported("MSSQL", "SET types".into()));
+        }
+
+        match column_of(change) {
+            Some(c) if c.unsigned => Err(Error::Unsupported("MSSQL", "Unsigned integers".into())),
+            Some(&Column {
+                _type: Type::Foreign(ref fk),
+                ..
+            }) if [fk.on_delete, fk.on_update].contains(&Some(ReferentialAction::Restrict)) => Err(
+                Error::Unsupported("MSSQL", "RESTRICT referential actions".into()),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl MsSql {
+    /// Qualify all table names with a schema
+    pub fn schema<S: Into<String>>(self, schema: S) -> Self {
+        MsSql {
+            schema: Some(schema.into()),
+        }
+    }
+
+    /// A quoted table name, including the schema if one was set
+    fn table(&self, name: &str) -> String {
+        match self.schema {
+            Some(ref schema) => format!("[{}].[{}]", schema, name),
+            None => format!("[{}]", name),
+        }
+    }
+
+    /// The (unquoted) name of a table, as used by `OBJECT_ID` and `sp_rename`
+    fn object(&self, name: &str) -> String {
+        match self.schema {
+            Some(ref schema) => format!("{}.{}", schema, name),
+            None => name.to_string(),
+        }
+    }
+
+    fn prefix(ex: bool) -> String {
+        match ex {
+            true => "ADD ".into(),
+            false => String::new(),
+        }
+    }
+
+    fn print_type(t: Type) -> String {
+        use crate::Type::*;
+        match t {
+            Primary => "INT IDENTITY(1,1) PRIMARY KEY".into(),
+            Text => "NVARCHAR(MAX)".into(),
+            TinyText => "NVARCHAR(255)".into(),
+            MediumText | LongText => "NVARCHAR(MAX)".into(),
+            Varchar(l) => match l {
+                0 => "NVARCHAR(MAX)".into(), // For "0" remove the limit
+                _ => format!("NVARCHAR({})", l),
+            },
+            Char(l) => format!("NCHAR({})", l),
+            Citext => "NVARCHAR(MAX) COLLATE Latin1_General_CI_AS".into(),
+            Integer => "INT".into(),
+            SmallInt => "SMALLINT".into(),
+            BigInt => "BIGINT".into(),
+            Serial => "INT IDENTITY(1,1)".into(),
+            BigSerial => "BIGINT IDENTITY(1,1)".into(),
+            Float => "REAL".into(),
+            Double => "FLOAT".into(),
+            Boolean => "BIT".into(),
+            /* BIT is a boolean, store the bits as bytes instead */
+            Bit(l) => format!("BINARY({})", bytes(l)),
+            Varbit(0) => "VARBINARY(MAX)".into(),
+            Varbit(l) => format!("VARBINARY({})", bytes(l)),
+            Binary | Varbinary(0) => "VARBINARY(MAX)".into(),
+            Varbinary(l) => format!("VARBINARY({})", l),
+            Uuid => "UNIQUEIDENTIFIER".into(),
+            Inet | Cidr => "NVARCHAR(43)".into(),
+            MacAddr => "NVARCHAR(17)".into(),
+            Json | Jsonb => "NVARCHAR(MAX)".into(),
+            Hstore => "NVARCHAR(MAX)".into(),
+            TsVector | TsQuery => panic!("MSSQL does not support full-text search types"),
+            Xml => "XML".into(),
+            Date => "DATE".into(),
+            Time(p) => precision("TIME", p),
+            DateTime(p) => precision("DATETIME2", p),
+            TimestampTz(p) => precision("DATETIMEOFFSET", p),
+            Interval(_) => "BIGINT".into(),
+            Decimal(p, s) => format!("DECIMAL({}, {})", p, s),
+            Range(_) => panic!("MSSQL does not support range types"),
+            Money => "MONEY".into(),
+            Point => "GEOMETRY".into(),
+            /* The shape and SRID are part of the values */
+            #[cfg(feature = "postgis")]
+            Geometry(_, _) => "GEOMETRY".into(),
+            #[cfg(feature = "postgis")]
+            Geography(_, _) => "GEOGRAPHY".into(),
+            Enum(_, values) => format!("NVARCHAR({})", longest(&values)),
+            Set(_) => panic!("MSSQL does not support SET types"),
+            Custom(t) => t,
+            CustomPer(types) => custom_for(&types, &[SqlVariant::MsSql])
+                .expect("Custom type without an entry for MSSQL"),
+            Foreign(fk) => format!(
+                "INT {}REFERENCES [{}]([{}]){}",
+                match fk.name {
+                    Some(ref n) => format!("CONSTRAINT [{}] ", n),
+                    None => String::new(),
+                },
+                fk.table,
+                fk.target(),
+                fk.actions()
+            ),
+            Array(_) => panic!("MSSQL does not support array types"),
+        }
+    }
+}
@@ -0,0 +1,387 @@
+//! MySQL implementation of a generator
+//!
+//! This module generates strings that are specific to MySQL
+//! databases. Identifiers are quoted with backticks and primary
+//! keys are created with `AUTO_INCREMENT`. Tables are created with
+//! the storage engine and charset defaults of the server.
+
+use super::{
+    base_type, checks, collate, constraint, create_kind, custom_for, custom_given, fraction_digits,
+    generated, index, insert, needs_definition, no_arrays, no_constraint_renames, no_deferrable,
+    no_identity, no_index_renames, no_partitions, no_policies, no_storage, no_storage_parameters,
+    postgres_only, postgres_table_only, precision, redeclared, sqlite_table_only, value_list,
+    Column, Constraint, DatabaseChange, Error, Index, Operation, SqlGenerator, SqlVariant,
+    TableChange, TableMeta, Type, Value,
+};
+
+/// We call this struct MySql to be consistent with the naming
+/// of the MySQL project itself
+///
+/// Use `MySql::default().database("...")` to qualify all table names
+/// with a database name.
+#[derive(Clone, Debug, Default)]
+pub struct MySql {
+    database: Option<String>,
+}
+
+impl SqlGenerator for MySql {
+    fn create_table(&self, name: &str) -> String {
+        format!("CREATE TABLE {}", self.table(name))
+    }
+
+    fn create_table_if_not_exists(&self, name: &str) -> String {
+        format!("CREATE TABLE IF NOT EXISTS {}", self.table(name))
+    }
+
+    fn create_table_from(&self, meta: &TableMeta, guarded: bool) -> String {
+        format!("{}{}", create_kind(meta, guarded), self.table(&meta.name))
+    }
+
+    fn create_table_like(&self, name: &str, source: &str, guarded: bool) -> String {
+        format!(
+            "{} LIKE {}",
+            match guarded {
+                true => self.create_table_if_not_exists(name),
+                false => self.create_table(name),
+            },
+            self.table(source)
+        )
+    }
+
+    fn drop_table(&self, name: &str) -> String {
+        format!("DROP TABLE {}", self.table(name))
+    }
+
+    fn quote(&self, name: &str) -> String {
+        format!("`{}`", name)
+    }
+
+    fn drop_table_if_exists(&self, name: &str) -> String {
+        format!("DROP TABLE IF EXISTS {}", self.table(name))
+    }
+
+    fn rename_table(&self, old: &str, new: &str) -> String {
+        format!("RENAME TABLE {} TO {}", self.table(old), self.table(new))
+    }
+
+    fn alter_table(&self, name: &str) -> String {
+        format!("ALTER TABLE {}", self.table(name))
+    }
+
+    fn add_column(&self, ex: bool, name: &str, column: &Column) -> String {
+        format!(
+            "{}`{}` {}{}{}{}{}{}{}{}{}{}{}",
+            MySql::prefix(ex),
+            name,
+            MySql::print_type(column._type.clone()),
+            match column.charset {
+                Some(ref c) => format!(" CHARACTER SET {}", c),
+                None => String::new(),
+            },
+            collate(column, |c| c.to_string()),
+            match column.unsigned {
+                true => " UNSIGNED",
+                false => "",
+            },
+            generated(column),
+            match column.def {
+                Some(ref m) => format!(" DEFAULT {}", m.sql()),
+                _ => String::new(),
+            },
+            match column.on_update {
+                Some(ref expr) => format!(" ON UPDATE {}", expr),
+                None => String::new(),
+            },
+            match column.nullable {
+                true => " NOT NULL",
+                false => "",
+            },
+            match column.primary {
+                true => " PRIMARY KEY",
+                false => "",
+            },
+            checks(column, |c| format!("`{}`", c)),
+            MySql::foreign_key(ex, name, &column._type)
+        )
+    }
+
+    /// The whole definition of the column is replaced, apart from its keys
+    fn change_column(&self, name: &str, column: &Column) -> String {
+        let column = Column {
+            _type: base_type(&column._type),
+            primary: false,
+            ..column.clone()
+        };
+        format!("MODIFY COLUMN {}", self.add_column(false, name, &column))
+    }
+
+    fn set_default(&self, name: &str, expr: Option<&str>) -> String {
+        match expr {
+            Some(expr) => format!("ALTER COLUMN {} SET DEFAULT {}", self.quote(name), expr),
+            None => format!("ALTER COLUMN {} DROP DEFAULT", self.quote(name)),
+        }
+    }
+
+    /// The whole column is declared again, which is validated to be
+    /// known, so that its default and other attributes are kept
+    fn set_not_null(&self, name: &str, not_null: bool, column: Option<&Column>) -> String {
+        match column {
+            Some(column) => format!(
+                "MODIFY COLUMN {}",
+                self.add_column(false, name, &redeclared(column, not_null))
+            ),
+            None => format!(
+                "MODIFY COLUMN {} {}NULL",
+                self.quote(name),
+                match not_null {
+                    true => "NOT ",
+                    false => "",
+                }
+            ),
+        }
+    }
+
+    fn drop_column(&self, name: &str) -> String {
+        format!("DROP COLUMN `{}`", name)
+    }
+
+    fn add_constraint(&self, ex: bool, name: Option<&str>, c: &Constraint) -> String {
+        constraint(ex, name, c, |c| format!("`{}`", c))
+    }
+
+    fn create_index(&self, table: &str, name: &str, i: &Index) -> String {
+        /* Functional key parts need their own parentheses */
+        let mut i = i.clone();
+        i.expression = i.expression.map(|expr| format!("({})", expr));
+        index("", name, &self.table(table), &i, |c| format!("`{}`", c))
+    }
+
+    fn insert(&self, table: &str, row: &[(String, Value)]) -> String {
+        match row.is_empty() {
+            true => format!("INSERT INTO {} () VALUES ()", self.table(table)),
+            false => insert(self, self.table(table), row, |c| format!("`{}`", c)),
+        }
+    }
+
+    /// Backslashes start escape sequences in MySQL strings
+    fn value(&self, value: &Value) -> String {
+        match *value {
+            Value::Text(ref t) => Value::quote(&t.replace('\\', "\\\\")),
+            _ => value.literal(),
+        }
+    }
+
+    fn begin_transaction(&self) -> String {
+        "START TRANSACTION".into()
+    }
+
+    fn rename_column(&self, old: &str, new: &str) -> String {
+        format!("RENAME COLUMN `{}` TO `{}`", old, new)
+    }
+
+    fn table_options(&self, meta: &TableMeta) -> String {
+        let mut options = String::new();
+        if let Some(ref engine) = meta.engine {
+            options.push_str(&format!(" ENGINE = {}", engine));
+        }
+
+        if let Some(ref charset) = meta.charset {
+            options.push_str(&format!(" DEFAULT CHARSET = {}", charset));
+        }
+
+        if let Some(ref collation) = meta.collation {
+            options.push_str(&format!(" COLLATE = {}", collation));
+        }
+
+        if let Some(ref format) = meta.row_format {
+            options.push_str(&format!(" ROW_FORMAT = {}", format));
+        }
+
+        if let Some(start) = meta.auto_increment_start {
+            options.push_str(&format!(" AUTO_INCREMENT = {}", start));
+        }
+
+        if let Some(ref comment) = meta.comment {
+            options.push_str(&format!(" COMMENT = {}", Value::quote(comment)));
+        }
+
+        if let Some((kind, ref columns)) = meta.partition_by {
+            let columns: Vec<_> = columns.iter().map(|c| format!("`{}`", c)).collect();
+            options.push_str(&format!(
+                " PARTITION BY {} ({})",
+                kind.sql(),
+                columns.join(", ")
+            ));
+        }
+
+        if let Some(count) = meta.partition_count {
+            options.push_str(&format!(" PARTITIONS {}", count));
+        }
+
+        if !meta.partitions.is_empty() {
+            let partitions: Vec<_> = meta
+                .partitions
+                .iter()
+                .map(|(name, values)| format!("PARTITION `{}` {}", name, values))
+                .collect();
+            options.push_str(&format!(" ({})", partitions.join(", ")));
+        }
+
+        options
+    }
+
+    fn alter_table_options(&self, meta: &TableMeta) -> Vec<(Operation, String)> {
+        match self.table_options(meta) {
+            options if options.is_empty() => Vec::new(),
+            options => vec![(
+                Operation::SetOptions,
+                format!("ALTER TABLE {}{}", self.table(&meta.name), options),
+            )],
+        }
+    }
+
+    fn validate_table(&self, meta: &TableMeta) -> Result<(), Error> {
+        no_storage_parameters("MySQL", meta)?;
+        postgres_table_only("MySQL", meta)?;
+        sqlite_table_only("MySQL", meta)
+    }
+
+    fn validate_change(&self, change: &DatabaseChange) -> Result<(), Error> {
+        no_policies("MySQL", change)?;
+        no_index_renames("MySQL", change)?;
+        no_partitions("MySQL", change)
+    }
+
+    fn validate_table_change(&self, change: &TableChange) -> Result<(), Error> {
+        custom_given("MySQL", &[SqlVariant::MySql], change)?;
+        postgres_only("MySQL", change)?;
+        no_constraint_renames("MySQL", change)?;
+        needs_definition("MySQL", change)?;
+        if let TableChange::ChangeColumn(_, ref c, _) = *change {
+            if c.using.is_some() {
+                return Err(Error::Unsupported("MySQL", "USING expressions".into()));
+            }
+        }
+        no_identity("MySQL", change)?;
+        no_deferrable("MySQL", change)?;
+        no_storage("MySQL", change)?;
+        fraction_digits("MySQL", 6, change)?;
+        no_arrays("MySQL", change)?;
+        match *change {
+            TableChange::AddIndex(_, ref i) if i.where_clause.is_some() => {
+                Err(Error::Unsupported("MySQL", "Partial indexes".into()))
+            }
+            TableChange::AddIndex(_, ref i) if !i.include.is_empty() => {
+                Err(Error::Unsupported("MySQL", "INCLUDE columns".into()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl MySql {
+    /// Qualify all table names with a database
+    pub fn database<S: Into<String>>(self, database: S) -> Self {
+        MySql {
+            database: Some(database.into()),
+        }
+    }
+
+    fn table(&self, name: &str) -> String {
+        match self.database {
+            Some(ref db) => format!("`{}`.`{}`", db, name),
+            None => format!("`{}`", name),
+        }
+    }
+
+    fn prefix(ex: bool) -> String {
+        match ex {
+            true => "ADD COLUMN ".into(),
+            false => String::new(),
+        }
+    }
+
+    /// InnoDB ignores inline `REFERENCES` on a column, so foreign
+    /// keys need to be declared as a separate constraint
+    fn foreign_key(ex: bool, name: &str, t: &Type) -> String {
+        match t {
+            Type::Foreign(fk) => format!(
+                ", {}{}FOREIGN KEY (`{}`) REFERENCES `{}`(`{}`){}",
+                match ex {
+                    true => "ADD ",
+                    false => "",
+                },
+                match fk.name {
+                    Some(ref n) => format!("CONSTRAINT `{}` ", n),
+                    None => String::new(),
+                },
+                name,
+                fk.table,
+                fk.target(),
+                fk.actions()
+            ),
+            _ => String::new(),
+        }
+    }
+
+    fn print_type(t: Type) -> String {
+        use crate::Type::*;
+        match t {
+            Primary => "INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY".into(),
+            Text => "TEXT".into(),
+            TinyText => "TINYTEXT".into(),
+            MediumText => "MEDIUMTEXT".into(),
+            LongText => "LONGTEXT".into(),
+            Varchar(l) => match l {
+                0 => "TEXT".into(), // MySQL has no unbounded VARCHAR
+                _ => format!("VARCHAR({})", l),
+            },
+            Char(l) => format!("CHAR({})", l),
+            Citext => "VARCHAR(255) COLLATE utf8mb4_unicode_ci".into(),
+            Integer => "INTEGER".into(),
+            SmallInt => "SMALLINT".into(),
+            BigInt => "BIGINT".into(),
+            /* Auto-increment columns need to be a key */
+            Serial => "INTEGER NOT NULL AUTO_INCREMENT UNIQUE".into(),
+            BigSerial => "BIGINT NOT NULL AUTO_INCREMENT UNIQUE".into(),
+            Float => "FLOAT".into(),
+            Double => "DOUBLE".into(),
+            Boolean => "BOOLEAN".into(),
+            /* MySQL only has fixed-length bit fields, of up to 64 bits */
+            Bit(l) | Varbit(l) if l > 0 => format!("BIT({})", l),
+            Bit(_) | Varbit(_) => "BIT(64)".into(),
+            Binary | Varbinary(0) => "BLOB".into(),
+            Varbinary(l) => format!("VARBINARY({})", l),
+            Uuid => "CHAR(36)".into(),
+            /* Long enough for an IPv6 address with a netmask */
+            Inet | Cidr => "VARCHAR(43)".into(),
+            MacAddr => "VARCHAR(17)".into(),
+            Json | Jsonb => "JSON".into(),
+            Hstore => "JSON".into(),
+            TsVector | TsQuery => panic!("MySQL does not support full-text search types"),
+            Xml => "TEXT".into(),
+            Date => "DATE".into(),
+            Time(p) => precision("TIME", p),
+            DateTime(p) => precision("DATETIME", p),
+            /* Converted to UTC for storage */
+            TimestampTz(p) => precision("TIMESTAMP", p),
+            Interval(_) => "BIGINT".into(),
+            Decimal(p, s) => format!("DECIMAL({}, {})", p, s),
+            Range(_) => panic!("MySQL does not support range types"),
+            Money => "DECIMAL(19, 4)".into(),
+            Point => "POINT".into(),
+            #[cfg(feature = "postgis")]
+            Geometry(kind, srid) | Geography(kind, srid) => match srid {
+                Some(srid) => format!("{} SRID {}", kind.name(), srid),
+                None => kind.name().into(),
+            },
+            Enum(_, values) => format!("ENUM({})", value_list(&MySql::default(), &values)),
+            Set(values) => format!("SET({})", value_list(&MySql::default(), &values)),
+            Custom(t) => t,
+            CustomPer(types) => custom_for(&types, &[SqlVariant::MySql])
+                .expect("Custom type without an entry for MySQL"),
+            Foreign(_) => "INTEGER".into(),
+            Array(_) => panic!("MySQL does not support array types"),
+        }
+    }
+}
@@ -3,99 +3,481 @@
 //! This module generates strings that are specific to Postgres
 //! databases. They should be thoroughly tested via unit testing
 
-use super::{Column, SqlGenerator, Type};
+use crate::statement::Operation;
+#[cfg(feature = "postgis")]
+use crate::GeometryKind;
+use crate::RangeKind;
+
+use super::{
+    base_type, checks, collate, column_of, constraint, create_kind, custom_for, custom_given,
+    fraction_digits, generated, identity_type, incrementing, index, insert, mysql_only,
+    mysql_table_only, policy, precision, sqlite_table_only, starts_with_any, storage_parameters,
+    types_of, using, value_list, Column, Constraint, Error, Index, Policy, SqlGenerator,
+    SqlVariant, Table, TableChange, TableMeta, Type, Value,
+};
+
+/// A generator for Postgres
+///
+/// By default tables are created in the first schema of the search
+/// path. Use `Pg::default().schema("...")` to qualify all table names
+/// with a schema instead.
+#[derive(Clone, Debug, Default)]
+pub struct Pg {
+    schema: Option<String>,
+}
 
-pub struct Pg;
 impl SqlGenerator for Pg {
-    fn create_table(name: &str) -> String {
-        format!("CREATE TABLE \"{}\"", name)
+    fn create_table(&self, name: &str) -> String {
+        format!("CREATE TABLE {}", self.table(name))
     }
 
-    fn create_table_if_not_exists(name: &str) -> String {
-        format!("CREATE TABLE \"{}\" IF NOT EXISTS", name)
+    fn create_table_if_not_exists(&self, name: &str) -> String {
+        format!("CREATE TABLE IF NOT EXISTS {}", self.table(name))
     }
 
-    fn drop_table(name: &str) -> String {
-        format!("DROP TABLE \"{}\"", name)
+    fn create_table_from(&self, meta: &TableMeta, guarded: bool) -> String {
+        format!("{}{}", create_kind(meta, guarded), self.table(&meta.name))
+    }
+
+    fn create_table_like(&self, name: &str, source: &str, guarded: bool) -> String {
+        format!(
+            "{} (LIKE {} INCLUDING ALL)",
+            match guarded {
+                true => self.create_table_if_not_exists(name),
+                false => self.create_table(name),
+            },
+            self.table(source)
+        )
     }
 
-    fn drop_table_if_exists(name: &str) -> String {
-        format!("DROP TABLE \"{}\" IF EXISTS", name)
+    fn create_policy(&self, name: &str, table: &str, p: &Policy) -> String {
+        policy(&self.quote(name), &self.table(table), p)
     }
 
-    fn rename_table(old: &str, new: &str) -> String {
-        format!("ALTER TABLE \"{}\" RENAME TO \"{}\"", old, new)
+    fn create_partition(&self, name: &str, parent: &str, bounds: &str, guarded: bool) -> String {
+        format!(
+            "CREATE TABLE {}{} PARTITION OF {} {}",
+            match guarded {
+                true => "IF NOT EXISTS ",
+                false => "",
+            },
+            self.table(name),
+            self.table(parent),
+            bounds
+        )
     }
 
-    fn alter_table(name: &str) -> String {
-        format!("ALTER TABLE \"{}\"", name)
+    fn drop_table(&self, name: &str) -> String {
+        format!("DROP TABLE {}", self.table(name))
     }
 
-    fn add_column(ex: bool, name: &str, column: &Column) -> String {
-        use Type::*;
-        let t: Type = column._type.clone();
+    fn drop_table_if_exists(&self, name: &str) -> String {
+        format!("DROP TABLE IF EXISTS {}", self.table(name))
+    }
 
-        #[cfg_attr(rustfmt, rustfmt_skip)] /* This shouldn't be formatted. It's too long */
+    fn rename_table(&self, old: &str, new: &str) -> String {
+        format!("ALTER TABLE {} RENAME TO \"{}\"", self.table(old), new)
+    }
+
+    fn alter_table(&self, name: &str) -> String {
+        format!("ALTER TABLE {}", self.table(name))
+    }
+
+    /// Identity columns start where the table does, unless they have
+    /// a start of their own
+    fn add_table_column(&self, meta: &TableMeta, ex: bool, name: &str, column: &Column) -> String {
+        match (column.identity, meta.auto_increment_start) {
+            (Some(id), Some(start)) if id.start.is_none() => {
+                let column = Column {
+                    identity: Some(id.start(start as i64)),
+                    ..column.clone()
+                };
+                self.add_column(ex, name, &column)
+            }
+            _ => self.add_column(ex, name, column),
+        }
+    }
+
+    fn add_column(&self, ex: bool, name: &str, column: &Column) -> String {
         format!(
-            "{}{}{}",
-            match t {
-                Primary => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(t)),
-                Text => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(t)),
-                Varchar(_) => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(t)),
-                Integer => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(t)),
-                Float => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(t)),
-                Double => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(t)),
-                Boolean => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(t)),
-                Binary => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(t)),
-                Foreign(_) => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(t)),
-                Custom(_) => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(t)),
-                Array(it) => format!("{}\"{}\" {}",Pg::prefix(ex),name,Pg::print_type(Array(Box::new(*it)))
-                ),
+            "{}\"{}\" {}{}{}{}{}{}{}{}{}",
+            Pg::prefix(ex),
+            name,
+            match column.identity {
+                Some(ref id) => identity_type(&column._type, id.sql(), Pg::print_type),
+                None => Pg::print_type(column._type.clone()),
+            },
+            match column.storage {
+                Some(s) => format!(" STORAGE {}", s.sql()),
+                None => String::new(),
+            },
+            match column.compression {
+                Some(ref m) => format!(" COMPRESSION {}", m),
+                None => String::new(),
             },
-            match (&column.def).as_ref() {
-                Some(ref m) => format!(" DEFAULT '{}'", m),
-                _ => format!(""),
+            collate(column, |c| format!("\"{}\"", c)),
+            generated(column),
+            match column.def {
+                Some(ref m) => format!(" DEFAULT {}", m.sql()),
+                _ => String::new(),
             },
             match column.nullable {
                 true => " NOT NULL",
                 false => "",
-            }
+            },
+            match column.primary {
+                true => " PRIMARY KEY",
+                false => "",
+            },
+            checks(column, |c| format!("\"{}\"", c))
         )
     }
 
-    fn drop_column(name: &str) -> String {
+    fn drop_column(&self, name: &str) -> String {
         format!("DROP COLUMN \"{}\"", name)
     }
 
-    fn rename_column(old: &str, new: &str) -> String {
+    fn add_constraint(&self, ex: bool, name: Option<&str>, c: &Constraint) -> String {
+        constraint(ex, name, c, |c| format!("\"{}\"", c))
+    }
+
+    fn create_index(&self, table: &str, name: &str, i: &Index) -> String {
+        index("", name, &self.table(table), i, |c| format!("\"{}\"", c))
+    }
+
+    fn create_index_if_not_exists(&self, table: &str, name: &str, i: &Index) -> String {
+        index("IF NOT EXISTS ", name, &self.table(table), i, |c| {
+            format!("\"{}\"", c)
+        })
+    }
+
+    fn add_column_if_not_exists(&self, name: &str, column: &Column) -> String {
+        format!(
+            "ADD COLUMN IF NOT EXISTS {}",
+            self.add_column(false, name, column)
+        )
+    }
+
+    fn drop_column_if_exists(&self, name: &str) -> String {
+        format!("DROP COLUMN IF EXISTS \"{}\"", name)
+    }
+
+    fn insert(&self, table: &str, row: &[(String, Value)]) -> String {
+        insert(self, self.table(table), row, |c| format!("\"{}\"", c))
+    }
+
+    fn transactional(&self, stmt: &str) -> bool {
+        !stmt.to_uppercase().contains(" CONCURRENTLY ")
+            && !starts_with_any(
+                stmt,
+                &["VACUUM", "CREATE DATABASE", "DROP DATABASE", "ALTER SYSTEM"],
+            )
+    }
+
+    fn change_column(&self, name: &str, column: &Column) -> String {
+        format!(
+            "ALTER COLUMN \"{}\" TYPE {}{}",
+            name,
+            Pg::print_type(base_type(&column._type)),
+            using(column)
+        )
+    }
+
+    fn rename_index(&self, old: &str, new: &str) -> String {
+        format!("ALTER INDEX {} RENAME TO \"{}\"", self.table(old), new)
+    }
+
+    fn rename_column(&self, old: &str, new: &str) -> String {
         format!("ALTER COLUMN \"{}\" RENAME TO \"{}\"", old, new)
     }
+
+    fn table_options(&self, meta: &TableMeta) -> String {
+        let mut options = String::new();
+        if !meta.inherits.is_empty() {
+            let parents: Vec<_> = meta.inherits.iter().map(|p| self.table(p)).collect();
+            options.push_str(&format!(" INHERITS ({})", parents.join(", ")));
+        }
+
+        if let Some((kind, ref columns)) = meta.partition_by {
+            let columns: Vec<_> = columns.iter().map(|c| format!("\"{}\"", c)).collect();
+            options.push_str(&format!(
+                " PARTITION BY {} ({})",
+                kind.sql(),
+                columns.join(", ")
+            ));
+        }
+
+        if !meta.storage_parameters.is_empty() {
+            options.push_str(&format!(" WITH ({})", storage_parameters(meta)));
+        }
+
+        if let Some(ref tablespace) = meta.tablespace {
+            options.push_str(&format!(" TABLESPACE \"{}\"", tablespace));
+        }
+
+        options
+    }
+
+    /// Sequences can only be restarted once they exist
+    fn finish_table(&self, t: &Table) -> Vec<(Operation, String)> {
+        let mut stmts: Vec<_> = match t.meta.auto_increment_start {
+            Some(start) => incrementing(t)
+                .into_iter()
+                .filter(|(_, c)| c.identity.is_none())
+                .map(|(column, _)| {
+                    (
+                        Operation::SetSequence,
+                        format!(
+                            "SELECT setval(pg_get_serial_sequence('{}', '{}'), {}, false)",
+                            self.table(&t.meta.name),
+                            column,
+                            start
+                        ),
+                    )
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        stmts.extend(self.row_level_security(&t.meta));
+        stmts.extend(self.comment(&t.meta));
+        stmts
+    }
+
+    fn alter_table_options(&self, meta: &TableMeta) -> Vec<(Operation, String)> {
+        let table = self.table(&meta.name);
+        let mut stmts: Vec<_> = meta
+            .inherits
+            .iter()
+            .map(|p| {
+                (
+                    Operation::SetOptions,
+                    format!("ALTER TABLE {} INHERIT {}", table, self.table(p)),
+                )
+            })
+            .collect();
+
+        if !meta.storage_parameters.is_empty() {
+            stmts.push((
+                Operation::SetOptions,
+                format!("ALTER TABLE {} SET ({})", table, storage_parameters(meta)),
+            ));
+        }
+
+        if let Some(ref tablespace) = meta.tablespace {
+            stmts.push((
+                Operation::SetOptions,
+                format!("ALTER TABLE {} SET TABLESPACE \"{}\"", table, tablespace),
+            ));
+        }
+
+        stmts.extend(self.row_level_security(meta));
+        stmts.extend(self.comment(meta));
+        stmts
+    }
+
+    /// Enum types need to exist before a column can use them
+    /// and some types come with an extension
+    fn prepare_column(&self, column: &Column) -> Vec<(Operation, String)> {
+        match *Pg::element_type(&column._type) {
+            Type::Enum(ref name, ref values) => vec![(
+                Operation::CreateType,
+                format!(
+                    "CREATE TYPE \"{}\" AS ENUM ({})",
+                    name,
+                    value_list(self, values)
+                ),
+            )],
+            Type::Hstore => vec![Pg::extension("hstore")],
+            Type::Citext => vec![Pg::extension("citext")],
+            #[cfg(feature = "postgis")]
+            Type::Geometry(_, _) | Type::Geography(_, _) => vec![Pg::extension("postgis")],
+            _ => Vec::new(),
+        }
+    }
+
+    fn drop_type(&self, name: &str, guarded: bool) -> Option<String> {
+        Some(format!(
+            "DROP TYPE {}\"{}\"",
+            match guarded {
+                true => "IF EXISTS ",
+                false => "",
+            },
+            name
+        ))
+    }
+
+    /// Partitions are tables of their own, see `create_partition`
+    fn validate_table(&self, meta: &TableMeta) -> Result<(), Error> {
+        mysql_table_only("Postgres", meta)?;
+        sqlite_table_only("Postgres", meta)
+    }
+
+    fn validate_table_change(&self, change: &TableChange) -> Result<(), Error> {
+        custom_given("Postgres", &[SqlVariant::Pg], change)?;
+        mysql_only("Postgres", change)?;
+        fraction_digits("Postgres", 6, change)?;
+        if matches!(column_of(change), Some(c) if c.unsigned) {
+            return Err(Error::Unsupported("Postgres", "Unsigned integers".into()));
+        }
+        types_of(change).into_iter().try_for_each(|t| match *t {
+            Type::Set(_) => Err(Error::Unsupported("Postgres", "SET types".into())),
+            _ => Ok(()),
+        })
+    }
 }
 
 impl Pg {
-    fn prefix(ex: bool) -> String {
+    /// Qualify all table names with a schema
+    pub fn schema<S: Into<String>>(self, schema: S) -> Self {
+        Pg {
+            schema: Some(schema.into()),
+        }
+    }
+
+    /// Quote a table name, including the schema if one was set
+    pub(super) fn table(&self, name: &str) -> String {
+        match self.schema {
+            Some(ref schema) => format!("\"{}\".\"{}\"", schema, name),
+            None => format!("\"{}\"", name),
+        }
+    }
+
+    /// The statement that enables row-level security, if the table asks for it
+    fn row_level_security(&self, meta: &TableMeta) -> Option<(Operation, String)> {
+        match meta.row_level_security {
+            true => Some((
+                Operation::SetOptions,
+                format!(
+                    "ALTER TABLE {} ENABLE ROW LEVEL SECURITY",
+                    self.table(&meta.name)
+                ),
+            )),
+            false => None,
+        }
+    }
+
+    /// The statement that sets the comment of a table, if it has one
+    fn comment(&self, meta: &TableMeta) -> Option<(Operation, String)> {
+        meta.comment.as_ref().map(|comment| {
+            (
+                Operation::Comment,
+                format!(
+                    "COMMENT ON TABLE {} IS {}",
+                    self.table(&meta.name),
+                    Value::quote(comment)
+                ),
+            )
+        })
+    }
+
+    pub(super) fn prefix(ex: bool) -> String {
         match ex {
-            true => format!("ADD COLUMN "),
-            false => format!(""),
+            true => "ADD COLUMN ".to_string(),
+            false => String::new(),
         }
     }
 
-    fn print_type(t: Type) -> String {
-        use Type::*;
+    /// The type of a column, or of the elements if it's an array
+    fn element_type(t: &Type) -> &Type {
+        match *t {
+            Type::Array(ref inner) => Pg::element_type(inner),
+            ref t => t,
+        }
+    }
+
+    /// Create an extension, unless it was already created
+    fn extension(name: &str) -> (Operation, String) {
+        (
+            Operation::CreateExtension,
+            format!("CREATE EXTENSION IF NOT EXISTS {}", name),
+        )
+    }
+
+    /// A PostGIS type, like `GEOMETRY(POINT, 4326)`
+    #[cfg(feature = "postgis")]
+    fn postgis(name: &str, kind: GeometryKind, srid: Option<u32>) -> String {
+        match srid {
+            Some(srid) => format!("{}({}, {})", name, kind.name(), srid),
+            None => format!("{}({})", name, kind.name()),
+        }
+    }
+
+    pub(super) fn print_type(t: Type) -> String {
+        use crate::Type::*;
         match t {
-            Primary => format!("SERIAL PRIMARY KEY"),
-            Text => format!("TEXT"),
+            Primary => "SERIAL PRIMARY KEY".to_string(),
+            Text => "TEXT".to_string(),
+            TinyText | MediumText | LongText => "TEXT".to_string(),
             Varchar(l) => match l {
-                0 => format!("VARCHAR"), // For "0" remove the limit
+                0 => "VARCHAR".to_string(), // For "0" remove the limit
                 _ => format!("VARCHAR({})", l),
             },
-            Integer => format!("INTEGER"),
-            Float => format!("FLOAT"),
-            Double => format!("DOUBLE"),
-            Boolean => format!("BOOLEAN"),
-            Binary => format!("BINARY"),
-            Custom(t) => format!("{}", t),
-            Foreign(t) => format!("INTEGER REFERENCES {}", t),
+            Char(l) => format!("CHAR({})", l),
+            Citext => "CITEXT".to_string(),
+            Integer => "INTEGER".to_string(),
+            SmallInt => "SMALLINT".to_string(),
+            BigInt => "BIGINT".to_string(),
+            Serial => "SERIAL".to_string(),
+            BigSerial => "BIGSERIAL".to_string(),
+            Float => "FLOAT".to_string(),
+            Double => "DOUBLE".to_string(),
+            Boolean => "BOOLEAN".to_string(),
+            Bit(l) => format!("BIT({})", l),
+            Varbit(0) => "VARBIT".to_string(),
+            Varbit(l) => format!("VARBIT({})", l),
+            Binary | Varbinary(_) => "BYTEA".to_string(),
+            Uuid => "UUID".to_string(),
+            Inet => "INET".to_string(),
+            Cidr => "CIDR".to_string(),
+            MacAddr => "MACADDR".to_string(),
+            Json => "JSON".to_string(),
+            Jsonb => "JSONB".to_string(),
+            Hstore => "HSTORE".to_string(),
+            TsVector => "TSVECTOR".to_string(),
+            TsQuery => "TSQUERY".to_string(),
+            Xml => "XML".to_string(),
+            Date => "DATE".to_string(),
+            Time(p) => precision("TIME", p),
+            DateTime(p) => precision("TIMESTAMP", p),
+            TimestampTz(p) => format!("{} WITH TIME ZONE", precision("TIMESTAMP", p)),
+            Interval(f) => match f {
+                Some(f) => format!("INTERVAL {}", f),
+                None => "INTERVAL".to_string(),
+            },
+            Decimal(p, s) => format!("NUMERIC({}, {})", p, s),
+            Range(kind) => match kind {
+                RangeKind::Int4 => "INT4RANGE".to_string(),
+                RangeKind::Int8 => "INT8RANGE".to_string(),
+                RangeKind::Numeric => "NUMRANGE".to_string(),
+                RangeKind::Timestamp => "TSRANGE".to_string(),
+                RangeKind::Timestamptz => "TSTZRANGE".to_string(),
+                RangeKind::Date => "DATERANGE".to_string(),
+            },
+            Money => "MONEY".to_string(),
+            Point => "POINT".to_string(),
+            #[cfg(feature = "postgis")]
+            Geometry(kind, srid) => Pg::postgis("GEOMETRY", kind, srid),
+            #[cfg(feature = "postgis")]
+            Geography(kind, srid) => Pg::postgis("GEOGRAPHY", kind, srid),
+            Enum(name, _) => format!("\"{}\"", name),
+            Set(_) => panic!("Postgres does not support SET types"),
+            Custom(t) => t,
+            CustomPer(types) => custom_for(&types, &[SqlVariant::Pg])
+                .expect("Custom type without an entry for Postgres"),
+            Foreign(fk) => format!(
+                "INTEGER {}REFERENCES {}{}{}",
+                match fk.name {
+                    Some(ref n) => format!("CONSTRAINT \"{}\" ", n),
+                    None => String::new(),
+                },
+                fk.table,
+                match fk.column {
+                    Some(ref c) => format!("({})", c),
+                    None => String::new(),
+                },
+                fk.actions()
+            ),
             Array(meh) => format!("{}[]", Pg::print_type(*meh)),
         }
     }
@@ -1,104 +1,396 @@
 //! Sqlite3 implementation of a generator
+//!
+//! Sqlite only supports a very small subset of `ALTER TABLE`. Every
+//! change to an existing table is emitted as its own statement and
+//! renaming or dropping columns requires Sqlite `3.25` and `3.35`
+//! respectively. To change the type, default or nullability of a
+//! column, the table is created again under a new name, its rows are
+//! copied over, and it replaces the old table. This needs the columns
+//! of the table: tables created in another migration have to be passed
+//! through `Schema::complete` first. Indexes of the table are dropped
+//! with it and have to be created again.
+//!
+//! If you need to support older versions of Sqlite, tell the generator
+//! about it via `Sqlite::default().version(3, 24)`. Changes which that
+//! version can't handle are then reported as errors.
 
-use super::{Column, SqlGenerator, Type};
+use super::{
+    base_type, checks, collate, constraint, create_kind, custom_for, custom_given, enum_check,
+    generated, incrementing, index, insert, mysql_only, mysql_table_only, no_arrays,
+    no_constraint_renames, no_identity, no_index_renames, no_partitions, no_policies, no_storage,
+    no_storage_parameters, no_table_like, postgres_only, postgres_table_only, starts_with_any,
+    unpartitioned, Column, Constraint, DatabaseChange, Error, Index, Operation, SqlGenerator,
+    SqlVariant, Table, TableChange, TableMeta, TableSchema, Type, Value,
+};
 
-/// We call this struct Sqlite instead of Sqlite3 because we hope not 
-/// to have to break the API further down the road 
-pub struct Sqlite;
-impl SqlGenerator for Sqlite {
+/// We call this struct Sqlite instead of Sqlite3 because we hope not
+/// to have to break the API further down the road
+#[derive(Clone, Debug, Default)]
+pub struct Sqlite {
+    /// The targeted (major, minor) version, `None` meaning "recent enough"
+    version: Option<(u32, u32)>,
+}
 
-    fn create_table(name: &str) -> String {
+impl SqlGenerator for Sqlite {
+    fn create_table(&self, name: &str) -> String {
         format!("CREATE TABLE \"{}\"", name)
     }
 
-    fn create_table_if_not_exists(name: &str) -> String {
+    fn create_table_if_not_exists(&self, name: &str) -> String {
         format!("CREATE TABLE IF NOT EXISTS \"{}\"", name)
     }
 
-    fn drop_table(name: &str) -> String {
+    fn create_table_from(&self, meta: &TableMeta, guarded: bool) -> String {
+        format!("{}\"{}\"", create_kind(meta, guarded), meta.name)
+    }
+
+    fn drop_table(&self, name: &str) -> String {
         format!("DROP TABLE \"{}\"", name)
     }
 
-    fn drop_table_if_exists(name: &str) -> String {
+    fn drop_table_if_exists(&self, name: &str) -> String {
         format!("DROP TABLE IF EXISTS \"{}\"", name)
     }
 
-    fn rename_table(old: &str, new: &str) -> String {
+    fn rename_table(&self, old: &str, new: &str) -> String {
         format!("ALTER TABLE \"{}\" RENAME TO \"{}\"", old, new)
     }
 
-    fn alter_table(name: &str) -> String {
+    fn alter_table(&self, name: &str) -> String {
         format!("ALTER TABLE \"{}\"", name)
     }
 
-   fn add_column(ex: bool, name: &str, column: &Column) -> String {
-        use Type::*;
-        let t: Type = column._type.clone();
+    fn add_column(&self, ex: bool, name: &str, column: &Column) -> String {
+        self.column(ex, name, column, false)
+    }
+
+    /// Columns of `STRICT` tables only get the types those allow
+    fn add_table_column(&self, meta: &TableMeta, ex: bool, name: &str, column: &Column) -> String {
+        self.column(ex, name, column, meta.strict)
+    }
 
-        #[cfg_attr(rustfmt, rustfmt_skip)] /* This shouldn't be formatted. It's too long */
+    /// Rejected in `validate_table_change`, since Sqlite can only
+    /// change a column by copying the whole table
+    ///
+    /// Called directly, this renders the standard SQL.
+    fn change_column(&self, name: &str, column: &Column) -> String {
         format!(
-            "{} {} {}",
-            match t {
-                Primary => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(t)),
-                Text => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(t)),
-                Varchar(_) => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(t)),
-                Integer => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(t)),
-                Float => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(t)),
-                Double => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(t)),
-                Boolean => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(t)),
-                Binary => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(t)),
-                Foreign(_) => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(t)),
-                Custom(_) => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(t)),
-                Array(it) => format!("{}\"{}\" {}",Sqlite::prefix(ex),name,Sqlite::print_type(Array(Box::new(*it)))
+            "ALTER COLUMN \"{}\" TYPE {}",
+            name,
+            Sqlite::print_type(base_type(&column._type))
+        )
+    }
+
+    fn drop_column(&self, name: &str) -> String {
+        format!("DROP COLUMN \"{}\"", name)
+    }
+
+    fn add_constraint(&self, ex: bool, name: Option<&str>, c: &Constraint) -> String {
+        constraint(ex, name, c, |c| format!("\"{}\"", c))
+    }
+
+    fn create_index(&self, table: &str, name: &str, i: &Index) -> String {
+        index("", name, &format!("\"{}\"", table), i, |c| {
+            format!("\"{}\"", c)
+        })
+    }
+
+    fn create_index_if_not_exists(&self, table: &str, name: &str, i: &Index) -> String {
+        index("IF NOT EXISTS ", name, &format!("\"{}\"", table), i, |c| {
+            format!("\"{}\"", c)
+        })
+    }
+
+    fn insert(&self, table: &str, row: &[(String, Value)]) -> String {
+        insert(self, format!("\"{}\"", table), row, |c| {
+            format!("\"{}\"", c)
+        })
+    }
+
+    /// Booleans are stored as integers
+    fn value(&self, value: &Value) -> String {
+        match *value {
+            Value::Boolean(b) => (b as i64).to_string(),
+            _ => value.literal(),
+        }
+    }
+
+    fn transactional(&self, stmt: &str) -> bool {
+        !starts_with_any(stmt, &["VACUUM"])
+    }
+
+    /// `AUTOINCREMENT` continues after the value in `sqlite_sequence`
+    fn table_options(&self, meta: &TableMeta) -> String {
+        let mut options = Vec::new();
+        if meta.without_rowid {
+            options.push("WITHOUT ROWID");
+        }
+        if meta.strict {
+            options.push("STRICT");
+        }
+
+        match options.is_empty() {
+            true => String::new(),
+            false => format!(" {}", options.join(", ")),
+        }
+    }
+
+    fn finish_table(&self, t: &Table) -> Vec<(Operation, String)> {
+        match (t.meta.auto_increment_start, incrementing(t).is_empty()) {
+            (Some(start), false) => vec![(
+                Operation::SetSequence,
+                format!(
+                    "INSERT INTO sqlite_sequence (name, seq) VALUES ('{}', {})",
+                    t.meta.name,
+                    start.saturating_sub(1)
                 ),
-            },
-            match (&column.def).as_ref() {
-                Some(ref m) => format!(" DEFAULT '{}'", m),
-                _ => format!(""),
-            },
-            match column.nullable {
-                true => " NOT NULL",
-                false => "",
-            }
+            )],
+            _ => Vec::new(),
+        }
+    }
+
+    fn rename_column(&self, old: &str, new: &str) -> String {
+        format!("RENAME COLUMN \"{}\" TO \"{}\"", old, new)
+    }
+
+    fn rebuilds_table(&self, change: &TableChange) -> bool {
+        matches!(
+            change,
+            TableChange::ChangeColumn(_, _, _)
+                | TableChange::SetDefault(_, _)
+                | TableChange::SetNotNull(_, _, _)
         )
     }
 
-    #[allow(unused_variables)]
-    fn drop_column(name: &str) -> String {
-        unimplemented!()
+    fn validate_table(&self, meta: &TableMeta) -> Result<(), Error> {
+        mysql_table_only("Sqlite", meta)?;
+        no_storage_parameters("Sqlite", meta)?;
+        postgres_table_only("Sqlite", meta)?;
+        unpartitioned("Sqlite", meta)?;
+        if meta.without_rowid {
+            self.requires((3, 8), "WITHOUT ROWID tables")?;
+        }
+        if meta.strict {
+            self.requires((3, 37), "STRICT tables")?;
+        }
+        match meta.comment {
+            Some(_) => Err(Error::Unsupported("Sqlite", "Table comments".into())),
+            None => Ok(()),
+        }
+    }
+
+    fn validate_change(&self, change: &DatabaseChange) -> Result<(), Error> {
+        no_policies("Sqlite", change)?;
+        no_index_renames("Sqlite", change)?;
+        no_partitions("Sqlite", change)?;
+        no_table_like("Sqlite", change)
+    }
+
+    fn validate_table_change(&self, change: &TableChange) -> Result<(), Error> {
+        custom_given("Sqlite", &[SqlVariant::Sqlite], change)?;
+        postgres_only("Sqlite", change)?;
+        no_constraint_renames("Sqlite", change)?;
+        mysql_only("Sqlite", change)?;
+        no_storage("Sqlite", change)?;
+        no_identity("Sqlite", change)?;
+        no_arrays("Sqlite", change)?;
+        match *change {
+            TableChange::RenameColumn(_, _) => self.requires((3, 25), "Renaming a column"),
+            TableChange::DropColumn(_) => self.requires((3, 35), "Dropping a column"),
+            TableChange::DropConstraint(_) => {
+                Err(Error::Unsupported("Sqlite", "Dropping a constraint".into()))
+            }
+            TableChange::AddColumn(_, ref c)
+                if c._type == Type::Serial || c._type == Type::BigSerial =>
+            {
+                Err(Error::Unsupported(
+                    "Sqlite",
+                    "Auto-incrementing columns other than the primary key".into(),
+                ))
+            }
+            TableChange::AddIndex(_, ref i) if !i.include.is_empty() => {
+                Err(Error::Unsupported("Sqlite", "INCLUDE columns".into()))
+            }
+            TableChange::AddColumn(_, ref c) if c.unsigned => {
+                Err(Error::Unsupported("Sqlite", "Unsigned integers".into()))
+            }
+            TableChange::AddColumn(
+                _,
+                Column {
+                    _type: Type::Set(_),
+                    ..
+                },
+            ) => Err(Error::Unsupported("Sqlite", "SET types".into())),
+            #[cfg(feature = "postgis")]
+            TableChange::AddColumn(
+                _,
+                Column {
+                    _type: Type::Geometry(_, _) | Type::Geography(_, _),
+                    ..
+                },
+            ) => Err(Error::Unsupported("Sqlite", "Spatial types".into())),
+            _ => Ok(()),
+        }
     }
 
-    #[allow(unused_variables)]
-    fn rename_column(old: &str, new: &str) -> String {
-        unimplemented!()
+    fn validate_alter_table(
+        &self,
+        table: Option<&TableSchema>,
+        change: &TableChange,
+    ) -> Result<(), Error> {
+        match *change {
+            TableChange::ChangeColumn(ref name, _, _)
+            | TableChange::SetDefault(ref name, _)
+            | TableChange::SetNotNull(ref name, _, _)
+                if table.and_then(|t| t.column(name)).is_none() =>
+            {
+                Err(Error::Unsupported(
+                    "Sqlite",
+                    format!(
+                        "Changing column `{}` without the definition of its table",
+                        name
+                    ),
+                ))
+            }
+            TableChange::AddConstraint(_, _) => Err(Error::Unsupported(
+                "Sqlite",
+                "Adding a constraint to an existing table".into(),
+            )),
+            _ => Ok(()),
+        }
     }
 }
 
 impl Sqlite {
+    /// Target a specific (older) version of Sqlite
+    pub fn version(self, major: u32, minor: u32) -> Self {
+        Sqlite {
+            version: Some((major, minor)),
+        }
+    }
+
+    fn requires(&self, min: (u32, u32), what: &str) -> Result<(), Error> {
+        match self.version {
+            Some(v) if v < min => Err(Error::Unsupported(
+                "Sqlite",
+                format!("{} before version {}.{}", what, min.0, min.1),
+            )),
+            _ => Ok(()),
+        }
+    }
+
     fn prefix(ex: bool) -> String {
         match ex {
-            true => format!("ADD COLUMN "),
-            false => format!(""),
+            true => "ADD COLUMN ".to_string(),
+            false => String::new(),
+        }
+    }
+
+    /// Render a column, with only the types that `STRICT` tables allow if `strict` is set
+    fn column(&self, ex: bool, name: &str, column: &Column, strict: bool) -> String {
+        format!(
+            "{}\"{}\" {}{}{}{}{}{}{}{}",
+            Sqlite::prefix(ex),
+            name,
+            match strict {
+                true => Sqlite::print_strict_type(column._type.clone()),
+                false => Sqlite::print_type(column._type.clone()),
+            },
+            collate(column, |c| c.to_string()),
+            generated(column),
+            match column.def {
+                Some(ref m) => format!(" DEFAULT {}", m.sql()),
+                _ => String::new(),
+            },
+            match column.nullable {
+                true => " NOT NULL",
+                false => "",
+            },
+            match column.primary {
+                true => " PRIMARY KEY",
+                false => "",
+            },
+            enum_check(self, format!("\"{}\"", name), &column._type),
+            checks(column, |c| format!("\"{}\"", c))
+        )
+    }
+
+    /// The type of a column in a `STRICT` table, which only allows
+    /// `INT`, `INTEGER`, `REAL`, `TEXT`, `BLOB` and `ANY`
+    ///
+    /// Types are mapped by the affinity Sqlite would give them, except
+    /// that decimals keep their exact value as `ANY`.
+    fn print_strict_type(t: Type) -> String {
+        use crate::Type::*;
+        match t {
+            SmallInt | BigInt | Boolean => "INTEGER".to_string(),
+            Double => "REAL".to_string(),
+            Varchar(_) | Char(_) | Date | Time(_) | DateTime(_) | TimestampTz(_) => {
+                "TEXT".to_string()
+            }
+            Decimal(_, _) | Money => "ANY".to_string(),
+            t => Sqlite::print_type(t),
         }
     }
 
     fn print_type(t: Type) -> String {
-        use Type::*;
+        use crate::Type::*;
         match t {
-            Primary => format!("INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT"),
-            Text => format!("TEXT"),
+            Primary => "INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT".to_string(),
+            Text => "TEXT".to_string(),
+            TinyText | MediumText | LongText => "TEXT".to_string(),
             Varchar(l) => match l {
-                0 => format!("VARCHAR"), // For "0" remove the limit
+                0 => "VARCHAR".to_string(), // For "0" remove the limit
                 _ => format!("VARCHAR({})", l),
             },
-            Integer => format!("INTEGER"),
-            Float => format!("REAL"),
-            Double => format!("DOUBLE"),
-            Boolean => format!("BOOLEAN"),
-            Binary => format!("BINARY"),
-            Custom(t) => format!("{}", t),
-            Foreign(t) => format!("INTEGER REFERENCES {}", t),
-            Array(meh) => format!("{}[]", Sqlite::print_type(*meh)),
+            Char(l) => format!("CHAR({})", l),
+            Citext => "TEXT COLLATE NOCASE".to_string(),
+            Integer => "INTEGER".to_string(),
+            SmallInt => "SMALLINT".to_string(),
+            BigInt => "BIGINT".to_string(),
+            Serial | BigSerial => panic!("SQLite can only auto-increment primary keys"),
+            Float => "REAL".to_string(),
+            Double => "DOUBLE".to_string(),
+            Boolean => "BOOLEAN".to_string(),
+            Bit(_) | Varbit(_) => "BLOB".to_string(),
+            Binary | Varbinary(_) => "BLOB".to_string(),
+            Uuid => "TEXT".to_string(),
+            Inet | Cidr | MacAddr => "TEXT".to_string(),
+            Json | Jsonb => "TEXT".to_string(),
+            Hstore => "TEXT".to_string(),
+            TsVector | TsQuery => panic!("SQLite does not support full-text search types"),
+            Xml => "TEXT".to_string(),
+            /* SQLite stores these as text and ignores the precision */
+            Date => "DATE".to_string(),
+            Time(_) => "TIME".to_string(),
+            DateTime(_) => "DATETIME".to_string(),
+            TimestampTz(_) => "DATETIME".to_string(),
+            Interval(_) => "INTEGER".to_string(),
+            Decimal(p, s) => format!("NUMERIC({}, {})", p, s),
+            Range(_) => panic!("SQLite does not support range types"),
+            Money => "NUMERIC(19, 4)".to_string(),
+            Point => "TEXT".to_string(),
+            #[cfg(feature = "postgis")]
+            Geometry(_, _) | Geography(_, _) => panic!("SQLite does not support spatial types"),
+            Enum(_, _) => "TEXT".to_string(),
+            Set(_) => panic!("SQLite does not support SET types"),
+            Custom(t) => t,
+            CustomPer(types) => custom_for(&types, &[SqlVariant::Sqlite])
+                .expect("Custom type without an entry for SQLite"),
+            Foreign(fk) => format!(
+                "INTEGER {}REFERENCES {}{}{}",
+                match fk.name {
+                    Some(ref n) => format!("CONSTRAINT \"{}\" ", n),
+                    None => String::new(),
+                },
+                fk.table,
+                match fk.column {
+                    Some(ref c) => format!("({})", c),
+                    None => String::new(),
+                },
+                fk.actions()
+            ),
+            Array(_) => panic!("SQLite does not support array types"),
         }
     }
 }
@@ -0,0 +1,201 @@
+//! A command line interface to barrel migrations
+//!
+//! Migrations are Rust files in a migrations directory (`migrations/`
+//! by default), each providing an `up` and a `down` function. They are
+//! rendered with `barrel::build`, which compiles them into a small
+//! cargo project under `target/barrel`.
+//!
+//! ```text
+//! barrel new <name>            Scaffold a new migration file
+//! barrel render <backend>      Print the SQL of all migrations
+//! barrel pending <url>         List migrations that weren't applied yet
+//! barrel apply <url>           Apply all pending migrations
+//! ```
+//!
+//! Applied migrations are recorded in a `__barrel_migrations` table.
+//! Connection strings can use the `postgres://`, `mysql://` and
+//! `sqlite://` schemes.
+
+use barrel::backend::{MySql, Pg, Sqlite};
+use barrel::build::{self, RenderedMigration};
+use barrel::connectors::DatabaseExecutor;
+use barrel::tracking;
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+const USAGE: &str = "Usage: barrel <command> [--dir <migrations>]
+
+Commands:
+    new <name>          Scaffold a new migration file
+    render <backend>    Print the SQL of all migrations (`--down` for the down steps)
+    pending <url>       List migrations that weren't applied yet
+    apply <url>         Apply all pending migrations";
+
+const TEMPLATE: &str = "use barrel::*;
+
+/// Handle up migrations
+pub fn up(m: &mut Migration) {}
+
+/// Handle down migrations
+///
+/// If no changes are registered, they are inferred from `up`
+pub fn down(m: &mut Migration) {}
+";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(e) = run(args) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(mut args: Vec<String>) -> Result<(), String> {
+    let dir = match args.iter().position(|a| a == "--dir") {
+        Some(i) if i + 1 < args.len() => PathBuf::from(args.drain(i..i + 2).nth(1).unwrap()),
+        Some(_) => return Err("`--dir` requires a path".into()),
+        None => PathBuf::from("migrations"),
+    };
+    let down = match args.iter().position(|a| a == "--down") {
+        Some(i) => {
+            args.remove(i);
+            true
+        }
+        None => false,
+    };
+
+    match (args.first().map(|s| s.as_str()), args.get(1)) {
+        (Some("new"), Some(name)) => new(&dir, name),
+        (Some("render"), Some(backend)) => render(&dir, backend, down),
+        (Some("pending"), Some(url)) => pending(&dir, url),
+        (Some("apply"), Some(url)) => apply(&dir, url),
+        _ => Err(USAGE.into()),
+    }
+}
+
+fn new(dir: &Path, name: &str) -> Result<(), String> {
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Err(format!("Invalid migration name `{}`", name));
+    }
+
+    let next = build::migration_files(dir)
+        .map_err(|e| e.to_string())?
+        .iter()
+        .filter_map(|p| {
+            build::migration_name(p)
+                .split('_')
+                .next()
+                .and_then(|v| v.parse::<u32>().ok())
+        })
+        .max()
+        .unwrap_or(0)
+        + 1;
+
+    let path = dir.join(format!("{:04}_{}.rs", next, name));
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    fs::write(&path, TEMPLATE).map_err(|e| e.to_string())?;
+    println!("Created {}", path.display());
+    Ok(())
+}
+
+/// Compile all migrations and render them for a backend
+fn render_all(dir: &Path, backend: &str) -> Result<Vec<RenderedMigration>, String> {
+    build::render(dir, backend, Path::new("target").join("barrel")).map_err(|e| e.to_string())
+}
+
+fn render(dir: &Path, backend: &str, down: bool) -> Result<(), String> {
+    for r in render_all(dir, backend)? {
+        println!("-- {}", r.name);
+        match (down, r.down) {
+            (false, _) if !r.up.is_empty() => println!("{};", r.up),
+            (true, Ok(ref sql)) if !sql.is_empty() => println!("{};", sql),
+            (true, Err(e)) => println!("-- {}", e),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// A database connection the CLI can apply migrations to
+enum Connection {
+    Pg(postgres::Client),
+    MySql(mysql::Conn),
+    Sqlite(rusqlite::Connection),
+}
+
+impl Connection {
+    fn open(url: &str) -> Result<Connection, String> {
+        match url.split("://").next() {
+            Some("postgres") | Some("postgresql") => {
+                postgres::Client::connect(url, postgres::NoTls)
+                    .map(Connection::Pg)
+                    .map_err(|e| e.to_string())
+            }
+            Some("mysql") => mysql::Opts::from_url(url)
+                .map_err(|e| e.to_string())
+                .and_then(|opts| mysql::Conn::new(opts).map_err(|e| e.to_string()))
+                .map(Connection::MySql),
+            Some("sqlite") => rusqlite::Connection::open(&url["sqlite://".len()..])
+                .map(Connection::Sqlite)
+                .map_err(|e| e.to_string()),
+            _ => Err(format!("Unsupported connection string `{}`", url)),
+        }
+    }
+
+    fn backend(&self) -> &'static str {
+        match *self {
+            Connection::Pg(_) => "pg",
+            Connection::MySql(_) => "mysql",
+            Connection::Sqlite(_) => "sqlite",
+        }
+    }
+
+    fn executor(&mut self) -> &mut dyn DatabaseExecutor {
+        match *self {
+            Connection::Pg(ref mut c) => c,
+            Connection::MySql(ref mut c) => c,
+            Connection::Sqlite(ref mut c) => c,
+        }
+    }
+
+    /// Create the tracking table if necessary and read all applied versions
+    fn applied(&mut self) -> Result<Vec<String>, String> {
+        match *self {
+            Connection::Pg(ref mut c) => tracking::applied::<_, Pg>(c),
+            Connection::MySql(ref mut c) => tracking::applied::<_, MySql>(c),
+            Connection::Sqlite(ref mut c) => tracking::applied::<_, Sqlite>(c),
+        }
+        .map_err(|e| e.to_string())
+    }
+}
+
+fn pending_for(conn: &mut Connection, dir: &Path) -> Result<Vec<RenderedMigration>, String> {
+    let applied = conn.applied()?;
+    Ok(render_all(dir, conn.backend())?
+        .into_iter()
+        .filter(|r| !applied.contains(&r.name))
+        .collect())
+}
+
+fn pending(dir: &Path, url: &str) -> Result<(), String> {
+    let mut conn = Connection::open(url)?;
+    for r in pending_for(&mut conn, dir)? {
+        println!("{}", r.name);
+    }
+    Ok(())
+}
+
+fn apply(dir: &Path, url: &str) -> Result<(), String> {
+    let mut conn = Connection::open(url)?;
+    for r in pending_for(&mut conn, dir)? {
+        /* Record the version in the same transaction as the migration */
+        conn.executor()
+            .batch_execute(&tracking::with_version(&r.up, &r.name, None))
+            .map_err(|e| e.to_string())?;
+        println!("Applied {}", r.name);
+    }
+    Ok(())
+}
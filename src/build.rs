@@ -0,0 +1,252 @@
+//! Render a directory of migrations, e.g. from a `build.rs`
+//!
+//! Migrations are Rust files in a directory, ordered by their file
+//! name. Each provides an `up` and a `down` function:
+//!
+//! ```norun
+//! use barrel::*;
+//!
+//! pub fn up(m: &mut Migration) {
+//!     m.create_table("users", |t| {
+//!         t.add_column("name", Type::Text);
+//!     });
+//! }
+//!
+//! /// If no changes are registered, they are inferred from `up`
+//! pub fn down(m: &mut Migration) {}
+//! ```
+//!
+//! To render them, a small cargo project which includes all migration
+//! files is generated and compiled against barrel. From a build script,
+//! `emit` writes the SQL into `OUT_DIR`, where it can be embedded:
+//!
+//! ```norun
+//! // build.rs
+//! fn main() {
+//!     barrel::build::emit("migrations", "pg").unwrap();
+//! }
+//!
+//! // src/main.rs
+//! include!(concat!(env!("OUT_DIR"), "/migrations.rs"));
+//!
+//! for &(name, up, down) in MIGRATIONS { /* ... */ }
+//! ```
+
+use crate::Error;
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The SQL of a single migration file
+#[derive(Clone, Debug, PartialEq)]
+pub struct RenderedMigration {
+    /// The file name of the migration, without its extension
+    pub name: String,
+
+    /// The SQL of the `up` step
+    pub up: String,
+
+    /// The SQL of the `down` step, or the reason it can't be reverted
+    pub down: Result<String, String>,
+}
+
+/// All migration files in a directory, ordered by their name
+///
+/// A missing directory contains no migrations.
+pub fn migration_files<P: AsRef<Path>>(dir: P) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+    if !dir.as_ref().exists() {
+        return Ok(files);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|e| e == "rs") {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// The name of a migration file, without its extension
+pub fn migration_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Render all migrations in a directory for a backend
+///
+/// The backend name is anything `SqlVariant` can be parsed from.
+/// The generated cargo project is kept in `work_dir`, so that later
+/// calls only need to recompile the migrations.
+pub fn render<P: AsRef<Path>, W: AsRef<Path>>(
+    dir: P,
+    backend: &str,
+    work_dir: W,
+) -> Result<Vec<RenderedMigration>, Error> {
+    let files = migration_files(&dir)?;
+    let project = work_dir.as_ref();
+    fs::create_dir_all(project.join("src"))?;
+
+    let mut mods = String::new();
+    let mut list = String::new();
+    for (i, file) in files.iter().enumerate() {
+        let path = fs::canonicalize(file)?;
+        mods.push_str(&format!("#[path = {:?}]\nmod m{};\n", path, i));
+        list.push_str(&format!(
+            "        ({:?}, m{}::up, m{}::down),\n",
+            migration_name(file),
+            i,
+            i
+        ));
+    }
+
+    fs::write(project.join("Cargo.toml"), manifest())?;
+    fs::write(project.join("src").join("main.rs"), program(&mods, &list))?;
+
+    let output = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".into()))
+        .args(["run", "--quiet", "--manifest-path"])
+        .arg(project.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(project.join("target"))
+        .arg("--")
+        .arg(backend)
+        .output()?;
+
+    match output.status.success() {
+        true => Ok(parse(&String::from_utf8_lossy(&output.stdout))),
+        false => Err(Error::Execution(
+            String::from_utf8_lossy(&output.stderr).into_owned(),
+        )),
+    }
+}
+
+/// Render all migrations for a backend into `OUT_DIR`
+///
+/// Meant to be called from a build script. This writes a
+/// `migrations.rs` which defines a `MIGRATIONS` constant with the
+/// name, `up` and (if reversible) `down` SQL of every migration.
+pub fn emit<P: AsRef<Path>>(dir: P, backend: &str) -> Result<(), Error> {
+    let out = PathBuf::from(env::var("OUT_DIR").map_err(|e| Error::Io(e.to_string()))?);
+    let migrations = render(&dir, backend, out.join("barrel"))?;
+
+    let mut consts = String::from(
+        "/// Migrations rendered by barrel: name, `up` and `down` SQL\n\
+         pub const MIGRATIONS: &[(&str, &str, Option<&str>)] = &[\n",
+    );
+    for m in &migrations {
+        consts.push_str(&format!(
+            "    ({:?}, {:?}, {:?}),\n",
+            m.name,
+            m.up,
+            m.down.as_ref().ok()
+        ));
+    }
+    consts.push_str("];\n");
+    fs::write(out.join("migrations.rs"), consts)?;
+
+    println!("cargo:rerun-if-changed={}", dir.as_ref().display());
+    Ok(())
+}
+
+fn manifest() -> String {
+    format!(
+        "# This file is auto generated by barrel
+[package]
+name = \"barrel-migrations\"
+version = \"0.0.0\"
+edition = \"2018\"
+
+[dependencies]
+barrel = {{ path = {:?}, features = [\"pg\", \"sqlite3\", \"mysql\", \"mssql\", \"cockroach\", \"ansi\"] }}
+
+[workspace]
+",
+        env!("CARGO_MANIFEST_DIR")
+    )
+}
+
+/// A program that prints the SQL of every migration
+///
+/// The `up` SQL follows a `-- barrel:up <name>` line, the `down` SQL
+/// a `-- barrel:down` line (or `-- barrel:irreversible <reason>`).
+fn program(mods: &str, list: &str) -> String {
+    format!(
+        "//! This file is auto generated by barrel
+#![allow(unused_variables)]
+use barrel::backend::SqlVariant;
+use barrel::Migration;
+
+{}
+fn main() {{
+    let variant: SqlVariant = std::env::args().nth(1).unwrap().parse().unwrap();
+    let migrations: Vec<(&str, fn(&mut Migration), fn(&mut Migration))> = vec![
+{}    ];
+
+    for (name, up, down) in migrations {{
+        let mut m = Migration::new();
+        up(&mut m);
+        let mut d = Migration::new();
+        down(&mut d);
+
+        println!(\"-- barrel:up {{}}\", name);
+        println!(\"{{}}\", m.make_from(variant));
+        let down = match d.changes.is_empty() {{
+            true => m.reversed().and_then(|r| r.try_make_from(variant)),
+            false => d.try_make_from(variant),
+        }};
+        match down {{
+            Ok(sql) => println!(\"-- barrel:down\\n{{}}\", sql),
+            Err(e) => println!(\"-- barrel:irreversible {{}}\", e),
+        }}
+    }}
+}}
+",
+        mods, list
+    )
+}
+
+/// Split the output of the generated program into migrations
+fn parse(output: &str) -> Vec<RenderedMigration> {
+    let mut rendered: Vec<RenderedMigration> = Vec::new();
+    let mut in_down = false;
+
+    for line in output.lines() {
+        if let Some(name) = line.strip_prefix("-- barrel:up ") {
+            in_down = false;
+            rendered.push(RenderedMigration {
+                name: name.into(),
+                up: String::new(),
+                down: Ok(String::new()),
+            });
+            continue;
+        }
+
+        let r = match rendered.last_mut() {
+            Some(r) => r,
+            None => continue,
+        };
+
+        if line == "-- barrel:down" {
+            in_down = true;
+        } else if let Some(e) = line.strip_prefix("-- barrel:irreversible ") {
+            r.down = Err(e.into());
+        } else {
+            let sql = match in_down {
+                true => r.down.as_mut().unwrap(),
+                false => &mut r.up,
+            };
+            if !sql.is_empty() {
+                sql.push('\n');
+            }
+            sql.push_str(line);
+        }
+    }
+
+    rendered
+}
@@ -0,0 +1,289 @@
+//! Stable checksums of migrations
+//!
+//! A migration is hashed via a description of its changes, with all
+//! table and column callbacks applied. The hash (64 bit FNV-1a) doesn't
+//! depend on the platform or on the order of runs, so it can be stored
+//! and compared later on.
+
+use crate::{Column, DatabaseChange, Index, Migration, Table, TableChange, TableMeta};
+
+use std::fmt::Write;
+
+const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hash the logical changes of a migration, including its `down` step
+pub(crate) fn checksum(migr: &Migration) -> u64 {
+    let mut desc = String::new();
+    describe(migr, &mut desc);
+
+    desc.bytes()
+        .fold(OFFSET, |hash, b| (hash ^ b as u64).wrapping_mul(PRIME))
+}
+
+fn describe(migr: &Migration, out: &mut String) {
+    use crate::DatabaseChange::*;
+
+    if migr.idempotent {
+        out.push_str("idempotent;");
+    }
+
+    for change in &migr.changes {
+        match *change {
+            CreateTable(ref t, ref cb) | CreateTableIfNotExists(ref t, ref cb) => {
+                let mut t = t.clone();
+                t.prepare(cb.as_ref());
+                let guard = match *change {
+                    CreateTableIfNotExists(_, _) => " if_not_exists",
+                    _ => "",
+                };
+                let _ = write!(out, "create_table{} {:?} (", guard, t.meta.name);
+                describe_meta(&t.meta, out);
+                describe_table(&t, out);
+                out.push_str(");");
+            }
+            ChangeTable(ref t, ref cb) => {
+                let mut t = t.clone();
+                cb(&mut t);
+                let _ = write!(out, "change_table {:?} (", t.meta.name);
+                describe_meta(&t.meta, out);
+                describe_table(&t, out);
+                out.push_str(");");
+            }
+            RenameTable(ref old, ref new) => {
+                let _ = write!(out, "rename_table {:?} {:?};", old, new);
+            }
+            DropTable(ref name) => {
+                let _ = write!(out, "drop_table {:?};", name);
+            }
+            DropTableIfExists(ref name) => {
+                let _ = write!(out, "drop_table_if_exists {:?};", name);
+            }
+            CreateTableLike(ref name, ref source) => {
+                let _ = write!(out, "create_table_like {:?} {:?};", name, source);
+            }
+            CreatePolicy(ref name, ref table, ref policy) => {
+                let _ = write!(out, "create_policy {:?} {:?} {:?};", name, table, policy);
+            }
+            RenameIndex(ref old, ref new) => {
+                let _ = write!(out, "rename_index {:?} {:?};", old, new);
+            }
+            CreateTableAs(ref name, ref query) => {
+                let _ = write!(out, "create_table_as {:?} {:?};", name, query);
+            }
+            CreatePartition(ref name, ref parent, ref bounds) => {
+                let _ = write!(
+                    out,
+                    "create_partition {:?} {:?} {:?};",
+                    name, parent, bounds
+                );
+            }
+            CustomLine(ref sql) => {
+                let _ = write!(out, "custom {:?};", sql);
+            }
+            DatabaseChange::Insert(ref table, ref row) => {
+                let _ = write!(out, "insert {:?} {:?};", table, row);
+            }
+            DropType(ref name) => {
+                let _ = write!(out, "drop_type {:?};", name);
+            }
+        }
+    }
+
+    if let Some(ref down) = migr.down {
+        out.push_str("down (");
+        describe(down, out);
+        out.push_str(");");
+    }
+}
+
+/// Describe the options of a table which are set
+fn describe_meta(meta: &TableMeta, out: &mut String) {
+    if let Some(start) = meta.auto_increment_start {
+        let _ = write!(out, "auto_increment_start {};", start);
+    }
+
+    if let Some(ref comment) = meta.comment {
+        let _ = write!(out, "comment {:?};", comment);
+    }
+
+    if meta.temporary {
+        out.push_str("temporary;");
+    }
+
+    if meta.unlogged {
+        out.push_str("unlogged;");
+    }
+
+    if meta.without_rowid {
+        out.push_str("without_rowid;");
+    }
+
+    if meta.strict {
+        out.push_str("strict;");
+    }
+
+    if let Some(ref tablespace) = meta.tablespace {
+        let _ = write!(out, "tablespace {:?};", tablespace);
+    }
+
+    if meta.row_level_security {
+        out.push_str("row_level_security;");
+    }
+
+    if !meta.storage_parameters.is_empty() {
+        let _ = write!(out, "with {:?};", meta.storage_parameters);
+    }
+
+    if !meta.inherits.is_empty() {
+        let _ = write!(out, "inherits {:?};", meta.inherits);
+    }
+
+    if let Some((kind, ref columns)) = meta.partition_by {
+        let _ = write!(out, "partition_by {:?} {:?};", kind, columns);
+    }
+
+    if !meta.partitions.is_empty() {
+        let _ = write!(out, "partitions {:?};", meta.partitions);
+    }
+
+    if let Some(count) = meta.partition_count {
+        let _ = write!(out, "partition_count {};", count);
+    }
+
+    let mysql = [
+        ("engine", &meta.engine),
+        ("charset", &meta.charset),
+        ("collate", &meta.collation),
+        ("row_format", &meta.row_format),
+    ];
+    for (option, value) in mysql.iter() {
+        if let Some(ref value) = value {
+            let _ = write!(out, "{} {:?};", option, value);
+        }
+    }
+}
+
+fn describe_table(t: &Table, out: &mut String) {
+    use crate::TableChange::*;
+
+    for change in &t.changes {
+        let _ = match *change {
+            AddColumn(ref name, ref col) => {
+                let _ = write!(out, "add_column {:?} ", name);
+                describe_column(col, out);
+                write!(out, ";")
+            }
+            ChangeColumn(ref name, ref col, ref cb) => {
+                let mut col = col.clone();
+                cb(&mut col);
+                let _ = write!(out, "change_column {:?} ", name);
+                describe_column(&col, out);
+                write!(out, ";")
+            }
+            RenameColumn(ref old, ref new) => write!(out, "rename_column {:?} {:?};", old, new),
+            SetDefault(ref name, ref expr) => write!(out, "set_default {:?} {:?};", name, expr),
+            RenameConstraint(ref old, ref new) => {
+                write!(out, "rename_constraint {:?} {:?};", old, new)
+            }
+            DropConstraint(ref name) => write!(out, "drop_constraint {:?};", name),
+            // The definition depends on the schema, not on the migration
+            SetNotNull(ref name, not_null, _) => {
+                write!(out, "set_not_null {:?} {:?};", name, not_null)
+            }
+            DropColumn(ref name) => write!(out, "drop_column {:?};", name),
+            TableChange::CustomLine(ref sql) => write!(out, "custom {:?};", sql),
+            AddConstraint(ref name, ref c) => write!(out, "add_constraint {:?} {:?};", name, c),
+            AddIndex(ref name, ref i) => {
+                let _ = write!(out, "add_index {:?} ", name);
+                describe_index(i, out);
+                write!(out, ";")
+            }
+        };
+    }
+}
+
+/// Describe an index, with options only included when they are set
+fn describe_index(i: &Index, out: &mut String) {
+    let _ = write!(out, "Index {:?}", i.columns);
+
+    if let Some(ref expr) = i.expression {
+        let _ = write!(out, " expression {:?}", expr);
+    }
+
+    if i.unique {
+        out.push_str(" unique");
+    }
+
+    if let Some(kind) = i.kind {
+        let _ = write!(out, " kind {:?}", kind);
+    }
+
+    if !i.include.is_empty() {
+        let _ = write!(out, " include {:?}", i.include);
+    }
+
+    if let Some(ref expr) = i.where_clause {
+        let _ = write!(out, " where {:?}", expr);
+    }
+
+    if let Some(ref tablespace) = i.tablespace {
+        let _ = write!(out, " tablespace {:?}", tablespace);
+    }
+}
+
+/// Describe a column
+///
+/// Modifiers are only included when they are set, so that adding new
+/// ones doesn't change the checksums of existing migrations.
+fn describe_column(col: &Column, out: &mut String) {
+    let _ = write!(
+        out,
+        "Column {{ unique: {:?}, indexed: {:?}, nullable: {:?}, increments: {:?}, _type: {:?}, def: {:?} }}",
+        col.unique, col.indexed, col.nullable, col.increments, col._type, col.def
+    );
+
+    if col.unsigned {
+        out.push_str(" unsigned");
+    }
+
+    if col.primary {
+        out.push_str(" primary");
+    }
+
+    if let Some((ref expr, kind)) = col.generated {
+        let _ = write!(out, " generated {:?} {:?}", expr, kind);
+    }
+
+    if let Some(ref identity) = col.identity {
+        let _ = write!(out, " identity {:?}", identity);
+    }
+
+    if !col.checks.is_empty() {
+        let _ = write!(out, " checks {:?}", col.checks);
+    }
+
+    if let Some(ref collation) = col.collation {
+        let _ = write!(out, " collate {:?}", collation);
+    }
+
+    if let Some(ref charset) = col.charset {
+        let _ = write!(out, " charset {:?}", charset);
+    }
+
+    if let Some(ref expr) = col.on_update {
+        let _ = write!(out, " on_update {:?}", expr);
+    }
+
+    if let Some(storage) = col.storage {
+        let _ = write!(out, " storage {:?}", storage);
+    }
+
+    if let Some(ref method) = col.compression {
+        let _ = write!(out, " compression {:?}", method);
+    }
+
+    if let Some(ref expr) = col.using {
+        let _ = write!(out, " using {:?}", expr);
+    }
+}
@@ -7,6 +7,45 @@
 //!
 //! You can then simple call `Migration::execute` to run the provided
 //! migration.
+//!
+//! ```norun
+//! struct Runner(postgres::Client);
+//!
+//! impl DatabaseExecutor for Runner {
+//!     fn batch_execute(&mut self, sql: &str) -> Result<(), Error> {
+//!         self.0
+//!             .batch_execute(sql)
+//!             .map_err(|e| Error::Execution(e.to_string()))
+//!     }
+//! }
+//!
+//! m.execute::<_, Pg>(&mut runner)?;
+//! ```
+//!
+//! Async drivers implement `AsyncDatabaseExecutor` instead, which
+//! also decides the `SqlGenerator` the migration is rendered with.
+//! Implementations for `sqlx` pools are available behind the
+//! `sqlx` feature.
+//!
+//! ```norun
+//! m.execute_async(&pool).await?;
+//! ```
+//!
+//! Executors which also implement `query_column` can skip migrations
+//! which were applied already, see the `tracking` module.
+
+use crate::backend::SqlGenerator;
+use crate::Error;
+
+use std::future::Future;
+use std::pin::Pin;
+
+/// The future returned when executing SQL asynchronously
+pub type ExecuteFuture<'a, T = ()> = Pin<Box<dyn Future<Output = Result<T, Error>> + Send + 'a>>;
+
+fn unsupported() -> Error {
+    Error::Unsupported("executor", "Querying applied versions".into())
+}
 
 /// A generic trait that frameworks using barrel can implement
 ///
@@ -14,7 +53,41 @@
 /// automatically generate and run the given SQL string for a
 /// database connection which is wrapped by it
 pub trait DatabaseExecutor {
+    /// Execute one or more `;` separated SQL statements on a backend
+    ///
+    /// Errors from the database driver should be returned
+    /// as `Error::Execution`
+    fn batch_execute(&mut self, sql: &str) -> Result<(), Error>;
+
+    /// Run a query which selects a single text column and return its values
+    ///
+    /// This is used to read the versions recorded by the `tracking`
+    /// module. Executors which don't implement it return
+    /// `Error::Unsupported`.
+    fn query_column(&mut self, _sql: &str) -> Result<Vec<String>, Error> {
+        Err(unsupported())
+    }
+}
+
+/// An async version of `DatabaseExecutor`
+///
+/// Async database handles are usually shared, so the SQL is
+/// executed via `&self`. Implementations should apply all statements
+/// in a single transaction where the database allows it.
+pub trait AsyncDatabaseExecutor: Sync {
+    /// The generator that produces SQL for this database
+    type Generator: SqlGenerator + Default;
+
+    /// Execute one or more `;` separated SQL statements on a backend
+    ///
+    /// Errors from the database driver should be returned
+    /// as `Error::Execution`
+    fn batch_execute<'a>(&'a self, sql: &'a str) -> ExecuteFuture<'a>;
 
-    /// Execute the migration on a backend
-    fn execute<S: Into<String>>(&mut self, sql: S);
+    /// Run a query which selects a single text column and return its values
+    ///
+    /// See `DatabaseExecutor::query_column` for details
+    fn query_column<'a>(&'a self, _sql: &'a str) -> ExecuteFuture<'a, Vec<String>> {
+        Box::pin(async { Err(unsupported()) })
+    }
 }
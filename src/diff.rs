@@ -0,0 +1,89 @@
+//! Compute the migration between two schemas
+//!
+//! `diff` compares a current schema (e.g. from introspecting a
+//! database) with a desired one and returns a `Migration` that turns
+//! the first into the second.
+//!
+//! ```
+//! # use barrel::*;
+//! # use barrel::schema::Schema;
+//! let mut m = Migration::new();
+//! m.create_table("users", |t| {
+//!     t.add_column("name", Type::Text);
+//! });
+//!
+//! let desired = Schema::from_migrations(&[m]);
+//! let migration = diff::diff(&Schema::new(), &desired);
+//! assert_eq!(migration.changes.len(), 1);
+//! ```
+//!
+//! Renames can't be told apart from removing one table or column and
+//! adding another, so they show up as exactly that. Changed columns
+//! are emitted as `TableChange::ChangeColumn` with their new definition.
+
+use crate::schema::{Schema, TableSchema};
+use crate::{Column, Migration, TableChange};
+
+use std::rc::Rc;
+
+/// Create a migration that turns `from` into `to`
+///
+/// New tables are created first, in the order of `to`. Changes to
+/// existing tables follow, and tables missing from `to` are dropped last.
+pub fn diff(from: &Schema, to: &Schema) -> Migration {
+    let mut m = Migration::new();
+
+    for table in to.tables.iter().filter(|t| from.table(&t.name).is_none()) {
+        let columns = table.columns.clone();
+        m.create_table(table.name.clone(), move |t| {
+            for (name, col) in &columns {
+                t.changes
+                    .push(TableChange::AddColumn(name.clone(), col.clone()));
+            }
+        })
+        .without_id();
+    }
+
+    for table in &to.tables {
+        if let Some(old) = from.table(&table.name) {
+            let changes = diff_table(old, table);
+            if !changes.is_empty() {
+                m.change_table(table.name.clone(), move |t| {
+                    t.changes.extend(changes.iter().cloned());
+                });
+            }
+        }
+    }
+
+    for table in from.tables.iter().filter(|t| to.table(&t.name).is_none()) {
+        m.drop_table(table.name.clone());
+    }
+
+    m
+}
+
+/// The column changes that turn one version of a table into another
+fn diff_table(from: &TableSchema, to: &TableSchema) -> Vec<TableChange> {
+    let mut changes = Vec::new();
+
+    for (name, col) in &to.columns {
+        match from.column(name) {
+            None => changes.push(TableChange::AddColumn(name.clone(), col.clone())),
+            Some(old) if old != col => changes.push(change_column(name, col)),
+            Some(_) => {}
+        }
+    }
+
+    for (name, _) in &from.columns {
+        if to.column(name).is_none() {
+            changes.push(TableChange::DropColumn(name.clone()));
+        }
+    }
+
+    changes
+}
+
+fn change_column(name: &str, col: &Column) -> TableChange {
+    let new = col.clone();
+    TableChange::ChangeColumn(name.into(), col.clone(), Rc::new(move |c| *c = new.clone()))
+}
@@ -0,0 +1,66 @@
+//! Migrations compiled into a binary
+//!
+//! With the `macros` feature, `embed_migrations!` includes a directory
+//! of migration files (laid out as described in the `build` module) as
+//! a static list, ordered by file name:
+//!
+//! ```norun
+//! static MIGRATIONS: &[EmbeddedMigration] = barrel::embed_migrations!("migrations");
+//!
+//! let applied = embedded::run::<_, Pg>(MIGRATIONS, &mut client)?;
+//! ```
+//!
+//! The directory is relative to the `Cargo.toml` of the crate. Changes
+//! to existing files cause a rebuild, but newly added files are only
+//! picked up once the crate is rebuilt for another reason.
+
+use crate::backend::SqlGenerator;
+use crate::connectors::DatabaseExecutor;
+use crate::{Error, Migration};
+
+/// A migration file included via `embed_migrations!`
+#[derive(Clone, Copy, Debug)]
+pub struct EmbeddedMigration {
+    /// The file name of the migration, without its extension
+    pub name: &'static str,
+
+    /// The `up` function of the file
+    pub up: fn(&mut Migration),
+
+    /// The `down` function of the file
+    pub down: fn(&mut Migration),
+}
+
+impl EmbeddedMigration {
+    /// Build the migration, with its `down` step if it registers any changes
+    pub fn migration(&self) -> Migration {
+        let mut m = Migration::new();
+        (self.up)(&mut m);
+
+        let mut down = Migration::new();
+        (self.down)(&mut down);
+        if !down.changes.is_empty() {
+            m.down = Some(Box::new(down));
+        }
+
+        m
+    }
+}
+
+/// Apply all embedded migrations which weren't applied yet, in order
+///
+/// Each migration is versioned by its name (see `tracking`). Returns
+/// the names of the migrations that were applied.
+pub fn run<T: DatabaseExecutor, S: SqlGenerator + Default>(
+    migrations: &[EmbeddedMigration],
+    runner: &mut T,
+) -> Result<Vec<&'static str>, Error> {
+    let mut applied = Vec::new();
+    for m in migrations {
+        if m.migration().execute_versioned::<_, S>(m.name, runner)? {
+            applied.push(m.name);
+        }
+    }
+
+    Ok(applied)
+}
@@ -0,0 +1,71 @@
+//! Errors that can occur while generating a migration
+//!
+//! Most problems with a migration can only be detected once it's
+//! rendered for a specific database backend. Generators report these
+//! via the `Error` type in this module.
+
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Something that went wrong while generating SQL
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// A backend can't express a type or operation
+    ///
+    /// Contains the name of the backend and a description of what
+    /// it was asked to generate
+    Unsupported(&'static str, String),
+
+    /// No backend with this name exists (or it wasn't enabled
+    /// via its cargo feature)
+    UnknownBackend(String),
+
+    /// A migration (or part of it) can't be reverted
+    Irreversible(String),
+
+    /// Running a migration on a database failed
+    ///
+    /// Contains the error reported by the database driver
+    Execution(String),
+
+    /// Reading or writing migration files failed
+    Io(String),
+
+    /// A migration was changed after it was applied
+    ///
+    /// Contains the version of the migration
+    Modified(String),
+
+    /// A version isn't one of the known migrations
+    UnknownVersion(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            Error::Unsupported(backend, ref what) => {
+                write!(f, "{} is not supported by the {} backend", what, backend)
+            }
+            Error::UnknownBackend(ref name) => write!(f, "Unknown database backend `{}`", name),
+            Error::Irreversible(ref what) => write!(f, "{} can not be reverted", what),
+            Error::Execution(ref msg) => write!(f, "Failed to execute migration: {}", msg),
+            Error::Io(ref msg) => write!(f, "Failed to access migration files: {}", msg),
+            Error::Modified(ref version) => write!(
+                f,
+                "Migration `{}` was modified after it was applied",
+                version
+            ),
+            Error::UnknownVersion(ref version) => {
+                write!(f, "Unknown migration version `{}`", version)
+            }
+        }
+    }
+}
+
+impl StdError for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e.to_string())
+    }
+}
@@ -1,12 +1,12 @@
 //!
 
-use diesel::migration::{Migration, RunMigrationsError};
 use diesel::connection::SimpleConnection;
+use diesel::migration::{Migration, RunMigrationsError};
+use std::fs;
+use std::fs::*;
+use std::io::prelude::*;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::io::prelude::*;
-use std::fs::*;
-use std::fs;
 
 /// Represents a migration run inside Diesel
 ///
@@ -26,12 +26,12 @@ impl Migration for BarrelMigration {
     }
 
     fn run(&self, conn: &SimpleConnection) -> Result<(), RunMigrationsError> {
-        try!(conn.batch_execute(&self.2));
+        conn.batch_execute(&self.2)?;
         Ok(())
     }
 
     fn revert(&self, conn: &SimpleConnection) -> Result<(), RunMigrationsError> {
-        try!(conn.batch_execute(&self.3));
+        conn.batch_execute(&self.3)?;
         Ok(())
     }
 }
@@ -130,7 +130,8 @@ fn main() {{
 }}
 ",
                 user_migration
-            ).as_bytes(),
+            )
+            .as_bytes(),
         )
         .unwrap();
 
@@ -0,0 +1,91 @@
+//! Write migrations in the layout used by `diesel_migrations`
+//!
+//! Diesel expects every migration in its own directory, named after
+//! the version and the migration, containing an `up.sql` and a
+//! `down.sql` file:
+//!
+//! ```text
+//! migrations/
+//!     2019-01-31-174523_create_users/
+//!         up.sql
+//!         down.sql
+//! ```
+//!
+//! The `down.sql` is rendered from the migration's `down` step, or
+//! inferred via `Migration::revert` if none was registered.
+//!
+//! ```norun
+//! diesel_migrations::write("migrations", "create_users", &m, &Pg::default())?;
+//! ```
+
+use crate::backend::SqlGenerator;
+use crate::{Error, Migration};
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Format a unix timestamp as a Diesel migration version
+///
+/// Diesel uses `YYYY-MM-DD-HHMMSS` in UTC
+pub fn version(secs: u64) -> String {
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    /* Convert days since the epoch to a civil date (Howard Hinnant) */
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
+
+/// Write a migration into a new directory versioned with the current time
+///
+/// Returns the path of the created migration directory
+pub fn write<P: AsRef<Path>, T: SqlGenerator>(
+    dir: P,
+    name: &str,
+    migr: &Migration,
+    gen: &T,
+) -> Result<PathBuf, Error> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    write_version(dir, &version(now), name, migr, gen)
+}
+
+/// Write a migration into a new directory with an explicit version
+///
+/// See `write` for details
+pub fn write_version<P: AsRef<Path>, T: SqlGenerator>(
+    dir: P,
+    version: &str,
+    name: &str,
+    migr: &Migration,
+    gen: &T,
+) -> Result<PathBuf, Error> {
+    /* Render both steps before touching the file system */
+    let up = migr.try_make_with(gen)?;
+    let down = migr.try_make_down_with(gen)?;
+
+    let path = dir.as_ref().join(format!("{}_{}", version, name));
+    fs::create_dir_all(&path)?;
+    fs::write(path.join("up.sql"), format!("{};\n", up))?;
+    fs::write(path.join("down.sql"), format!("{};\n", down))?;
+    Ok(path)
+}
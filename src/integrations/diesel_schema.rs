@@ -0,0 +1,161 @@
+//! Render a Diesel `schema.rs` from barrel migrations
+//!
+//! The migrations are replayed in order to find the final layout of
+//! every table, which is then printed as Diesel `table!` macros.
+//! Foreign keys to tables in the schema are declared as `joinable!`.
+//!
+//! ```norun
+//! let schema = diesel_schema::generate(&[m1, m2])?;
+//! fs::write("src/schema.rs", schema)?;
+//! ```
+//!
+//! Columns are mapped to Diesel's SQL types the same way for every
+//! backend. `Custom` types can't be mapped and result in an error.
+
+use crate::schema::{Schema, TableSchema};
+use crate::{Column, Error, Migration, RangeKind, Type};
+
+/// Map a column type to a Diesel SQL type
+fn print_type(t: &Type) -> Result<String, Error> {
+    use crate::Type::*;
+    Ok(match *t {
+        Text | TinyText | MediumText | LongText => "Text".into(),
+        Varchar(_) => "Varchar".into(),
+        Char(_) => "Char".into(),
+        Citext => "Citext".into(),
+        Primary | Integer | Foreign(_) => "Integer".into(),
+        SmallInt => "SmallInt".into(),
+        BigInt => "BigInt".into(),
+        Serial => "Integer".into(),
+        BigSerial => "BigInt".into(),
+        Float => "Float".into(),
+        Double => "Double".into(),
+        Boolean => "Bool".into(),
+        Binary | Varbinary(_) => "Binary".into(),
+        Bit(_) | Varbit(_) => return Err(Error::Unsupported("diesel", "Bit strings".into())),
+        Uuid => "Uuid".into(),
+        Inet => "Inet".into(),
+        Cidr => "Cidr".into(),
+        MacAddr => "MacAddr".into(),
+        Json => "Json".into(),
+        Jsonb => "Jsonb".into(),
+        Hstore => return Err(Error::Unsupported("diesel", "HSTORE types".into())),
+        TsVector | TsQuery => {
+            return Err(Error::Unsupported(
+                "diesel",
+                "Full-text search types".into(),
+            ))
+        }
+        Xml => return Err(Error::Unsupported("diesel", "XML types".into())),
+        Date => "Date".into(),
+        Time(_) => "Time".into(),
+        DateTime(_) => "Timestamp".into(),
+        TimestampTz(_) => "Timestamptz".into(),
+        Interval(_) => "Interval".into(),
+        Decimal(_, _) => "Numeric".into(),
+        Range(kind) => match kind {
+            RangeKind::Int4 => "Int4range",
+            RangeKind::Int8 => "Int8range",
+            RangeKind::Numeric => "Numrange",
+            RangeKind::Timestamp => "Tsrange",
+            RangeKind::Timestamptz => "Tstzrange",
+            RangeKind::Date => "Daterange",
+        }
+        .into(),
+        Money => "Money".into(),
+        Point => "Point".into(),
+        #[cfg(feature = "postgis")]
+        Geometry(_, _) | Geography(_, _) => {
+            return Err(Error::Unsupported("diesel", "Spatial types".into()))
+        }
+        Array(ref t) => format!("Array<{}>", print_type(t)?),
+        Enum(ref name, _) => {
+            return Err(Error::Unsupported(
+                "diesel",
+                format!("Enum type `{}`", name),
+            ))
+        }
+        Set(_) => return Err(Error::Unsupported("diesel", "SET types".into())),
+        CustomPer(_) => return Err(Error::Unsupported("diesel", "Custom types".into())),
+        Custom(ref t) => return Err(Error::Unsupported("diesel", format!("Custom type `{}`", t))),
+    })
+}
+
+/// Whether the generated SQL makes this column `NOT NULL`
+///
+/// This mirrors the generators, which add `NOT NULL` for columns
+/// marked `nullable`. Primary keys can never be null.
+fn not_null(col: &Column) -> bool {
+    col.nullable || col._type == Type::Primary
+}
+
+fn print_table(table: &TableSchema) -> Result<String, Error> {
+    let keys: Vec<&str> = table
+        .columns
+        .iter()
+        .filter(|c| c.1._type == Type::Primary)
+        .map(|c| c.0.as_str())
+        .collect();
+
+    if keys.is_empty() {
+        return Err(Error::Unsupported(
+            "diesel",
+            format!("Table `{}` without a primary key", table.name),
+        ));
+    }
+
+    let mut columns = Vec::new();
+    for (name, col) in &table.columns {
+        let t = print_type(&col._type)?;
+        columns.push(match not_null(col) {
+            true => format!("        {} -> {},", name, t),
+            false => format!("        {} -> Nullable<{}>,", name, t),
+        });
+    }
+
+    Ok(format!(
+        "table! {{\n    {} ({}) {{\n{}\n    }}\n}}\n",
+        table.name,
+        keys.join(", "),
+        columns.join("\n")
+    ))
+}
+
+/// Generate the contents of a Diesel `schema.rs` for a set of migrations
+///
+/// Migrations are applied in the order they are given in.
+pub fn generate(migrations: &[Migration]) -> Result<String, Error> {
+    let tables = Schema::from_migrations(migrations).tables;
+    let mut out = vec![String::from("// This file is auto generated by barrel\n")];
+
+    for table in &tables {
+        out.push(print_table(table)?);
+    }
+
+    let mut joins = Vec::new();
+    for table in &tables {
+        for (name, col) in &table.columns {
+            if let Type::Foreign(ref fk) = col._type {
+                if tables.iter().any(|t| t.name == fk.table) {
+                    joins.push(format!(
+                        "joinable!({} -> {} ({}));",
+                        table.name, fk.table, name
+                    ));
+                }
+            }
+        }
+    }
+    if !joins.is_empty() {
+        out.push(format!("{}\n", joins.join("\n")));
+    }
+
+    if tables.len() > 1 {
+        let names: Vec<&str> = tables.iter().map(|t| t.name.as_str()).collect();
+        out.push(format!(
+            "allow_tables_to_appear_in_same_query!(\n    {},\n);\n",
+            names.join(",\n    ")
+        ));
+    }
+
+    Ok(out.join("\n"))
+}
@@ -1,6 +1,33 @@
 //! Include external integrations into frameworks and libraries
-//! 
-//! 
+//!
+//!
 
 #[cfg(feature = "diesel-filled")]
-pub mod diesel;
\ No newline at end of file
+pub mod diesel;
+
+#[cfg(feature = "diesel")]
+pub mod diesel_migrations;
+
+#[cfg(feature = "diesel")]
+pub mod diesel_schema;
+
+#[cfg(feature = "sqlx")]
+pub mod sqlx;
+
+#[cfg(feature = "postgres")]
+pub mod postgres;
+
+#[cfg(feature = "tokio-postgres")]
+pub mod tokio_postgres;
+
+#[cfg(feature = "rusqlite")]
+pub mod rusqlite;
+
+#[cfg(feature = "mysql-client")]
+pub mod mysql;
+
+#[cfg(feature = "mysql_async")]
+pub mod mysql_async;
+
+#[cfg(feature = "refinery")]
+pub mod refinery;
@@ -0,0 +1,51 @@
+//! Run migrations with the blocking `mysql` crate
+//!
+//! Executors are implemented for single connections and for pools.
+//! Each migration is run in a transaction, but MySQL commits implicitly
+//! after most schema changes, so a failed migration may be applied
+//! partially.
+//!
+//! ```norun
+//! let mut conn = Conn::new(Opts::from_url("mysql://localhost/db")?)?;
+//! m.execute::<_, MySql>(&mut conn)?;
+//! ```
+
+use crate::connectors::DatabaseExecutor;
+use crate::Error;
+
+use mysql::prelude::Queryable;
+use mysql::{Conn, Pool, TxOpts};
+
+fn execution(e: mysql::Error) -> Error {
+    Error::Execution(e.to_string())
+}
+
+impl DatabaseExecutor for Conn {
+    fn batch_execute(&mut self, sql: &str) -> Result<(), Error> {
+        let mut tx = self
+            .start_transaction(TxOpts::default())
+            .map_err(execution)?;
+        tx.query_drop(sql).map_err(execution)?;
+        tx.commit().map_err(execution)
+    }
+
+    fn query_column(&mut self, sql: &str) -> Result<Vec<String>, Error> {
+        self.query(sql).map_err(execution)
+    }
+}
+
+impl DatabaseExecutor for Pool {
+    fn batch_execute(&mut self, sql: &str) -> Result<(), Error> {
+        let mut tx = self
+            .start_transaction(TxOpts::default())
+            .map_err(execution)?;
+        tx.query_drop(sql).map_err(execution)?;
+        tx.commit().map_err(execution)
+    }
+
+    fn query_column(&mut self, sql: &str) -> Result<Vec<String>, Error> {
+        self.get_conn()
+            .and_then(|mut conn| conn.query(sql))
+            .map_err(execution)
+    }
+}
@@ -0,0 +1,44 @@
+//! Run migrations with the `mysql_async` crate
+//!
+//! Each migration is run in a transaction on a connection from the
+//! pool, but MySQL commits implicitly after most schema changes, so a
+//! failed migration may be applied partially.
+//!
+//! ```norun
+//! let pool = Pool::new("mysql://localhost/db");
+//! m.execute_async(&pool).await?;
+//! ```
+
+use crate::backend::MySql;
+use crate::connectors::{AsyncDatabaseExecutor, ExecuteFuture};
+use crate::Error;
+
+use mysql_async::prelude::Queryable;
+use mysql_async::{Pool, TxOpts};
+
+fn execution(e: mysql_async::Error) -> Error {
+    Error::Execution(e.to_string())
+}
+
+impl AsyncDatabaseExecutor for Pool {
+    type Generator = MySql;
+
+    fn batch_execute<'a>(&'a self, sql: &'a str) -> ExecuteFuture<'a> {
+        Box::pin(async move {
+            let mut conn = self.get_conn().await.map_err(execution)?;
+            let mut tx = conn
+                .start_transaction(TxOpts::default())
+                .await
+                .map_err(execution)?;
+            tx.query_drop(sql).await.map_err(execution)?;
+            tx.commit().await.map_err(execution)
+        })
+    }
+
+    fn query_column<'a>(&'a self, sql: &'a str) -> ExecuteFuture<'a, Vec<String>> {
+        Box::pin(async move {
+            let mut conn = self.get_conn().await.map_err(execution)?;
+            conn.query(sql).await.map_err(execution)
+        })
+    }
+}
@@ -0,0 +1,31 @@
+//! Run migrations on `postgres` clients
+//!
+//! Each migration is applied in its own transaction, which is
+//! rolled back if a statement fails.
+//!
+//! ```norun
+//! let mut client = Client::connect("host=localhost user=postgres", NoTls)?;
+//! m.execute::<_, Pg>(&mut client)?;
+//! ```
+
+use crate::connectors::DatabaseExecutor;
+use crate::Error;
+
+use postgres::Client;
+
+fn execution(e: postgres::Error) -> Error {
+    Error::Execution(e.to_string())
+}
+
+impl DatabaseExecutor for Client {
+    fn batch_execute(&mut self, sql: &str) -> Result<(), Error> {
+        let mut tx = self.transaction().map_err(execution)?;
+        tx.batch_execute(sql).map_err(execution)?;
+        tx.commit().map_err(execution)
+    }
+
+    fn query_column(&mut self, sql: &str) -> Result<Vec<String>, Error> {
+        let rows = self.query(sql, &[]).map_err(execution)?;
+        Ok(rows.iter().map(|r| r.get(0)).collect())
+    }
+}
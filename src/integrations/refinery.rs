@@ -0,0 +1,36 @@
+//! Author `refinery` migrations with barrel
+//!
+//! refinery keeps track of which migrations were applied, while
+//! barrel renders their SQL. Versions and names follow refinery's
+//! `V{version}__{name}` convention.
+//!
+//! ```norun
+//! let migrations = vec![
+//!     refinery::migration(1, "create_users", &m1, &Pg::default())?,
+//!     refinery::migration(2, "add_emails", &m2, &Pg::default())?,
+//! ];
+//! Runner::new(&migrations).run(&mut client)?;
+//! ```
+
+use crate::backend::SqlGenerator;
+use crate::{Error, Migration};
+
+/// Render a migration into an unapplied refinery migration
+///
+/// Names may only contain letters, digits and underscores. refinery
+/// would otherwise cut them off at the first other character.
+pub fn migration<T: SqlGenerator>(
+    version: u32,
+    name: &str,
+    migr: &Migration,
+    gen: &T,
+) -> Result<refinery_core::Migration, Error> {
+    let invalid = || Error::Unsupported("refinery", format!("Migration name `{}`", name));
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Err(invalid());
+    }
+
+    let sql = migr.try_make_with(gen)?;
+    refinery_core::Migration::unapplied(&format!("V{}__{}", version, name), &sql)
+        .map_err(|_| invalid())
+}
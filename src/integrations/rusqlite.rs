@@ -0,0 +1,42 @@
+//! Run migrations on `rusqlite` connections
+//!
+//! SQLite executes one statement at a time, so the generated script
+//! is walked with SQLite's own parser instead of being split on `;`.
+//! This keeps semicolons in string literals, quoted identifiers,
+//! comments or trigger bodies intact.
+//!
+//! Each migration is applied in its own transaction, which is rolled
+//! back if a statement fails.
+//!
+//! ```norun
+//! let mut conn = Connection::open("app.db")?;
+//! m.execute::<_, Sqlite>(&mut conn)?;
+//! ```
+
+use crate::connectors::DatabaseExecutor;
+use crate::Error;
+
+use rusqlite::{Batch, Connection};
+
+fn execution(e: rusqlite::Error) -> Error {
+    Error::Execution(e.to_string())
+}
+
+impl DatabaseExecutor for Connection {
+    fn batch_execute(&mut self, sql: &str) -> Result<(), Error> {
+        let tx = self.transaction().map_err(execution)?;
+        {
+            let mut batch = Batch::new(&tx, sql);
+            while let Some(mut stmt) = batch.next().map_err(execution)? {
+                stmt.raw_execute().map_err(execution)?;
+            }
+        }
+        tx.commit().map_err(execution)
+    }
+
+    fn query_column(&mut self, sql: &str) -> Result<Vec<String>, Error> {
+        self.prepare(sql)
+            .and_then(|mut stmt| stmt.query_map([], |r| r.get(0))?.collect())
+            .map_err(execution)
+    }
+}
@@ -0,0 +1,74 @@
+//! Run migrations on `sqlx` connection pools
+//!
+//! Executors are implemented for the pools of all enabled backends,
+//! `PgPool` (`pg`), `MySqlPool` (`mysql`) and `SqlitePool` (`sqlite3`).
+//! Each migration is applied in its own transaction, which is rolled
+//! back if a statement fails. Note that MySQL commits implicitly
+//! after most schema changes, so a failed migration may be applied
+//! partially there.
+//!
+//! ```norun
+//! let pool = PgPool::connect("postgres://localhost/db").await?;
+//! m.execute_async(&pool).await?;
+//! ```
+//!
+//! Pools of databases that speak another dialect, such as CockroachDB,
+//! can be used with `Migration::execute_async_with`.
+
+// Without any backend enabled there is nothing to implement
+#![cfg_attr(
+    not(any(feature = "pg", feature = "mysql", feature = "sqlite3")),
+    allow(unused)
+)]
+
+use crate::connectors::{AsyncDatabaseExecutor, ExecuteFuture};
+use crate::Error;
+
+use sqlx::Executor;
+
+#[cfg(feature = "mysql")]
+use crate::backend::MySql;
+#[cfg(feature = "pg")]
+use crate::backend::Pg;
+#[cfg(feature = "sqlite3")]
+use crate::backend::Sqlite;
+
+fn execution(e: sqlx::Error) -> Error {
+    Error::Execution(e.to_string())
+}
+
+/// Run all statements in a single transaction on the pool
+macro_rules! impl_executor {
+    ($pool:ty, $conn:ty, $gen:ty) => {
+        impl AsyncDatabaseExecutor for $pool {
+            type Generator = $gen;
+
+            fn batch_execute<'a>(&'a self, sql: &'a str) -> ExecuteFuture<'a> {
+                Box::pin(async move {
+                    let mut tx = self.begin().await.map_err(execution)?;
+                    let conn: &mut $conn = &mut tx;
+                    conn.execute(sqlx::raw_sql(sql)).await.map_err(execution)?;
+                    tx.commit().await.map_err(execution)
+                })
+            }
+
+            fn query_column<'a>(&'a self, sql: &'a str) -> ExecuteFuture<'a, Vec<String>> {
+                Box::pin(async move {
+                    sqlx::query_scalar(sql)
+                        .fetch_all(self)
+                        .await
+                        .map_err(execution)
+                })
+            }
+        }
+    };
+}
+
+#[cfg(feature = "pg")]
+impl_executor!(sqlx::PgPool, sqlx::PgConnection, Pg);
+
+#[cfg(feature = "mysql")]
+impl_executor!(sqlx::MySqlPool, sqlx::MySqlConnection, MySql);
+
+#[cfg(feature = "sqlite3")]
+impl_executor!(sqlx::SqlitePool, sqlx::SqliteConnection, Sqlite);
@@ -0,0 +1,40 @@
+//! Run migrations on `tokio-postgres` clients
+//!
+//! The statements of a migration are sent as a single simple query,
+//! which Postgres runs in one implicit transaction. A failing statement
+//! rolls back the whole migration.
+//!
+//! This also makes execution cancellation safe: if the future is
+//! dropped, the migration is either applied completely or not at all,
+//! and no transaction is left open on the client.
+//!
+//! ```norun
+//! let (client, connection) = tokio_postgres::connect("host=localhost", NoTls).await?;
+//! tokio::spawn(connection);
+//! m.execute_async(&client).await?;
+//! ```
+
+use crate::backend::Pg;
+use crate::connectors::{AsyncDatabaseExecutor, ExecuteFuture};
+use crate::Error;
+
+use tokio_postgres::Client;
+
+fn execution(e: tokio_postgres::Error) -> Error {
+    Error::Execution(e.to_string())
+}
+
+impl AsyncDatabaseExecutor for Client {
+    type Generator = Pg;
+
+    fn batch_execute<'a>(&'a self, sql: &'a str) -> ExecuteFuture<'a> {
+        Box::pin(async move { Client::batch_execute(self, sql).await.map_err(execution) })
+    }
+
+    fn query_column<'a>(&'a self, sql: &'a str) -> ExecuteFuture<'a, Vec<String>> {
+        Box::pin(async move {
+            let rows = self.query(sql, &[]).await.map_err(execution)?;
+            Ok(rows.iter().map(|r| r.get(0)).collect())
+        })
+    }
+}
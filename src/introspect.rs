@@ -0,0 +1,288 @@
+//! Read the schema of a live database
+//!
+//! The `Introspect` trait is implemented for the connections of the
+//! enabled drivers (`postgres`, `mysql-client` and `rusqlite`). It
+//! returns a `Schema` which can be compared with the one described by
+//! barrel migrations via the `diff` module.
+//!
+//! ```norun
+//! let current = client.introspect()?;
+//! let desired = Schema::from_migrations(&migrations);
+//! let migration = diff::diff(&current, &desired);
+//! ```
+//!
+//! Column types are mapped back to the `Type` barrel would generate
+//! them from. Types barrel doesn't know become `Type::Custom`, and
+//! column defaults aren't read.
+
+use crate::schema::Schema;
+use crate::Error;
+#[cfg(any(feature = "postgres", feature = "mysql-client", feature = "rusqlite"))]
+use crate::{schema::TableSchema, Column, RangeKind, Type};
+
+/// A database connection whose schema can be read
+pub trait Introspect {
+    /// Read all tables and their columns
+    ///
+    /// Errors from the database driver are returned
+    /// as `Error::Execution`
+    fn introspect(&mut self) -> Result<Schema, Error>;
+}
+
+/// A column as reported by the database
+#[cfg(any(feature = "postgres", feature = "mysql-client", feature = "rusqlite"))]
+struct RawColumn {
+    table: String,
+    name: String,
+    _type: Type,
+    not_null: bool,
+    primary: bool,
+}
+
+/// Assemble a schema from columns (ordered by table) and foreign keys
+#[cfg(any(feature = "postgres", feature = "mysql-client", feature = "rusqlite"))]
+fn build(columns: Vec<RawColumn>, foreign: Vec<(String, String, String)>) -> Schema {
+    let mut schema = Schema::new();
+
+    for raw in columns {
+        let target = foreign
+            .iter()
+            .find(|f| f.0 == raw.table && f.1 == raw.name)
+            .map(|f| f.2.clone());
+
+        let mut col = Column::new(match (raw.primary, target) {
+            (true, _) => Type::Primary,
+            (false, Some(t)) => Type::Foreign(t.into()),
+            (false, None) => raw._type,
+        });
+        col.increments = raw.primary;
+
+        /* The generators add `NOT NULL` for columns marked `nullable` */
+        col.nullable = raw.not_null && !raw.primary;
+
+        if schema.tables.last().map(|t| &t.name) != Some(&raw.table) {
+            schema.tables.push(TableSchema::new(raw.table.clone()));
+        }
+        schema
+            .tables
+            .last_mut()
+            .unwrap()
+            .columns
+            .push((raw.name, col));
+    }
+
+    schema
+}
+
+/// Map a type name, as used in SQL, to a barrel type
+#[cfg(any(feature = "postgres", feature = "mysql-client", feature = "rusqlite"))]
+fn parse_type(name: &str, len: Option<usize>) -> Type {
+    let lower = name.to_lowercase();
+    match lower.as_str() {
+        "text" => Type::Text,
+        "tinytext" => Type::TinyText,
+        "mediumtext" => Type::MediumText,
+        "longtext" => Type::LongText,
+        "varchar" | "character varying" => Type::Varchar(len.unwrap_or(0)),
+        "char" | "bpchar" | "character" | "nchar" => Type::Char(len.unwrap_or(1)),
+        "citext" => Type::Citext,
+        "int" | "int4" | "integer" => Type::Integer,
+        "smallint" | "int2" => Type::SmallInt,
+        "bigint" | "int8" => Type::BigInt,
+        "real" | "float" | "float4" => Type::Float,
+        "double" | "double precision" | "float8" => Type::Double,
+        "bool" | "boolean" | "tinyint(1)" => Type::Boolean,
+        "binary" | "blob" | "bytea" => Type::Binary,
+        "varbinary" => Type::Varbinary(len.unwrap_or(0)),
+        "bit" => Type::Bit(len.unwrap_or(1)),
+        "varbit" | "bit varying" => Type::Varbit(len.unwrap_or(0)),
+        "uuid" | "uniqueidentifier" => Type::Uuid,
+        "inet" => Type::Inet,
+        "cidr" => Type::Cidr,
+        "macaddr" => Type::MacAddr,
+        "json" => Type::Json,
+        "jsonb" => Type::Jsonb,
+        "hstore" => Type::Hstore,
+        "tsvector" => Type::TsVector,
+        "tsquery" => Type::TsQuery,
+        "xml" => Type::Xml,
+        "date" => Type::Date,
+        "time" => Type::Time(None),
+        "datetime" | "timestamp" => Type::DateTime(None),
+        "timestamptz" | "datetimeoffset" => Type::TimestampTz(None),
+        "interval" => Type::Interval(None),
+        "int4range" => Type::Range(RangeKind::Int4),
+        "int8range" => Type::Range(RangeKind::Int8),
+        "numrange" => Type::Range(RangeKind::Numeric),
+        "tsrange" => Type::Range(RangeKind::Timestamp),
+        "tstzrange" => Type::Range(RangeKind::Timestamptz),
+        "daterange" => Type::Range(RangeKind::Date),
+        "money" => Type::Money,
+        "point" => Type::Point,
+        _ if lower.starts_with("varchar(") => Type::Varchar(
+            lower["varchar(".len()..lower.len() - 1]
+                .parse()
+                .unwrap_or(0),
+        ),
+        _ => Type::Custom(name.to_uppercase()),
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl Introspect for postgres::Client {
+    /// Reads the tables of the first schema in the search path
+    fn introspect(&mut self) -> Result<Schema, Error> {
+        let err = |e: postgres::Error| Error::Execution(e.to_string());
+
+        let columns = self
+            .query(
+                "SELECT c.table_name::TEXT, c.column_name::TEXT, c.udt_name::TEXT,
+                        c.character_maximum_length, c.is_nullable = 'NO',
+                        COALESCE(c.column_default LIKE 'nextval(%', FALSE)
+                 FROM information_schema.columns c
+                 JOIN information_schema.tables t
+                   ON t.table_schema = c.table_schema AND t.table_name = c.table_name
+                 WHERE c.table_schema = current_schema() AND t.table_type = 'BASE TABLE'
+                 ORDER BY c.table_name, c.ordinal_position",
+                &[],
+            )
+            .map_err(err)?
+            .iter()
+            .map(|row| {
+                let udt: String = row.get(2);
+                let len = row.get::<_, Option<i32>>(3).map(|l| l as usize);
+                RawColumn {
+                    table: row.get(0),
+                    name: row.get(1),
+                    _type: match udt.strip_prefix('_') {
+                        Some(inner) => Type::Array(Box::new(parse_type(inner, None))),
+                        None => parse_type(&udt, len),
+                    },
+                    not_null: row.get(4),
+                    primary: row.get(5),
+                }
+            })
+            .collect();
+
+        let foreign = self
+            .query(
+                "SELECT kcu.table_name::TEXT, kcu.column_name::TEXT, ccu.table_name::TEXT
+                 FROM information_schema.table_constraints tc
+                 JOIN information_schema.key_column_usage kcu
+                   ON kcu.constraint_name = tc.constraint_name
+                  AND kcu.table_schema = tc.table_schema
+                 JOIN information_schema.constraint_column_usage ccu
+                   ON ccu.constraint_name = tc.constraint_name
+                  AND ccu.table_schema = tc.table_schema
+                 WHERE tc.constraint_type = 'FOREIGN KEY'
+                   AND tc.table_schema = current_schema()",
+                &[],
+            )
+            .map_err(err)?
+            .iter()
+            .map(|row| (row.get(0), row.get(1), row.get(2)))
+            .collect();
+
+        Ok(build(columns, foreign))
+    }
+}
+
+#[cfg(feature = "mysql-client")]
+impl Introspect for mysql::Conn {
+    /// Reads the tables of the current database
+    fn introspect(&mut self) -> Result<Schema, Error> {
+        use mysql::prelude::Queryable;
+        let err = |e: mysql::Error| Error::Execution(e.to_string());
+
+        /* Table, column, data type, column type, length, nullable, extra */
+        type Row = (String, String, String, String, Option<u64>, String, String);
+        let rows: Vec<Row> = self
+            .query(
+                "SELECT c.table_name, c.column_name, c.data_type, c.column_type,
+                        c.character_maximum_length, c.is_nullable, c.extra
+                 FROM information_schema.columns c
+                 JOIN information_schema.tables t
+                   ON t.table_schema = c.table_schema AND t.table_name = c.table_name
+                 WHERE c.table_schema = DATABASE() AND t.table_type = 'BASE TABLE'
+                 ORDER BY c.table_name, c.ordinal_position",
+            )
+            .map_err(err)?;
+
+        let columns = rows
+            .into_iter()
+            .map(
+                |(table, name, data, full, len, nullable, extra)| RawColumn {
+                    table,
+                    name,
+                    _type: match full.as_str() {
+                        "tinyint(1)" => Type::Boolean,
+                        _ => parse_type(&data, len.map(|l| l as usize)),
+                    },
+                    not_null: nullable == "NO",
+                    primary: extra.contains("auto_increment"),
+                },
+            )
+            .collect();
+
+        let foreign = self
+            .query(
+                "SELECT table_name, column_name, referenced_table_name
+                 FROM information_schema.key_column_usage
+                 WHERE table_schema = DATABASE() AND referenced_table_name IS NOT NULL",
+            )
+            .map_err(err)?;
+
+        Ok(build(columns, foreign))
+    }
+}
+
+#[cfg(feature = "rusqlite")]
+impl Introspect for rusqlite::Connection {
+    fn introspect(&mut self) -> Result<Schema, Error> {
+        let err = |e: rusqlite::Error| Error::Execution(e.to_string());
+
+        let tables: Vec<String> = self
+            .prepare(
+                "SELECT name FROM sqlite_master
+                 WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+            )
+            .and_then(|mut s| s.query_map([], |r| r.get(0))?.collect())
+            .map_err(err)?;
+
+        let mut columns = Vec::new();
+        let mut foreign = Vec::new();
+        for table in &tables {
+            let mut info = self
+                .prepare("SELECT name, type, \"notnull\", pk FROM pragma_table_info(?1)")
+                .map_err(err)?;
+            let rows = info
+                .query_map([table], |r| {
+                    let t: String = r.get(1)?;
+                    let pk: i64 = r.get(3)?;
+                    Ok(RawColumn {
+                        table: table.clone(),
+                        name: r.get(0)?,
+                        primary: pk > 0 && t.eq_ignore_ascii_case("integer"),
+                        _type: parse_type(&t, None),
+                        not_null: r.get(2)?,
+                    })
+                })
+                .map_err(err)?;
+            for row in rows {
+                columns.push(row.map_err(err)?);
+            }
+
+            let mut fks = self
+                .prepare("SELECT \"from\", \"table\" FROM pragma_foreign_key_list(?1)")
+                .map_err(err)?;
+            let rows = fks
+                .query_map([table], |r| Ok((table.clone(), r.get(0)?, r.get(1)?)))
+                .map_err(err)?;
+            for row in rows {
+                foreign.push(row.map_err(err)?);
+            }
+        }
+
+        Ok(build(columns, foreign))
+    }
+}
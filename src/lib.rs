@@ -1,9 +1,9 @@
 //! Powerful schema migration builder API in Rust.
 //!
 //! Barrel is meant to make writing migrations for different databases as easy
-//! as possible. It has three primary models: 
+//! as possible. It has three primary models:
 //! the [Migration](migration/struct.Migration.html) which represents
-//! all changes and changes made on a database level, 
+//! all changes and changes made on a database level,
 //! the [Table](table/struct.Table.html) and the
 //! [Column](column/struct.Column.html).
 //!
@@ -16,7 +16,7 @@
 //! default types and override encodings, nullability or uniqueness of columns.
 //! Some checks are performed at compile-time however most things (including)
 //! correct default values) are only checked at runtime.
-//! 
+//!
 //! **Note** Since version `0.3.0` it is required to provide a database backend
 //! in order to compile `barrel`.
 //!
@@ -43,7 +43,7 @@
 //! configuration.
 //!
 //! To generate SQL strings you have two options. If you just want to run the
-//! migration yourself simply run `Migration::exec()` where you provide a
+//! migration yourself simply run `Migration::make()` where you provide a
 //! generic `SqlGenerator` type according to your database backend
 //!
 //! ```norun
@@ -57,7 +57,7 @@
 //! Running a migration with `barrel` is then super easy.
 //!
 //! ```norun
-//! m.execute(executor);
+//! m.execute::<_, Pg>(&mut executor)?;
 //! ```
 //!
 //! In this case `executor` is your provided type which implements the required
@@ -76,57 +76,137 @@
 // TODO: Make this "diesel" block prettier
 #[cfg(feature = "diesel-filled")]
 extern crate tempdir;
-#[cfg(feature = "diesel-filled")]
+#[cfg(any(
+    feature = "diesel",
+    feature = "sqlx",
+    feature = "postgres",
+    feature = "tokio-postgres",
+    feature = "rusqlite",
+    feature = "mysql-client",
+    feature = "mysql_async",
+    feature = "refinery"
+))]
 pub mod integrations;
 #[cfg(feature = "diesel-filled")]
-pub use integrations::*;
+pub use crate::integrations::*;
 #[cfg(feature = "diesel-filled")]
 extern crate diesel;
 
 pub mod backend;
+use crate::backend::SqlVariant;
+pub mod build;
+mod checksum;
 pub mod connectors;
 
+pub mod errors;
+pub use crate::errors::Error;
+
 pub mod table;
-pub use table::{Column, Table, TableMeta};
+pub use crate::table::{
+    Column, Constraint, Generated, Identity, Index, IndexKind, PartitionKind, Storage, Table,
+    TableMeta,
+};
 
 pub mod migration;
-pub use migration::Migration;
+pub use crate::migration::Migration;
+
+pub mod diff;
+pub mod embedded;
+pub mod schema;
+
+pub mod runner;
+pub use crate::runner::Runner;
+
+pub mod statement;
+pub mod tracking;
+
+pub mod value;
+pub use crate::value::Value;
+
+#[cfg(feature = "introspect")]
+pub mod introspect;
+
+#[cfg(feature = "macros")]
+pub use barrel_macros::embed_migrations;
 
 #[cfg(feature = "unstable")]
 pub mod types;
 
+#[cfg(feature = "serde")]
+mod serialize;
+
 #[cfg(test)]
 mod tests;
 
+use std::fmt;
 use std::rc::Rc;
 
 /// An enum set that represents a single change on a table
 #[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        into = "crate::serialize::TableChangeRepr",
+        from = "crate::serialize::TableChangeRepr"
+    )
+)]
 pub enum TableChange {
     /// Add a column of a name and type
     AddColumn(String, Column),
 
     /// Change an existing column
-    ChangeColumn(String, Column, Rc<Fn(&mut Column)>),
+    ChangeColumn(String, Column, Rc<dyn Fn(&mut Column)>),
 
     /// Simply rename a column
     RenameColumn(String, String),
 
+    /// Set the default of an existing column to an SQL expression,
+    /// or drop it if there is none
+    SetDefault(String, Option<String>),
+
+    /// Rename a constraint of the table from <old> to <new>
+    RenameConstraint(String, String),
+
+    /// Remove a named constraint from an existing table
+    DropConstraint(String),
+
+    /// Make an existing column `NOT NULL`, or nullable again, with its
+    /// definition if known (see `Schema::complete`)
+    SetNotNull(String, bool, Option<Column>),
+
     /// Remove a column
     DropColumn(String),
+
+    /// Add a raw SQL line to the table definition (or `ALTER TABLE`)
+    CustomLine(String),
+
+    /// Add a constraint over one or more columns, with an optional name
+    AddConstraint(Option<String>, Constraint),
+
+    /// Create a named index, after the table itself
+    AddIndex(String, Index),
 }
 
 /// An enum set that represents a single change on a database
 #[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        into = "crate::serialize::DatabaseChangeRepr",
+        from = "crate::serialize::DatabaseChangeRepr"
+    )
+)]
 pub enum DatabaseChange {
     /// Create a new table
-    CreateTable(Table, Rc<Fn(&mut Table)>),
+    CreateTable(Table, Rc<dyn Fn(&mut Table)>),
 
     /// Create a new table *only* if it doesn't exist yet
-    CreateTableIfNotExists(Table, Rc<Fn(&mut Table)>),
+    CreateTableIfNotExists(Table, Rc<dyn Fn(&mut Table)>),
 
     /// Change fields on an existing table
-    ChangeTable(Table, Rc<Fn(&mut Table)>),
+    ChangeTable(Table, Rc<dyn Fn(&mut Table)>),
 
     /// Rename a table
     RenameTable(String, String),
@@ -136,6 +216,34 @@ pub enum DatabaseChange {
 
     /// Only drop a table if it exists
     DropTableIfExists(String),
+
+    /// Create a partition of a partitioned table, with a name,
+    /// the parent table and the bounds of its values
+    CreatePartition(String, String, String),
+
+    /// Create a new table with the columns of an existing one
+    CreateTableLike(String, String),
+
+    /// Create a new table from the results of a query
+    CreateTableAs(String, String),
+
+    /// Create a row-level security policy with a name, for a table
+    CreatePolicy(String, String, Policy),
+
+    /// Rename an index from <old> to <new>
+    RenameIndex(String, String),
+
+    /// A raw SQL statement
+    CustomLine(String),
+
+    /// Insert a row of values into a table
+    Insert(String, Vec<(String, Value)>),
+
+    /// Drop a type which columns were declared with, e.g. a Postgres
+    /// enum, when reverting the migration that created them
+    ///
+    /// Backends which don't create types for columns skip it.
+    DropType(String),
 }
 
 /// Type enum to specificy the `type` of an SQL column. NOTE: Not all types are
@@ -145,16 +253,39 @@ pub enum DatabaseChange {
 /// metadata this type requires to the `add_column` function.
 ///
 /// ```norun
-/// t.add_column("posts", Type::Array(box Type::Foreign("posts")));
+/// t.add_column("posts", Type::Array(Box::new(Type::foreign("posts"))));
 /// ```
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Type {
     /// Create a simple "text" field
     Text,
 
+    /// A text field of up to 255 bytes
+    ///
+    /// MySQL picks its text types by the size of the content, the
+    /// other backends treat this and the other sizes like `Text`.
+    TinyText,
+
+    /// A text field of up to 16 MiB
+    MediumText,
+
+    /// A text field of up to 4 GiB
+    LongText,
+
     /// Provide a size limit for this field
     Varchar(usize),
 
+    /// A fixed-length string, padded with spaces
+    Char(usize),
+
+    /// Text which is compared case-insensitively
+    ///
+    /// Postgres needs the `citext` extension for it, which is created
+    /// ahead of the table. Other backends use a case-insensitive
+    /// collation, with MySQL limiting it to 255 characters.
+    Citext,
+
     /// Create a primary key regardless of database
     ///
     /// This type is more of a convenience wrapper around other
@@ -164,9 +295,23 @@ pub enum Type {
     /// **Be careful when using this type!**
     Primary,
 
-    /// Creates a 64-bit integer
+    /// Creates a 32-bit integer
     Integer,
 
+    /// Creates a 16-bit integer
+    SmallInt,
+
+    /// Creates a 64-bit integer
+    BigInt,
+
+    /// Creates an auto-incrementing 32-bit integer
+    ///
+    /// Unlike `Primary` it doesn't make the column the primary key
+    Serial,
+
+    /// Creates an auto-incrementing 64-bit integer
+    BigSerial,
+
     /// Creates a 32-bit float
     Float,
 
@@ -176,18 +321,613 @@ pub enum Type {
     /// Boring ol' boolean
     Boolean,
 
+    /// A fixed-length string of bits
+    ///
+    /// Backends without bit strings store the bits as bytes
+    Bit(usize),
+
+    /// A string of bits with a maximum length
+    ///
+    /// `0` removes the limit where possible.
+    Varbit(usize),
+
+    /// A universally unique identifier
+    ///
+    /// Backends without a native UUID type store it as text.
+    /// Use `Column::default_expr` to generate values in the database.
+    Uuid,
+
+    /// An IPv4 or IPv6 host address, with an optional netmask
+    ///
+    /// Backends without network types store it as text, as do
+    /// `Cidr` and `MacAddr`.
+    Inet,
+
+    /// An IPv4 or IPv6 network
+    Cidr,
+
+    /// A MAC address
+    MacAddr,
+
+    /// A JSON document
+    ///
+    /// Backends without a JSON type store it as text
+    Json,
+
+    /// A binary JSON document, as supported by Postgres
+    ///
+    /// Other backends store it like `Json`
+    Jsonb,
+
+    /// A set of key/value pairs, as supported by Postgres
+    ///
+    /// Postgres needs the `hstore` extension for it, which is created
+    /// ahead of the table. Other backends store it like `Json`.
+    Hstore,
+
+    /// A document prepared for full-text search (Postgres only)
+    TsVector,
+
+    /// A full-text search query (Postgres only)
+    TsQuery,
+
+    /// An XML document
+    ///
+    /// Backends without an XML type store it as text
+    Xml,
+
+    /// A calendar date
+    Date,
+
+    /// A time of day, with an optional number of fractional second digits
+    Time(Option<u8>),
+
+    /// A date and time of day, without a time zone
+    ///
+    /// Takes an optional number of fractional second digits
+    DateTime(Option<u8>),
+
+    /// A point in time, with a time zone
+    ///
+    /// Takes an optional number of fractional second digits. Backends
+    /// without time zone support store it like `DateTime`.
+    TimestampTz(Option<u8>),
+
+    /// A span of time, with an optional fields qualifier
+    ///
+    /// The qualifier limits the stored fields on Postgres, e.g.
+    /// `Interval(Some("DAY TO SECOND".into()))`. Backends without
+    /// intervals store a number of seconds instead.
+    Interval(Option<String>),
+
+    /// An exact number with a precision (total digits) and a scale
+    /// (digits after the decimal point)
+    Decimal(usize, usize),
+
+    /// A point on a plane
+    ///
+    /// This is the geometric type of Postgres, or a spatial point on
+    /// MySQL and MSSQL. SQLite stores it as text.
+    Point,
+
+    /// A PostGIS geometry with a shape and an optional SRID
+    ///
+    /// MySQL and MSSQL use their spatial types instead.
+    #[cfg(feature = "postgis")]
+    Geometry(GeometryKind, Option<u32>),
+
+    /// A PostGIS geography, in geodetic coordinates, with a shape
+    /// and an optional SRID
+    #[cfg(feature = "postgis")]
+    Geography(GeometryKind, Option<u32>),
+
+    /// A range of values of some kind (Postgres only)
+    Range(RangeKind),
+
+    /// An amount of money
+    ///
+    /// Uses the native `MONEY` type where one exists, which is tied to
+    /// the locale of the database. Other backends store a
+    /// `Decimal(19, 4)`. Use `Decimal` directly for portable amounts.
+    Money,
+
     /// Create a simple "binary" field
     Binary,
 
-    /// Provide the name of a table to point to
-    Foreign(&'static str),
+    /// Create a binary field with a maximum length in bytes
+    ///
+    /// Backends without a length limit ignore it. `0` removes the limit.
+    Varbinary(usize),
+
+    /// A string which can only hold one of a list of values
+    ///
+    /// The first field names the type, which Postgres needs to create it
+    /// before the column (`CREATE TYPE ... AS ENUM`). MySQL has a native
+    /// `ENUM` column and other backends store text with a `CHECK`.
+    Enum(String, Vec<String>),
+
+    /// A MySQL `SET`, which holds any number of values from a list
+    ///
+    /// Other backends reject it.
+    Set(Vec<String>),
+
+    /// Point to a column of another table, `id` unless specified
+    Foreign(
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "crate::serialize::foreign_key")
+        )]
+        ForeignKey,
+    ),
 
     /// Used for types not Implemented by the migration system.
     /// This field is not statically checked.
-    Custom(&'static str),
+    Custom(String),
+
+    /// A custom type with a different name on each backend
+    ///
+    /// Backends without an entry reject the type. Cockroach falls
+    /// back to the entry for Postgres.
+    CustomPer(Vec<(SqlVariant, String)>),
 
-    // FIXME: Figure out a way to do this nicely
-    // Foreign(&'static str, &'static str),
     /// Any type can also exist as an array type
     Array(Box<Type>),
 }
+
+impl Type {
+    /// Create a foreign key type pointing to a table, or a `ForeignKey`
+    pub fn foreign<F: Into<ForeignKey>>(key: F) -> Type {
+        Type::Foreign(key.into())
+    }
+
+    /// Create a type which is rendered as the given SQL
+    pub fn custom<S: Into<String>>(sql: S) -> Type {
+        Type::Custom(sql.into())
+    }
+
+    /// Create a custom type with the SQL to render on each backend
+    ///
+    /// ```norun
+    /// Type::custom_per(&[(SqlVariant::Pg, "LTREE"), (SqlVariant::MySql, "VARCHAR(255)")])
+    /// ```
+    pub fn custom_per(types: &[(SqlVariant, &str)]) -> Type {
+        Type::CustomPer(types.iter().map(|&(v, t)| (v, t.to_string())).collect())
+    }
+
+    /// Create an enum type with a name and its values
+    pub fn enumeration<S: Into<String>>(name: S, values: &[&str]) -> Type {
+        Type::Enum(name.into(), values.iter().map(|v| v.to_string()).collect())
+    }
+
+    /// Create an array type with a number of dimensions
+    ///
+    /// `Type::array(Type::Text, 2)` is a `TEXT[][]` on Postgres.
+    /// A dimension of `0` returns the inner type as is.
+    pub fn array(inner: Type, dimensions: usize) -> Type {
+        (0..dimensions).fold(inner, |t, _| Type::Array(Box::new(t)))
+    }
+
+    /// Create a MySQL set type with its values
+    pub fn set(values: &[&str]) -> Type {
+        Type::Set(values.iter().map(|v| v.to_string()).collect())
+    }
+
+    /// The name of an enum, also as the element of an array
+    pub(crate) fn named(&self) -> Option<String> {
+        match *self {
+            Type::Enum(ref name, _) => Some(name.clone()),
+            Type::Array(ref inner) => inner.named(),
+            _ => None,
+        }
+    }
+
+    /// Give an enum without a name (see `types::enumeration`) one
+    pub(crate) fn name_enum(&mut self, default: &str) {
+        match *self {
+            Type::Enum(ref mut name, _) if name.is_empty() => *name = default.into(),
+            Type::Array(ref mut inner) => inner.name_enum(default),
+            _ => {}
+        }
+    }
+}
+
+/// The kind of values a `Type::Range` holds
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RangeKind {
+    /// 32-bit integers (`INT4RANGE`)
+    Int4,
+
+    /// 64-bit integers (`INT8RANGE`)
+    Int8,
+
+    /// Exact numbers (`NUMRANGE`)
+    Numeric,
+
+    /// Timestamps without a time zone (`TSRANGE`)
+    Timestamp,
+
+    /// Timestamps with a time zone (`TSTZRANGE`)
+    Timestamptz,
+
+    /// Calendar dates (`DATERANGE`)
+    Date,
+}
+
+/// The shape of a `Type::Geometry` or `Type::Geography`
+#[cfg(feature = "postgis")]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GeometryKind {
+    /// Any shape
+    Geometry,
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection,
+}
+
+#[cfg(feature = "postgis")]
+impl GeometryKind {
+    /// The name of the shape in SQL
+    pub(crate) fn name(self) -> &'static str {
+        use self::GeometryKind::*;
+        match self {
+            Geometry => "GEOMETRY",
+            Point => "POINT",
+            LineString => "LINESTRING",
+            Polygon => "POLYGON",
+            MultiPoint => "MULTIPOINT",
+            MultiLineString => "MULTILINESTRING",
+            MultiPolygon => "MULTIPOLYGON",
+            GeometryCollection => "GEOMETRYCOLLECTION",
+        }
+    }
+}
+
+/// The column a `Type::Foreign` points to, and what happens when it changes
+///
+/// ```
+/// # use barrel::{ForeignKey, ReferentialAction, Type};
+/// let t = Type::foreign(
+///     ForeignKey::new("users")
+///         .column("uid")
+///         .on_delete(ReferentialAction::Cascade),
+/// );
+/// ```
+#[derive(PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForeignKey {
+    /// The table to point to
+    pub table: String,
+
+    /// The column to point to, `id` if there is none
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub column: Option<String>,
+
+    /// What happens when the row pointed to is deleted
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub on_delete: Option<ReferentialAction>,
+
+    /// What happens when the column pointed to is changed
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub on_update: Option<ReferentialAction>,
+
+    /// Can the key be checked at the end of a transaction instead
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub deferrable: Option<Deferrable>,
+
+    /// The name of the constraint, instead of one picked by the database
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub name: Option<String>,
+}
+
+impl ForeignKey {
+    /// Point to the `id` of a table
+    pub fn new<S: Into<String>>(table: S) -> ForeignKey {
+        ForeignKey {
+            table: table.into(),
+            column: None,
+            on_delete: None,
+            on_update: None,
+            deferrable: None,
+            name: None,
+        }
+    }
+
+    /// Point to another column than `id`
+    pub fn column<S: Into<String>>(self, column: S) -> ForeignKey {
+        ForeignKey {
+            column: Some(column.into()),
+            ..self
+        }
+    }
+
+    /// Set what happens when the row pointed to is deleted
+    pub fn on_delete(self, action: ReferentialAction) -> ForeignKey {
+        ForeignKey {
+            on_delete: Some(action),
+            ..self
+        }
+    }
+
+    /// Set what happens when the column pointed to is changed
+    pub fn on_update(self, action: ReferentialAction) -> ForeignKey {
+        ForeignKey {
+            on_update: Some(action),
+            ..self
+        }
+    }
+
+    /// Allow checking the key at the end of a transaction with
+    /// `SET CONSTRAINTS ... DEFERRED`
+    pub fn deferrable(self) -> ForeignKey {
+        ForeignKey {
+            deferrable: Some(Deferrable::InitiallyImmediate),
+            ..self
+        }
+    }
+
+    /// Check the key at the end of each transaction
+    ///
+    /// Useful for circular references and bulk loads.
+    pub fn initially_deferred(self) -> ForeignKey {
+        ForeignKey {
+            deferrable: Some(Deferrable::InitiallyDeferred),
+            ..self
+        }
+    }
+
+    /// Name the constraint, so that it can be dropped by name later
+    pub fn constraint_name<S: Into<String>>(self, name: S) -> ForeignKey {
+        ForeignKey {
+            name: Some(name.into()),
+            ..self
+        }
+    }
+
+    /// The column pointed to
+    #[cfg(any(feature = "mysql", feature = "mssql"))]
+    pub(crate) fn target(&self) -> &str {
+        self.column.as_deref().unwrap_or("id")
+    }
+
+    /// The `ON DELETE`, `ON UPDATE` and `DEFERRABLE` clauses, if any
+    #[cfg(any(
+        feature = "pg",
+        feature = "mysql",
+        feature = "sqlite3",
+        feature = "mssql",
+        feature = "ansi"
+    ))]
+    pub(crate) fn actions(&self) -> String {
+        let mut s = String::new();
+        if let Some(action) = self.on_delete {
+            s.push_str(&format!(" ON DELETE {}", action.sql()));
+        }
+        if let Some(action) = self.on_update {
+            s.push_str(&format!(" ON UPDATE {}", action.sql()));
+        }
+        if let Some(deferrable) = self.deferrable {
+            s.push_str(deferrable.sql());
+        }
+        s
+    }
+}
+
+/// Keys which only name a table print like the table name did before,
+/// so that the checksums of existing migrations stay the same
+impl fmt::Debug for ForeignKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self == ForeignKey::new(self.table.as_str()) {
+            true => fmt::Debug::fmt(&self.table, f),
+            false => f
+                .debug_struct("ForeignKey")
+                .field("table", &self.table)
+                .field("column", &self.column)
+                .field("on_delete", &self.on_delete)
+                .field("on_update", &self.on_update)
+                .field("deferrable", &self.deferrable)
+                .field("name", &self.name)
+                .finish(),
+        }
+    }
+}
+
+impl From<&str> for ForeignKey {
+    fn from(table: &str) -> Self {
+        ForeignKey::new(table)
+    }
+}
+
+impl From<String> for ForeignKey {
+    fn from(table: String) -> Self {
+        ForeignKey::new(table)
+    }
+}
+
+/// What a foreign key does when the row it points to changes
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReferentialAction {
+    /// Delete or change the rows pointing to it as well
+    Cascade,
+
+    /// Set the rows pointing to it to `NULL`
+    SetNull,
+
+    /// Set the rows pointing to it to their default
+    SetDefault,
+
+    /// Fail right away if any rows point to it
+    Restrict,
+
+    /// Fail at the end of the statement if any rows still point to it
+    NoAction,
+}
+
+impl ReferentialAction {
+    /// The action in SQL
+    #[cfg(any(
+        feature = "pg",
+        feature = "mysql",
+        feature = "sqlite3",
+        feature = "mssql",
+        feature = "ansi"
+    ))]
+    pub(crate) fn sql(self) -> &'static str {
+        use self::ReferentialAction::*;
+        match self {
+            Cascade => "CASCADE",
+            SetNull => "SET NULL",
+            SetDefault => "SET DEFAULT",
+            Restrict => "RESTRICT",
+            NoAction => "NO ACTION",
+        }
+    }
+}
+
+/// When a deferrable constraint is checked, unless a transaction says otherwise
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Deferrable {
+    /// At the end of each statement
+    InitiallyImmediate,
+
+    /// At the end of each transaction
+    InitiallyDeferred,
+}
+
+impl Deferrable {
+    /// The `DEFERRABLE` clause in SQL
+    #[cfg(any(
+        feature = "pg",
+        feature = "mysql",
+        feature = "sqlite3",
+        feature = "mssql",
+        feature = "ansi"
+    ))]
+    pub(crate) fn sql(self) -> &'static str {
+        match self {
+            Deferrable::InitiallyImmediate => " DEFERRABLE INITIALLY IMMEDIATE",
+            Deferrable::InitiallyDeferred => " DEFERRABLE INITIALLY DEFERRED",
+        }
+    }
+}
+
+/// A row-level security policy, which limits the rows a query can see or change
+///
+/// ```
+/// # use barrel::*;
+/// let policy = Policy::new()
+///     .to(&["app_user"])
+///     .using("tenant_id = current_setting('app.tenant_id')::int");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Policy {
+    /// The commands the policy applies to, instead of all of them
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub command: Option<PolicyCommand>,
+
+    /// The roles the policy applies to, instead of everyone
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub roles: Vec<String>,
+
+    /// Which existing rows can be seen, updated or deleted
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub using: Option<String>,
+
+    /// Which new rows can be inserted or updated
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub check: Option<String>,
+
+    /// Must pass in addition to the other policies, instead of as an alternative
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub restrictive: bool,
+}
+
+impl Policy {
+    /// A policy for all commands and roles, which doesn't allow any rows yet
+    pub fn new() -> Policy {
+        Policy {
+            command: None,
+            roles: Vec::new(),
+            using: None,
+            check: None,
+            restrictive: false,
+        }
+    }
+
+    /// Only apply the policy to one command
+    pub fn command(self, command: PolicyCommand) -> Policy {
+        Policy {
+            command: Some(command),
+            ..self
+        }
+    }
+
+    /// Only apply the policy to some roles
+    pub fn to(self, roles: &[&str]) -> Policy {
+        Policy {
+            roles: roles.iter().map(|r| r.to_string()).collect(),
+            ..self
+        }
+    }
+
+    /// Allow the existing rows which match an expression
+    pub fn using<S: Into<String>>(self, expr: S) -> Policy {
+        Policy {
+            using: Some(expr.into()),
+            ..self
+        }
+    }
+
+    /// Allow the new rows which match an expression
+    pub fn with_check<S: Into<String>>(self, expr: S) -> Policy {
+        Policy {
+            check: Some(expr.into()),
+            ..self
+        }
+    }
+
+    /// Require the policy to pass in addition to the permissive ones
+    pub fn restrictive(self) -> Policy {
+        Policy {
+            restrictive: true,
+            ..self
+        }
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy::new()
+    }
+}
+
+/// A command that a row-level security policy can be limited to
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PolicyCommand {
+    Select,
+    Insert,
+    Update,
+    Delete,
+}
+
+impl PolicyCommand {
+    /// The command in SQL
+    pub(crate) fn sql(self) -> &'static str {
+        use self::PolicyCommand::*;
+        match self {
+            Select => "SELECT",
+            Insert => "INSERT",
+            Update => "UPDATE",
+            Delete => "DELETE",
+        }
+    }
+}
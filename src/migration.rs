@@ -3,42 +3,83 @@
 //! A migration can be done for a specific schema which contains
 //! multiple additions or removables from a database or table.
 //!
-//! At the end of crafting a migration you can use `Migration::exec` to
+//! At the end of crafting a migration you can use `Migration::make` to
 //! get the raw SQL string for a database backend or `Migration::revert`
 //! to try to auto-infer the migration rollback. In cases where that
-//! can't be done the `Result<String, RevertError>` will not unwrap.
+//! can't be done the `Result<String, Error>` will not unwrap.
 //!
-//! You can also use `Migration::exec` with your SQL connection for convenience
+//! You can also use `Migration::execute` with your SQL connection for convenience
 //! if you're a library developer.
 
 use super::table::{Table, TableMeta};
-use super::{DatabaseChange, Type};
+use super::{DatabaseChange, Error, Policy, TableChange, Value};
 
-use super::backend::SqlGenerator;
-use super::connectors::DatabaseExecutor;
+use super::backend::{SqlGenerator, SqlVariant};
+use super::checksum;
+use super::connectors::{AsyncDatabaseExecutor, DatabaseExecutor, ExecuteFuture};
+use super::schema::{Schema, TableSchema};
+use super::statement::{self, Operation, Statement};
+use super::tracking;
 
 use std::rc::Rc;
 
 /// Represents a schema migration on a database
+///
+/// All changes registered on a migration make up its `up` step.
+/// The changes which undo them can be registered via `down`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Migration {
     #[doc(hidden)]
     pub schema: String,
     #[doc(hidden)]
     pub changes: Vec<DatabaseChange>,
+    #[doc(hidden)]
+    pub down: Option<Box<Migration>>,
+    #[doc(hidden)]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub idempotent: bool,
+}
+
+impl Default for Migration {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Migration {
     pub fn new() -> Migration {
-        return Migration {
+        Migration {
             schema: String::new(),
             changes: Vec::new(),
-        };
+            down: None,
+            idempotent: false,
+        }
     }
 
     /// Specify a database schema name for this migration
     pub fn schema<S: Into<String>>(mut self, schema: S) -> Migration {
         self.schema = schema.into();
-        return self;
+        self
+    }
+
+    /// Guard all statements with `IF NOT EXISTS` or `IF EXISTS`
+    ///
+    /// The generated SQL can then be run again on a database it was
+    /// already applied to, e.g. in bootstrap scripts. Tables are
+    /// created and dropped conditionally on backends which support it
+    /// (all but ANSI SQL), and so are columns (Postgres, CockroachDB
+    /// and, for dropping columns, SQL Server). Other statements, like
+    /// renames, aren't guarded.
+    ///
+    /// ```
+    /// # use barrel::*;
+    /// let mut m = Migration::new();
+    /// m.set_idempotent(true);
+    /// m.drop_table("sessions");
+    /// ```
+    pub fn set_idempotent(&mut self, idempotent: bool) -> &mut Migration {
+        self.idempotent = idempotent;
+        self
     }
 
     /// Creates the SQL for this migration for a specific backend
@@ -46,96 +87,702 @@ impl Migration {
     /// This function copies state and does not touch the original
     /// migration layout. This allows you to call `revert` later on
     /// in the process to auto-infer the down-behaviour
-    pub fn make<T: SqlGenerator>(&self) -> String {
-        use DatabaseChange::*;
-        let mut s = String::new();
+    ///
+    /// Panics if the migration contains changes that the backend
+    /// can't express. Use `try_make` to handle these cases yourself.
+    pub fn make<T: SqlGenerator + Default>(&self) -> String {
+        self.make_with(&T::default())
+    }
+
+    /// Creates the SQL for this migration with a configured generator
+    ///
+    /// See `make` for details
+    pub fn make_with<T: SqlGenerator>(&self, gen: &T) -> String {
+        match self.try_make_with(gen) {
+            Ok(sql) => sql,
+            Err(e) => panic!("Failed to generate migration: {}", e),
+        }
+    }
+
+    /// Creates the SQL for this migration for a specific backend
+    ///
+    /// Returns an error if the migration contains changes that
+    /// the backend can't express
+    pub fn try_make<T: SqlGenerator + Default>(&self) -> Result<String, Error> {
+        self.try_make_with(&T::default())
+    }
+
+    /// Creates the SQL for this migration with a configured generator
+    ///
+    /// See `try_make` for details
+    pub fn try_make_with<T: SqlGenerator>(&self, gen: &T) -> Result<String, Error> {
+        self.render(gen, self.idempotent)
+    }
+
+    /// Creates the SQL for this migration, wrapped in a transaction
+    ///
+    /// Statements which the backend can't run inside a transaction
+    /// (like `CREATE INDEX CONCURRENTLY` on Postgres) are emitted after
+    /// it. This is meant for scripts that are run by other tools: the
+    /// executors in `integrations` already use a transaction. Note that
+    /// MySQL commits implicitly after most schema changes.
+    ///
+    /// Panics if the migration contains changes that the backend
+    /// can't express. Use `try_make_transactional` to handle these
+    /// cases yourself.
+    pub fn make_transactional<T: SqlGenerator + Default>(&self) -> String {
+        self.make_transactional_with(&T::default())
+    }
+
+    /// Creates the SQL for this migration, wrapped in a transaction,
+    /// with a configured generator
+    ///
+    /// See `make_transactional` for details
+    pub fn make_transactional_with<T: SqlGenerator>(&self, gen: &T) -> String {
+        match self.try_make_transactional_with(gen) {
+            Ok(sql) => sql,
+            Err(e) => panic!("Failed to generate migration: {}", e),
+        }
+    }
+
+    /// Creates the SQL for this migration, wrapped in a transaction
+    pub fn try_make_transactional<T: SqlGenerator + Default>(&self) -> Result<String, Error> {
+        self.try_make_transactional_with(&T::default())
+    }
+
+    /// Creates the SQL for this migration, wrapped in a transaction,
+    /// with a configured generator
+    pub fn try_make_transactional_with<T: SqlGenerator>(&self, gen: &T) -> Result<String, Error> {
+        let (inside, outside): (Vec<_>, Vec<_>) = self
+            .render_statements(gen, self.idempotent)?
+            .into_iter()
+            .map(|s| s.sql)
+            .partition(|s| gen.transactional(s));
+
+        let mut stmts = Vec::new();
+        if !inside.is_empty() {
+            stmts.push(gen.begin_transaction());
+            stmts.extend(inside);
+            stmts.push(gen.commit_transaction());
+        }
+        stmts.extend(outside);
+        Ok(stmts.join("; "))
+    }
+
+    /// Creates the individual statements of this migration
+    /// for a backend chosen at runtime
+    ///
+    /// Panics if the migration contains changes that the backend
+    /// can't express. Use `try_statements` to handle these cases yourself.
+    pub fn statements(&self, variant: SqlVariant) -> Vec<Statement> {
+        match self.try_statements(variant) {
+            Ok(stmts) => stmts,
+            Err(e) => panic!("Failed to generate migration: {}", e),
+        }
+    }
+
+    /// Creates the individual statements of this migration
+    /// for a backend chosen at runtime
+    pub fn try_statements(&self, variant: SqlVariant) -> Result<Vec<Statement>, Error> {
+        variant.statements_for(self)
+    }
+
+    /// Creates the individual statements of this migration
+    /// with a configured generator
+    pub fn try_statements_with<T: SqlGenerator>(&self, gen: &T) -> Result<Vec<Statement>, Error> {
+        self.render_statements(gen, self.idempotent)
+    }
+
+    /// Generate the SQL of this migration, optionally guarded
+    fn render<T: SqlGenerator>(&self, gen: &T, idempotent: bool) -> Result<String, Error> {
+        Ok(statement::join(&self.render_statements(gen, idempotent)?))
+    }
+
+    /// Generate the individual statements of this migration
+    fn render_statements<T: SqlGenerator>(
+        &self,
+        gen: &T,
+        idempotent: bool,
+    ) -> Result<Vec<Statement>, Error> {
+        use crate::DatabaseChange::*;
+        let mut stmts = Vec::new();
+
+        /* Backends without `IF [NOT] EXISTS` get plain table statements */
+        let guarded = idempotent && gen.table_guards();
 
         /* What happens in make, stays in make (sort of) */
-        let mut changes = self.changes.clone();
+        let mut changes = Schema::new().complete_changes(&self.changes);
         for change in &mut changes {
-            match change {
-                &mut CreateTable(ref mut t, ref mut cb) => {
-                    if t.meta.has_id {
-                        t.add_column("id", Type::Primary).increments();
-                    }
+            gen.validate_change(change)?;
+
+            match *change {
+                CreateTable(ref mut t, ref mut cb) if !guarded => {
+                    let body = Migration::create_body(gen, t, cb.as_ref())?;
+                    Migration::prepare_columns(gen, t, &mut stmts);
+                    stmts.push(Statement::new(
+                        format!(
+                            "{} ({}){}",
+                            gen.create_table_from(&t.meta, false),
+                            body,
+                            gen.table_options(&t.meta)
+                        ),
+                        t.meta.name(),
+                        Operation::CreateTable,
+                    ));
+                    Migration::finish_table(gen, t, &mut stmts);
+                    Migration::create_indexes(gen, t, false, &mut stmts);
+                }
+                CreateTable(ref mut t, ref mut cb)
+                | CreateTableIfNotExists(ref mut t, ref mut cb) => {
+                    let body = Migration::create_body(gen, t, cb.as_ref())?;
+                    Migration::prepare_columns(gen, t, &mut stmts);
+                    stmts.push(Statement::new(
+                        format!(
+                            "{} ({}){}",
+                            gen.create_table_from(&t.meta, true),
+                            body,
+                            gen.table_options(&t.meta)
+                        ),
+                        t.meta.name(),
+                        Operation::CreateTable,
+                    ));
+                    Migration::finish_table(gen, t, &mut stmts);
+                    Migration::create_indexes(gen, t, true, &mut stmts);
+                }
+                ChangeTable(ref mut t, ref mut cb) => {
+                    cb(t);
+                    t.validate_alter(gen)?;
+                    Migration::prepare_columns(gen, t, &mut stmts);
 
-                    cb(t); // Run the user code
-                    let vec = t.make::<T>(false);
-                    s.push_str(&T::create_table(&t.meta.name()));
-                    s.push_str(" (");
-                    let l = vec.len();
-                    for (i, slice) in vec.iter().enumerate() {
-                        s.push_str(slice);
+                    /* Not all databases can do more than one thing per ALTER */
+                    let alter = gen.alter_table(&t.meta.name());
+                    let slices = t.make_guarded(gen, true, idempotent);
+                    let changes = t
+                        .changes
+                        .iter()
+                        .filter(|c| c.operation() != Operation::CreateIndex);
 
-                        if i < l - 1 {
-                            s.push_str(", ");
+                    /* Validation made sure that rebuilt tables are known */
+                    let mut table = t
+                        .known
+                        .clone()
+                        .unwrap_or_else(|| TableSchema::new(t.meta.name()));
+                    let mut rebuild = false;
+                    for (change, slice) in changes.zip(slices) {
+                        /* Consecutive changes share a single rebuild */
+                        if rebuild && !gen.rebuilds_table(change) {
+                            Migration::rebuild_table(gen, &table, &mut stmts);
+                            rebuild = false;
                         }
+                        table.apply(change);
+                        if gen.rebuilds_table(change) {
+                            rebuild = true;
+                            continue;
+                        }
+                        let sql = match *change {
+                            TableChange::RenameColumn(ref old, ref new) => {
+                                gen.rename_table_column(&t.meta.name, old, new)
+                            }
+                            _ => format!("{} {}", alter, slice),
+                        };
+                        stmts.push(Statement::new(sql, t.meta.name(), change.operation()));
+                    }
+                    if rebuild {
+                        Migration::rebuild_table(gen, &table, &mut stmts);
+                    }
+                    for (op, sql) in gen.alter_table_options(&t.meta) {
+                        stmts.push(Statement::new(sql, t.meta.name(), op));
+                    }
+                    Migration::create_indexes(gen, t, idempotent, &mut stmts);
+                }
+                DropTable(ref name) if !guarded => stmts.push(Statement::new(
+                    gen.drop_table(name),
+                    name.clone(),
+                    Operation::DropTable,
+                )),
+                DropTable(ref name) | DropTableIfExists(ref name) => stmts.push(Statement::new(
+                    gen.drop_table_if_exists(name),
+                    name.clone(),
+                    Operation::DropTable,
+                )),
+                CreatePartition(ref name, ref parent, ref bounds) => stmts.push(Statement::new(
+                    gen.create_partition(name, parent, bounds, guarded),
+                    name.clone(),
+                    Operation::CreateTable,
+                )),
+                CreateTableLike(ref name, ref source) => stmts.push(Statement::new(
+                    gen.create_table_like(name, source, guarded),
+                    name.clone(),
+                    Operation::CreateTable,
+                )),
+                CreateTableAs(ref name, ref query) => stmts.push(Statement::new(
+                    gen.create_table_as(name, query, guarded),
+                    name.clone(),
+                    Operation::CreateTable,
+                )),
+                CreatePolicy(ref name, ref table, ref policy) => stmts.push(Statement::new(
+                    gen.create_policy(name, table, policy),
+                    table.clone(),
+                    Operation::CreatePolicy,
+                )),
+                RenameIndex(ref old, ref new) => stmts.push(Statement::new(
+                    gen.rename_index(old, new),
+                    old.clone(),
+                    Operation::RenameIndex,
+                )),
+                RenameTable(ref old, ref new) => stmts.push(Statement::new(
+                    gen.rename_table(old, new),
+                    old.clone(),
+                    Operation::RenameTable,
+                )),
+                CustomLine(ref sql) => {
+                    stmts.push(Statement::new(sql.clone(), "", Operation::Custom))
+                }
+                Insert(ref table, ref row) => stmts.push(Statement::new(
+                    gen.insert(table, row),
+                    table.clone(),
+                    Operation::Insert,
+                )),
+                DropType(ref name) => {
+                    if let Some(sql) = gen.drop_type(name, idempotent) {
+                        stmts.push(Statement::new(sql, name.clone(), Operation::DropType));
                     }
-                    s.push_str(")");
                 }
-                &mut DropTable(ref name) => s.push_str(&T::drop_table(name)),
-                &mut DropTableIfExists(ref name) => s.push_str(&T::drop_table_if_exists(name)),
-                &mut RenameTable(ref old, ref new) => s.push_str(&T::rename_table(old, new)),
-                _ => {}
             }
         }
 
-        return s;
+        Ok(stmts)
+    }
+
+    /// Creates the SQL for this migration for a backend chosen at runtime
+    ///
+    /// Panics if the migration contains changes that the backend
+    /// can't express. Use `try_make_from` to handle these cases yourself.
+    pub fn make_from(&self, variant: SqlVariant) -> String {
+        match self.try_make_from(variant) {
+            Ok(sql) => sql,
+            Err(e) => panic!("Failed to generate migration: {}", e),
+        }
+    }
+
+    /// Creates the SQL for this migration for a backend chosen at runtime
+    pub fn try_make_from(&self, variant: SqlVariant) -> Result<String, Error> {
+        variant.run_for(self)
+    }
+
+    /// Register the changes that undo this migration
+    ///
+    /// The closure is given a new, empty migration to describe
+    /// the `down` step on. Calling this again replaces it.
+    ///
+    /// ```
+    /// # use barrel::*;
+    /// let mut m = Migration::new();
+    /// m.create_table("users", |t| {
+    ///     t.add_column("name", Type::Text);
+    /// });
+    /// m.down(|m| m.drop_table("users"));
+    /// ```
+    pub fn down<F: Fn(&mut Migration)>(&mut self, cb: F) -> &mut Migration {
+        let mut down = Migration::new();
+        cb(&mut down);
+        self.down = Some(Box::new(down));
+        self
+    }
+
+    /// Creates the SQL for the `down` step of this migration
+    ///
+    /// If no `down` step was registered, it is inferred via `revert`.
+    ///
+    /// Panics if the `down` step can't be inferred or expressed
+    /// by the backend. Use `try_make_down` to handle
+    /// these cases yourself.
+    pub fn make_down<T: SqlGenerator + Default>(&self) -> String {
+        self.make_down_with(&T::default())
+    }
+
+    /// Creates the SQL for the `down` step with a configured generator
+    ///
+    /// See `make_down` for details
+    pub fn make_down_with<T: SqlGenerator>(&self, gen: &T) -> String {
+        match self.try_make_down_with(gen) {
+            Ok(sql) => sql,
+            Err(e) => panic!("Failed to generate migration: {}", e),
+        }
+    }
+
+    /// Creates the SQL for the `down` step of this migration
+    pub fn try_make_down<T: SqlGenerator + Default>(&self) -> Result<String, Error> {
+        self.try_make_down_with(&T::default())
+    }
+
+    /// Creates the SQL for the `down` step with a configured generator
+    pub fn try_make_down_with<T: SqlGenerator>(&self, gen: &T) -> Result<String, Error> {
+        match self.down {
+            Some(ref down) => down.render(gen, self.idempotent || down.idempotent),
+            None => self.revert_with(gen),
+        }
+    }
+
+    /// Generate the individual statements of the `down` step
+    pub(crate) fn down_statements<T: SqlGenerator>(
+        &self,
+        gen: &T,
+    ) -> Result<Vec<Statement>, Error> {
+        match self.down {
+            Some(ref down) => down.render_statements(gen, self.idempotent || down.idempotent),
+            None => {
+                let down = self.reversed()?;
+                down.render_statements(gen, down.idempotent)
+            }
+        }
+    }
+
+    /// Run the user code for a new table and return its column definitions
+    fn create_body<T: SqlGenerator>(
+        gen: &T,
+        t: &mut Table,
+        cb: &dyn Fn(&mut Table),
+    ) -> Result<String, Error> {
+        t.prepare(cb);
+        t.validate(gen)?;
+        Ok(t.make(gen, false).join(", "))
+    }
+
+    /// Add the statements that new columns of a table need beforehand
+    ///
+    /// Statements are only added once per migration, so that columns
+    /// can share e.g. a type or extension.
+    fn prepare_columns<T: SqlGenerator>(gen: &T, t: &Table, stmts: &mut Vec<Statement>) {
+        for change in &t.changes {
+            if let TableChange::AddColumn(_, ref col) = *change {
+                for (op, sql) in gen.prepare_column(col) {
+                    if !stmts.iter().any(|s| s.sql == sql) {
+                        stmts.push(Statement::new(sql, t.meta.name(), op));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Add the statements that a new table needs afterwards
+    fn finish_table<T: SqlGenerator>(gen: &T, t: &Table, stmts: &mut Vec<Statement>) {
+        for (op, sql) in gen.finish_table(t) {
+            stmts.push(Statement::new(sql, t.meta.name(), op));
+        }
+    }
+
+    /// Add the statements that create a table again with the given
+    /// columns, copy its rows and replace the old table
+    ///
+    /// Indexes and constraints other than those of columns aren't part
+    /// of the schema, and aren't created again.
+    fn rebuild_table<T: SqlGenerator>(gen: &T, table: &TableSchema, stmts: &mut Vec<Statement>) {
+        let tmp = format!("new_{}", table.name);
+        let mut t = Table::new(tmp.clone());
+        t.meta.has_id = false;
+        t.changes = table
+            .columns
+            .iter()
+            .map(|(name, col)| TableChange::AddColumn(name.clone(), col.clone()))
+            .collect();
+
+        let columns = table
+            .columns
+            .iter()
+            .map(|c| gen.quote(&c.0))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let body = t.make(gen, false).join(", ");
+
+        stmts.push(Statement::new(
+            format!("{} ({})", gen.create_table(&tmp), body),
+            tmp.clone(),
+            Operation::CreateTable,
+        ));
+        stmts.push(Statement::new(
+            format!(
+                "INSERT INTO {} ({}) SELECT {} FROM {}",
+                gen.quote(&tmp),
+                columns,
+                columns,
+                gen.quote(&table.name)
+            ),
+            tmp.clone(),
+            Operation::Insert,
+        ));
+        stmts.push(Statement::new(
+            gen.drop_table(&table.name),
+            table.name.clone(),
+            Operation::DropTable,
+        ));
+        stmts.push(Statement::new(
+            gen.rename_table(&tmp, &table.name),
+            tmp,
+            Operation::RenameTable,
+        ));
+    }
+
+    /// Add the statements that create the indexes of a table
+    fn create_indexes<T: SqlGenerator>(
+        gen: &T,
+        t: &Table,
+        guarded: bool,
+        stmts: &mut Vec<Statement>,
+    ) {
+        for sql in t.make_indexes(gen, guarded) {
+            stmts.push(Statement::new(sql, t.meta.name(), Operation::CreateIndex));
+        }
     }
 
     /// Automatically infer the `down` step of this migration
     ///
-    /// Will thrown an error if behaviour is ambigous or not
+    /// Will return an error if behaviour is ambigous or not
     /// possible to infer (e.g. revert a `drop_table`)
-    pub fn revert<T: SqlGenerator>(&self) -> String {
-        unimplemented!()
+    pub fn revert<T: SqlGenerator + Default>(&self) -> Result<String, Error> {
+        self.revert_with(&T::default())
+    }
+
+    /// Automatically infer the `down` step with a configured generator
+    ///
+    /// See `revert` for details
+    pub fn revert_with<T: SqlGenerator>(&self, gen: &T) -> Result<String, Error> {
+        self.reversed()?.try_make_with(gen)
+    }
+
+    /// Create a migration with the changes that undo this one
+    ///
+    /// Changes are reverted in the opposite order they were made in.
+    /// Dropping tables or columns can't be undone, because their
+    /// previous definition isn't known. Types created for new columns,
+    /// like Postgres enums, are dropped at the end.
+    pub fn reversed(&self) -> Result<Migration, Error> {
+        use crate::DatabaseChange::*;
+        let mut m = Migration::new();
+        m.idempotent = self.idempotent;
+
+        /* Types are dropped last, once no table uses them anymore */
+        let mut types: Vec<String> = Vec::new();
+        let mut dropped = |t: &Table| {
+            for name in t.column_types() {
+                if !types.contains(&name) {
+                    types.push(name);
+                }
+            }
+        };
+
+        for change in self.changes.iter().rev() {
+            m.changes.push(match *change {
+                CreateTable(ref t, ref cb) | CreateTableIfNotExists(ref t, ref cb) => {
+                    let mut t = t.clone();
+                    t.prepare(cb.as_ref());
+                    dropped(&t);
+                    match *change {
+                        CreateTable(_, _) => DropTable(t.meta.name()),
+                        _ => DropTableIfExists(t.meta.name()),
+                    }
+                }
+                ChangeTable(ref t, ref cb) => {
+                    let mut t = t.clone();
+                    cb(&mut t);
+                    dropped(&t);
+                    ChangeTable(t.reversed()?, Rc::new(|_| {}))
+                }
+                RenameTable(ref old, ref new) => RenameTable(new.clone(), old.clone()),
+                RenameIndex(ref old, ref new) => RenameIndex(new.clone(), old.clone()),
+                CreatePartition(ref name, _, _)
+                | CreateTableLike(ref name, _)
+                | CreateTableAs(ref name, _) => DropTable(name.clone()),
+                DropTable(ref name) | DropTableIfExists(ref name) => {
+                    return Err(Error::Irreversible(format!("Dropping table `{}`", name)))
+                }
+                CreatePolicy(ref name, _, _) => {
+                    return Err(Error::Irreversible(format!("Creating policy `{}`", name)))
+                }
+                CustomLine(ref sql) => {
+                    return Err(Error::Irreversible(format!("Custom SQL `{}`", sql)))
+                }
+                Insert(ref table, _) => {
+                    return Err(Error::Irreversible(format!(
+                        "Inserting into table `{}`",
+                        table
+                    )))
+                }
+                DropType(ref name) => {
+                    return Err(Error::Irreversible(format!("Dropping type `{}`", name)))
+                }
+            });
+        }
+        m.changes.extend(types.into_iter().map(DropType));
+
+        Ok(m)
+    }
+
+    /// A stable hash of the changes in this migration
+    ///
+    /// The checksum covers the changes (with all callbacks applied) and
+    /// the `down` step, not the SQL of a backend. Runners can store it
+    /// to detect migrations which were edited after they were applied.
+    ///
+    /// ```
+    /// # use barrel::*;
+    /// let mut a = Migration::new();
+    /// a.create_table("users", |t| {
+    ///     t.add_column("name", Type::Text);
+    /// });
+    ///
+    /// let mut b = Migration::new();
+    /// b.create_table("users", |t| {
+    ///     t.add_column("name", Type::Varchar(255));
+    /// });
+    /// assert_ne!(a.checksum(), b.checksum());
+    /// ```
+    pub fn checksum(&self) -> u64 {
+        checksum::checksum(self)
     }
 
     /// Pass a reference to a migration toolkit runner which will
     /// automatically generate and execute
-    pub fn execute<T: DatabaseExecutor, S: SqlGenerator>(&self, runner: &mut T) {
-        runner.execute(self.make::<S>());
+    pub fn execute<T: DatabaseExecutor, S: SqlGenerator + Default>(
+        &self,
+        runner: &mut T,
+    ) -> Result<(), Error> {
+        self.execute_with(&S::default(), runner)
+    }
+
+    /// Generate the SQL with a configured generator and execute it
+    ///
+    /// See `execute` for details
+    pub fn execute_with<T: DatabaseExecutor, S: SqlGenerator>(
+        &self,
+        gen: &S,
+        runner: &mut T,
+    ) -> Result<(), Error> {
+        runner.batch_execute(&self.try_make_with(gen)?)
+    }
+
+    /// Generate and execute this migration on an async database handle
+    ///
+    /// The SQL is generated with the handle's own generator before
+    /// the returned future is polled, so the future doesn't borrow
+    /// the migration.
+    pub fn execute_async<'a, T: AsyncDatabaseExecutor>(&self, runner: &'a T) -> ExecuteFuture<'a> {
+        self.execute_async_with(&T::Generator::default(), runner)
+    }
+
+    /// Generate the SQL with a configured generator and execute it
+    /// on an async database handle
+    ///
+    /// See `execute_async` for details
+    pub fn execute_async_with<'a, T: AsyncDatabaseExecutor, S: SqlGenerator>(
+        &self,
+        gen: &S,
+        runner: &'a T,
+    ) -> ExecuteFuture<'a> {
+        let sql = self.try_make_with(gen);
+        Box::pin(async move { runner.batch_execute(&sql?).await })
+    }
+
+    /// Execute this migration, unless its version was applied already
+    ///
+    /// The version and `checksum` are recorded in the tracking table
+    /// (see the `tracking` module) in the same batch as the migration.
+    /// Returns whether the migration was applied, or `Error::Modified`
+    /// if it was applied with a different checksum.
+    pub fn execute_versioned<T: DatabaseExecutor, S: SqlGenerator + Default>(
+        &self,
+        version: &str,
+        runner: &mut T,
+    ) -> Result<bool, Error> {
+        self.execute_versioned_with(&S::default(), version, runner)
+    }
+
+    /// Execute this migration with a configured generator,
+    /// unless its version was applied already
+    ///
+    /// See `execute_versioned` for details
+    pub fn execute_versioned_with<T: DatabaseExecutor, S: SqlGenerator + Default>(
+        &self,
+        gen: &S,
+        version: &str,
+        runner: &mut T,
+    ) -> Result<bool, Error> {
+        let checksum = self.checksum();
+        if tracking::applied::<_, S>(runner)?
+            .iter()
+            .any(|v| v == version)
+        {
+            let stored = runner.query_column(&tracking::select_checksum(version))?;
+            return tracking::verify(&stored, version, checksum).map(|_| false);
+        }
+
+        let sql = self.try_make_with(gen)?;
+        runner.batch_execute(&tracking::with_version(&sql, version, Some(checksum)))?;
+        Ok(true)
+    }
+
+    /// Execute this migration on an async database handle,
+    /// unless its version was applied already
+    ///
+    /// See `execute_versioned` for details
+    pub fn execute_versioned_async<'a, T: AsyncDatabaseExecutor>(
+        &self,
+        version: &str,
+        runner: &'a T,
+    ) -> ExecuteFuture<'a, bool> {
+        let checksum = self.checksum();
+        let sql = self
+            .try_make::<T::Generator>()
+            .map(|sql| tracking::with_version(&sql, version, Some(checksum)));
+        let version = version.to_string();
+
+        Box::pin(async move {
+            if tracking::applied_async(runner).await?.contains(&version) {
+                let stored = runner
+                    .query_column(&tracking::select_checksum(&version))
+                    .await?;
+                return tracking::verify(&stored, &version, checksum).map(|_| false);
+            }
+
+            runner.batch_execute(&sql?).await?;
+            Ok(true)
+        })
     }
 
     /// Create a new table with a specific name
-    pub fn create_table<S: Into<String>, F: 'static>(&mut self, name: S, cb: F) -> &mut TableMeta
-    where
-        F: Fn(&mut Table),
-    {
+    pub fn create_table<S: Into<String>, F: 'static + Fn(&mut Table)>(
+        &mut self,
+        name: S,
+        cb: F,
+    ) -> &mut TableMeta {
         self.changes
             .push(DatabaseChange::CreateTable(Table::new(name), Rc::new(cb)));
 
-        return match self.changes.last_mut().unwrap() {
+        match self.changes.last_mut().unwrap() {
             &mut DatabaseChange::CreateTable(ref mut t, _) => &mut t.meta,
             _ => unreachable!(),
-        };
+        }
     }
 
     /// Create a new table *only* if it doesn't exist yet
-    pub fn create_table_if_not_exists<S: Into<String>, F: 'static>(
+    pub fn create_table_if_not_exists<S: Into<String>, F: 'static + Fn(&mut Table)>(
         &mut self,
         name: S,
         cb: F,
-    ) -> &mut TableMeta
-    where
-        F: Fn(&mut Table),
-    {
+    ) -> &mut TableMeta {
         self.changes.push(DatabaseChange::CreateTableIfNotExists(
             Table::new(name),
             Rc::new(cb),
         ));
 
-        return match self.changes.last_mut().unwrap() {
-            &mut DatabaseChange::CreateTable(ref mut t, _) => &mut t.meta,
+        match self.changes.last_mut().unwrap() {
+            &mut DatabaseChange::CreateTableIfNotExists(ref mut t, _) => &mut t.meta,
             _ => unreachable!(),
-        };
+        }
     }
 
     /// Change fields on an existing table
-    pub fn change_table<S: Into<String>, F: 'static>(&mut self, name: S, cb: F)
-    where
-        F: Fn(&mut Table),
-    {
+    pub fn change_table<S: Into<String>, F: 'static + Fn(&mut Table)>(&mut self, name: S, cb: F) {
         let t = Table::new(name);
         let c = DatabaseChange::ChangeTable(t, Rc::new(cb));
         self.changes.push(c);
@@ -147,6 +794,14 @@ impl Migration {
             .push(DatabaseChange::RenameTable(old.into(), new.into()));
     }
 
+    /// Rename an index, e.g. after renaming its table
+    ///
+    /// Not supported by MySQL, Sqlite and MSSQL.
+    pub fn rename_index<S: Into<String>>(&mut self, old: S, new: S) {
+        self.changes
+            .push(DatabaseChange::RenameIndex(old.into(), new.into()));
+    }
+
     /// Drop an existing table
     pub fn drop_table<S: Into<String>>(&mut self, name: S) {
         self.changes.push(DatabaseChange::DropTable(name.into()));
@@ -157,4 +812,94 @@ impl Migration {
         self.changes
             .push(DatabaseChange::DropTableIfExists(name.into()));
     }
+
+    /// Create a new table with the same columns as an existing one,
+    /// e.g. as a shadow table for auditing
+    ///
+    /// Postgres also copies defaults, constraints and indexes.
+    /// Not supported by Sqlite and MSSQL.
+    pub fn create_table_like<S: Into<String>>(&mut self, name: S, source: S) {
+        self.changes
+            .push(DatabaseChange::CreateTableLike(name.into(), source.into()));
+    }
+
+    /// Create a new table filled with the results of a query,
+    /// e.g. a summary table
+    ///
+    /// The query is rendered as is. Not supported by MSSQL.
+    pub fn create_table_from_query<S: Into<String>>(&mut self, name: S, query: S) {
+        self.changes
+            .push(DatabaseChange::CreateTableAs(name.into(), query.into()));
+    }
+
+    /// Create a row-level security policy for a table (Postgres only)
+    ///
+    /// The policy only takes effect once row-level security is enabled
+    /// for the table with `Table::enable_row_level_security`.
+    ///
+    /// ```
+    /// # use barrel::*;
+    /// let mut m = Migration::new();
+    /// m.create_policy(
+    ///     "tenant_isolation",
+    ///     "users",
+    ///     Policy::new().using("tenant_id = current_setting('app.tenant_id')::int"),
+    /// );
+    /// ```
+    pub fn create_policy<S: Into<String>>(&mut self, name: S, table: S, policy: Policy) {
+        self.changes.push(DatabaseChange::CreatePolicy(
+            name.into(),
+            table.into(),
+            policy,
+        ));
+    }
+
+    /// Create a partition of a table that was partitioned with `Table::partition_by`
+    ///
+    /// The bounds take the values of the partition, e.g. `FOR VALUES
+    /// FROM ('2024-01-01') TO ('2025-01-01')`. Only supported by Postgres.
+    pub fn create_partition<S: Into<String>>(&mut self, name: S, parent: S, bounds: S) {
+        self.changes.push(DatabaseChange::CreatePartition(
+            name.into(),
+            parent.into(),
+            bounds.into(),
+        ));
+    }
+
+    /// Inject a raw SQL statement into the migration
+    ///
+    /// It is emitted as is, between the statements of the changes
+    /// registered before and after it. Use this for features barrel
+    /// doesn't support yet. The SQL is not checked and can't be reverted.
+    ///
+    /// ```
+    /// # use barrel::*;
+    /// let mut m = Migration::new();
+    /// m.create_table("users", |t| {
+    ///     t.add_column("name", Type::Text);
+    /// });
+    /// m.inject_custom("CREATE INDEX users_name ON users (name)");
+    /// ```
+    pub fn inject_custom<S: Into<String>>(&mut self, sql: S) {
+        self.changes.push(DatabaseChange::CustomLine(sql.into()));
+    }
+
+    /// Insert a row into a table, e.g. for reference data
+    ///
+    /// Values are escaped by the generator. A row without any
+    /// values inserts the column defaults.
+    ///
+    /// ```
+    /// # use barrel::*;
+    /// let mut m = Migration::new();
+    /// m.insert_into("roles", &[("name", "admin".into()), ("level", 10.into())]);
+    /// ```
+    pub fn insert_into<S: Into<String>>(&mut self, table: S, row: &[(&str, Value)]) {
+        self.changes.push(DatabaseChange::Insert(
+            table.into(),
+            row.iter()
+                .map(|(c, v)| (c.to_string(), v.clone()))
+                .collect(),
+        ));
+    }
 }
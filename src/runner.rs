@@ -0,0 +1,130 @@
+//! Apply and roll back an ordered list of migrations
+//!
+//! A `Runner` holds migrations along with their versions, in the order
+//! they need to be applied in. Applied versions are recorded via the
+//! `tracking` module, so running it again only applies new migrations:
+//!
+//! ```norun
+//! let mut runner = Runner::new();
+//! runner.add("0001_users", users).add("0002_posts", posts);
+//!
+//! runner.run::<_, Pg>(&mut client)?;
+//!
+//! // Revert `0002_posts`, keeping `0001_users`
+//! runner.rollback_to::<_, Pg>("0001_users", &mut client)?;
+//! ```
+
+use crate::backend::SqlGenerator;
+use crate::connectors::DatabaseExecutor;
+use crate::embedded::EmbeddedMigration;
+use crate::tracking;
+use crate::{Error, Migration};
+
+/// An ordered list of versioned migrations
+#[derive(Default)]
+pub struct Runner {
+    migrations: Vec<(String, Migration)>,
+}
+
+impl Runner {
+    pub fn new() -> Runner {
+        Runner::default()
+    }
+
+    /// Add a migration, to be applied after all previous ones
+    pub fn add<S: Into<String>>(&mut self, version: S, migration: Migration) -> &mut Runner {
+        self.migrations.push((version.into(), migration));
+        self
+    }
+
+    /// The versions of all migrations, in order
+    pub fn versions(&self) -> Vec<&str> {
+        self.migrations.iter().map(|(v, _)| v.as_str()).collect()
+    }
+
+    /// Apply all migrations which weren't applied yet, in order
+    ///
+    /// Returns the versions of the migrations that were applied.
+    pub fn run<T: DatabaseExecutor, S: SqlGenerator + Default>(
+        &self,
+        executor: &mut T,
+    ) -> Result<Vec<String>, Error> {
+        self.run_with(&S::default(), executor)
+    }
+
+    /// Apply all pending migrations with a configured generator
+    ///
+    /// See `run` for details
+    pub fn run_with<T: DatabaseExecutor, S: SqlGenerator + Default>(
+        &self,
+        gen: &S,
+        executor: &mut T,
+    ) -> Result<Vec<String>, Error> {
+        let mut applied = Vec::new();
+        for (version, m) in &self.migrations {
+            if m.execute_versioned_with(gen, version, executor)? {
+                applied.push(version.clone());
+            }
+        }
+
+        Ok(applied)
+    }
+
+    /// Revert all applied migrations which come after `version`
+    ///
+    /// Their `down` steps are executed in reverse order, along with
+    /// removing their versions from the tracking table. All of it is
+    /// sent as one batch, which executors run in a single transaction.
+    /// `version` itself stays applied.
+    ///
+    /// Returns the versions of the migrations that were reverted.
+    pub fn rollback_to<T: DatabaseExecutor, S: SqlGenerator + Default>(
+        &self,
+        version: &str,
+        executor: &mut T,
+    ) -> Result<Vec<String>, Error> {
+        self.rollback_to_with(&S::default(), version, executor)
+    }
+
+    /// Revert migrations after `version` with a configured generator
+    ///
+    /// See `rollback_to` for details
+    pub fn rollback_to_with<T: DatabaseExecutor, S: SqlGenerator + Default>(
+        &self,
+        gen: &S,
+        version: &str,
+        executor: &mut T,
+    ) -> Result<Vec<String>, Error> {
+        let pos = self
+            .migrations
+            .iter()
+            .position(|(v, _)| v == version)
+            .ok_or_else(|| Error::UnknownVersion(version.into()))?;
+        let applied = tracking::applied::<_, S>(executor)?;
+
+        let mut stmts = Vec::new();
+        let mut reverted = Vec::new();
+        for (version, m) in self.migrations[pos + 1..].iter().rev() {
+            if applied.contains(version) {
+                stmts.extend(m.down_statements(gen)?.into_iter().map(|s| s.sql));
+                stmts.push(tracking::delete_version(version));
+                reverted.push(version.clone());
+            }
+        }
+
+        if !reverted.is_empty() {
+            executor.batch_execute(&stmts.join("; "))?;
+        }
+        Ok(reverted)
+    }
+}
+
+impl<'a> From<&'a [EmbeddedMigration]> for Runner {
+    fn from(migrations: &'a [EmbeddedMigration]) -> Runner {
+        let mut runner = Runner::new();
+        for m in migrations {
+            runner.add(m.name, m.migration());
+        }
+        runner
+    }
+}
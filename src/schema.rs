@@ -1,29 +1,203 @@
 //! Simple schema representation for migration state
+//!
+//! A `Schema` describes the tables of a database and their columns.
+//! It can be built by replaying migrations, and is what other tools
+//! (like the `diff` module) work with.
 
-trait Schemas {
-    /// Returns the name of a database
-    fn name(&self) -> String;
+use crate::table::ColumnDefault;
+use crate::{Column, DatabaseChange, Migration, TableChange};
 
-    /// Returns a list of all tables in a database
-    fn tables(&self) -> Vec<String>;
+use std::rc::Rc;
 
-    /// Returns a list of all column names and types
-    fn columns(&self, table: &str) -> Vec<(String, Column)>;
+/// Describe the state of a database to apply a migration to
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Schema {
+    /// All tables, in the order they were created in
+    pub tables: Vec<TableSchema>,
 }
 
-trait Column {
-    /// Get the type of column in SQL specific terms
-    fn type(&self) -> String;
+/// A table and its columns
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableSchema {
+    pub name: String,
+    pub columns: Vec<(String, Column)>,
 }
 
-// Describe the current state of a database to apply a migration to
-struct Schema {
-    db_name: String,
-    columns: Vec<String>,
-}
+impl Schema {
+    /// Create an empty schema
+    pub fn new() -> Schema {
+        Schema::default()
+    }
 
+    /// Replay a list of migrations, in order, on an empty schema
+    pub fn from_migrations(migrations: &[Migration]) -> Schema {
+        let mut schema = Schema::new();
+        migrations.iter().for_each(|m| schema.apply(m));
+        schema
+    }
 
-impl Schema {
+    /// Get a table by name
+    pub fn table(&self, name: &str) -> Option<&TableSchema> {
+        self.tables.iter().find(|t| t.name == name)
+    }
+
+    fn table_mut(&mut self, name: &str) -> Option<&mut TableSchema> {
+        self.tables.iter_mut().find(|t| t.name == name)
+    }
+
+    /// Apply the changes of a migration to this schema
+    ///
+    /// Changes on tables that don't exist are ignored, and so are
+    /// custom SQL and inserted rows.
+    pub fn apply(&mut self, migr: &Migration) {
+        migr.changes.iter().for_each(|c| self.apply_change(c));
+    }
+
+    /// Fill in the current definitions of the tables a migration changes,
+    /// and of columns whose nullability it changes, as they are in this
+    /// schema
+    ///
+    /// MySQL and MSSQL can only change the nullability of a column by
+    /// declaring it again, and Sqlite changes columns by creating their
+    /// table again. A migration on its own only knows the definitions
+    /// of tables it created itself. The `down` step is completed with
+    /// the schema after the `up` step.
+    pub fn complete(&self, migr: &Migration) -> Migration {
+        let mut schema = self.clone();
+        let mut m = Migration::new();
+        m.schema = migr.schema.clone();
+        m.idempotent = migr.idempotent;
+        m.changes = schema.complete_changes(&migr.changes);
+        m.down = migr.down.as_ref().map(|d| Box::new(schema.complete(d)));
+        m
+    }
+
+    /// Fill in the definitions of changed tables and of columns whose
+    /// nullability changes, while applying the changes to this schema
+    ///
+    /// Definitions that are already known are kept.
+    pub(crate) fn complete_changes(&mut self, changes: &[DatabaseChange]) -> Vec<DatabaseChange> {
+        let mut completed = Vec::new();
 
+        for change in changes {
+            let change = match *change {
+                DatabaseChange::ChangeTable(ref t, ref cb) => {
+                    let mut t = t.clone();
+                    cb(&mut t);
+                    if let Some(table) = self.table(&t.meta.name) {
+                        let mut table = table.clone();
+                        t.known = Some(table.clone());
+                        for c in &mut t.changes {
+                            if let TableChange::SetNotNull(ref name, _, ref mut col @ None) = *c {
+                                *col = table.column(name).cloned();
+                            }
+                            table.apply(c);
+                        }
+                    }
+                    DatabaseChange::ChangeTable(t, Rc::new(|_| {}))
+                }
+                ref other => other.clone(),
+            };
+            self.apply_change(&change);
+            completed.push(change);
+        }
+
+        completed
+    }
+
+    fn apply_change(&mut self, change: &DatabaseChange) {
+        use crate::DatabaseChange::*;
+
+        match *change {
+            CreateTableIfNotExists(ref t, _) if self.table(&t.meta.name).is_some() => {}
+            CreateTable(ref t, ref cb) | CreateTableIfNotExists(ref t, ref cb) => {
+                let mut t = t.clone();
+                t.prepare(cb.as_ref());
+                let mut table = TableSchema::new(t.meta.name());
+                t.changes.iter().for_each(|c| table.apply(c));
+                self.tables.push(table);
+            }
+            ChangeTable(ref t, ref cb) => {
+                let mut t = t.clone();
+                cb(&mut t);
+                if let Some(table) = self.table_mut(&t.meta.name) {
+                    t.changes.iter().for_each(|c| table.apply(c));
+                }
+            }
+            RenameTable(ref old, ref new) => {
+                if let Some(table) = self.table_mut(old) {
+                    table.name = new.clone();
+                }
+            }
+            DropTable(ref name) | DropTableIfExists(ref name) => {
+                self.tables.retain(|t| &t.name != name)
+            }
+            CreatePartition(ref name, ref parent, _) | CreateTableLike(ref name, ref parent) => {
+                if let Some(mut table) = self.table(parent).cloned() {
+                    table.name = name.clone();
+                    self.tables.push(table);
+                }
+            }
+            // The columns are only known once the query ran
+            CreateTableAs(ref name, _) => self.tables.push(TableSchema::new(name.clone())),
+            CreatePolicy(_, _, _)
+            | RenameIndex(_, _)
+            | CustomLine(_)
+            | Insert(_, _)
+            | DropType(_) => {}
+        }
+    }
 }
 
+impl TableSchema {
+    /// Create a table without any columns
+    pub fn new<S: Into<String>>(name: S) -> TableSchema {
+        TableSchema {
+            name: name.into(),
+            columns: Vec::new(),
+        }
+    }
+
+    /// Get a column by name
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.columns.iter().find(|c| c.0 == name).map(|c| &c.1)
+    }
+
+    /// Apply a single change to the columns of this table
+    pub fn apply(&mut self, change: &TableChange) {
+        use crate::TableChange::*;
+        match *change {
+            AddColumn(ref name, ref col) => self.columns.push((name.clone(), col.clone())),
+            ChangeColumn(ref name, ref col, ref cb) => {
+                if let Some(c) = self.columns.iter_mut().find(|c| &c.0 == name) {
+                    c.1._type = col._type.clone();
+                    cb(&mut c.1);
+                }
+            }
+            RenameColumn(ref old, ref new) => {
+                if let Some(c) = self.columns.iter_mut().find(|c| &c.0 == old) {
+                    c.0 = new.clone();
+                }
+            }
+            SetDefault(ref name, ref expr) => {
+                if let Some(c) = self.columns.iter_mut().find(|c| &c.0 == name) {
+                    c.1.def = expr.clone().map(ColumnDefault::Expression);
+                }
+            }
+            /* The flag is rendered as `NOT NULL`, see `add_column` */
+            SetNotNull(ref name, not_null, _) => {
+                if let Some(c) = self.columns.iter_mut().find(|c| &c.0 == name) {
+                    c.1.nullable = not_null;
+                }
+            }
+            DropColumn(ref name) => self.columns.retain(|c| &c.0 != name),
+            CustomLine(_)
+            | AddConstraint(_, _)
+            | RenameConstraint(_, _)
+            | DropConstraint(_)
+            | AddIndex(_, _) => {}
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Serialization of the migration model
+//!
+//! Changes on tables and columns carry the callbacks they were
+//! registered with, which can't be serialized. Instead, the callbacks
+//! are run and the resulting tables and columns are stored. When loaded
+//! back, the changes render the same SQL as the originals.
+
+use crate::{
+    Column, Constraint, DatabaseChange, ForeignKey, Index, Policy, Table, TableChange, Value,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use std::rc::Rc;
+
+/// Foreign keys used to only name a table
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ForeignRepr {
+    Table(String),
+    Key(ForeignKey),
+}
+
+/// Load a foreign key in either format
+pub(crate) fn foreign_key<'de, D: Deserializer<'de>>(d: D) -> Result<ForeignKey, D::Error> {
+    Ok(match ForeignRepr::deserialize(d)? {
+        ForeignRepr::Table(t) => t.into(),
+        ForeignRepr::Key(fk) => fk,
+    })
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "TableChange")]
+#[allow(clippy::enum_variant_names)]
+pub(crate) enum TableChangeRepr {
+    AddColumn(String, Column),
+    ChangeColumn(String, Column),
+    RenameColumn(String, String),
+    DropColumn(String),
+    CustomLine(String),
+    AddConstraint(Option<String>, Constraint),
+    AddIndex(String, Index),
+    SetDefault(String, Option<String>),
+    SetNotNull(String, bool, Option<Column>),
+    RenameConstraint(String, String),
+    DropConstraint(String),
+}
+
+impl From<TableChange> for TableChangeRepr {
+    fn from(change: TableChange) -> Self {
+        match change {
+            TableChange::AddColumn(name, col) => TableChangeRepr::AddColumn(name, col),
+            TableChange::ChangeColumn(name, mut col, cb) => {
+                cb(&mut col);
+                TableChangeRepr::ChangeColumn(name, col)
+            }
+            TableChange::RenameColumn(old, new) => TableChangeRepr::RenameColumn(old, new),
+            TableChange::DropColumn(name) => TableChangeRepr::DropColumn(name),
+            TableChange::CustomLine(sql) => TableChangeRepr::CustomLine(sql),
+            TableChange::AddConstraint(name, c) => TableChangeRepr::AddConstraint(name, c),
+            TableChange::AddIndex(name, i) => TableChangeRepr::AddIndex(name, i),
+            TableChange::SetDefault(name, expr) => TableChangeRepr::SetDefault(name, expr),
+            TableChange::SetNotNull(name, not_null, t) => {
+                TableChangeRepr::SetNotNull(name, not_null, t)
+            }
+            TableChange::RenameConstraint(old, new) => TableChangeRepr::RenameConstraint(old, new),
+            TableChange::DropConstraint(name) => TableChangeRepr::DropConstraint(name),
+        }
+    }
+}
+
+impl From<TableChangeRepr> for TableChange {
+    fn from(repr: TableChangeRepr) -> Self {
+        match repr {
+            TableChangeRepr::AddColumn(name, col) => TableChange::AddColumn(name, col),
+            TableChangeRepr::ChangeColumn(name, col) => {
+                let new = col.clone();
+                TableChange::ChangeColumn(name, col, Rc::new(move |c| *c = new.clone()))
+            }
+            TableChangeRepr::RenameColumn(old, new) => TableChange::RenameColumn(old, new),
+            TableChangeRepr::DropColumn(name) => TableChange::DropColumn(name),
+            TableChangeRepr::CustomLine(sql) => TableChange::CustomLine(sql),
+            TableChangeRepr::AddConstraint(name, c) => TableChange::AddConstraint(name, c),
+            TableChangeRepr::AddIndex(name, i) => TableChange::AddIndex(name, i),
+            TableChangeRepr::SetDefault(name, expr) => TableChange::SetDefault(name, expr),
+            TableChangeRepr::SetNotNull(name, not_null, t) => {
+                TableChange::SetNotNull(name, not_null, t)
+            }
+            TableChangeRepr::RenameConstraint(old, new) => TableChange::RenameConstraint(old, new),
+            TableChangeRepr::DropConstraint(name) => TableChange::DropConstraint(name),
+        }
+    }
+}
+
+/// Run the callback of a new table, including its `id` column
+///
+/// The column is then part of the table's changes, so it must not
+/// be added again when the table is loaded.
+fn prepared(t: &mut Table, cb: &dyn Fn(&mut Table)) {
+    t.prepare(cb);
+    t.meta.has_id = false;
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "DatabaseChange")]
+pub(crate) enum DatabaseChangeRepr {
+    CreateTable(Table),
+    CreateTableIfNotExists(Table),
+    ChangeTable(Table),
+    RenameTable(String, String),
+    DropTable(String),
+    DropTableIfExists(String),
+    CreatePartition(String, String, String),
+    CreateTableLike(String, String),
+    CreateTableAs(String, String),
+    CreatePolicy(String, String, Policy),
+    RenameIndex(String, String),
+    CustomLine(String),
+    Insert(String, Vec<(String, Value)>),
+    DropType(String),
+}
+
+impl From<DatabaseChange> for DatabaseChangeRepr {
+    fn from(change: DatabaseChange) -> Self {
+        use crate::DatabaseChange::*;
+        match change {
+            CreateTable(mut t, cb) => {
+                prepared(&mut t, cb.as_ref());
+                DatabaseChangeRepr::CreateTable(t)
+            }
+            CreateTableIfNotExists(mut t, cb) => {
+                prepared(&mut t, cb.as_ref());
+                DatabaseChangeRepr::CreateTableIfNotExists(t)
+            }
+            ChangeTable(mut t, cb) => {
+                cb(&mut t);
+                DatabaseChangeRepr::ChangeTable(t)
+            }
+            RenameTable(old, new) => DatabaseChangeRepr::RenameTable(old, new),
+            DropTable(name) => DatabaseChangeRepr::DropTable(name),
+            DropTableIfExists(name) => DatabaseChangeRepr::DropTableIfExists(name),
+            CreatePartition(name, parent, bounds) => {
+                DatabaseChangeRepr::CreatePartition(name, parent, bounds)
+            }
+            CreateTableLike(name, source) => DatabaseChangeRepr::CreateTableLike(name, source),
+            CreateTableAs(name, query) => DatabaseChangeRepr::CreateTableAs(name, query),
+            CreatePolicy(name, table, policy) => {
+                DatabaseChangeRepr::CreatePolicy(name, table, policy)
+            }
+            RenameIndex(old, new) => DatabaseChangeRepr::RenameIndex(old, new),
+            CustomLine(sql) => DatabaseChangeRepr::CustomLine(sql),
+            Insert(table, row) => DatabaseChangeRepr::Insert(table, row),
+            DropType(name) => DatabaseChangeRepr::DropType(name),
+        }
+    }
+}
+
+impl From<DatabaseChangeRepr> for DatabaseChange {
+    fn from(repr: DatabaseChangeRepr) -> Self {
+        use crate::DatabaseChange::*;
+        match repr {
+            DatabaseChangeRepr::CreateTable(t) => CreateTable(t, Rc::new(|_| {})),
+            DatabaseChangeRepr::CreateTableIfNotExists(t) => {
+                CreateTableIfNotExists(t, Rc::new(|_| {}))
+            }
+            DatabaseChangeRepr::ChangeTable(t) => ChangeTable(t, Rc::new(|_| {})),
+            DatabaseChangeRepr::RenameTable(old, new) => RenameTable(old, new),
+            DatabaseChangeRepr::DropTable(name) => DropTable(name),
+            DatabaseChangeRepr::DropTableIfExists(name) => DropTableIfExists(name),
+            DatabaseChangeRepr::CreatePartition(name, parent, bounds) => {
+                CreatePartition(name, parent, bounds)
+            }
+            DatabaseChangeRepr::CreateTableLike(name, source) => CreateTableLike(name, source),
+            DatabaseChangeRepr::CreateTableAs(name, query) => CreateTableAs(name, query),
+            DatabaseChangeRepr::CreatePolicy(name, table, policy) => {
+                CreatePolicy(name, table, policy)
+            }
+            DatabaseChangeRepr::RenameIndex(old, new) => RenameIndex(old, new),
+            DatabaseChangeRepr::CustomLine(sql) => CustomLine(sql),
+            DatabaseChangeRepr::Insert(table, row) => Insert(table, row),
+            DatabaseChangeRepr::DropType(name) => DropType(name),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Individual statements of a rendered migration
+//!
+//! `Migration::statements` returns the SQL of a migration as a list of
+//! `Statement`s instead of a single string. Each one knows the table it
+//! targets and the kind of operation it performs, so tools can log,
+//! filter or review a migration before it is executed.
+//!
+//! ```norun
+//! for stmt in m.statements(SqlVariant::Pg) {
+//!     if stmt.operation == Operation::DropTable {
+//!         println!("Dropping `{}`: {}", stmt.table, stmt);
+//!     }
+//! }
+//! ```
+
+use crate::TableChange;
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// The kind of change a statement makes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    CreateTable,
+
+    /// A type which columns of a table depend on
+    CreateType,
+
+    /// Drop a type, after the tables which used it
+    DropType,
+
+    /// An extension which columns of a table depend on
+    ///
+    /// Creating extensions usually needs special privileges, so these
+    /// statements can be picked out to run them separately.
+    CreateExtension,
+
+    RenameTable,
+    DropTable,
+    AddColumn,
+    ChangeColumn,
+    RenameColumn,
+    DropColumn,
+    AddConstraint,
+    RenameConstraint,
+    DropConstraint,
+
+    /// Create an index, after the table it belongs to
+    CreateIndex,
+
+    RenameIndex,
+
+    /// Set the next value of an auto-incrementing column, after its table
+    SetSequence,
+
+    /// Set the comment of a table
+    Comment,
+
+    /// Create a row-level security policy, after its table
+    CreatePolicy,
+
+    /// Change the options of an existing table, such as its engine
+    SetOptions,
+
+    /// Raw SQL, injected via `inject_custom`
+    Custom,
+
+    Insert,
+}
+
+/// A single SQL statement of a migration
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Statement {
+    /// The SQL, without a trailing `;`
+    pub sql: String,
+
+    /// The table the statement changes (for renames, its old name)
+    ///
+    /// Empty for raw SQL injected into a migration
+    pub table: String,
+
+    /// What the statement does
+    pub operation: Operation,
+}
+
+impl Statement {
+    pub(crate) fn new<S: Into<String>>(sql: String, table: S, operation: Operation) -> Statement {
+        Statement {
+            sql,
+            table: table.into(),
+            operation,
+        }
+    }
+}
+
+impl Display for Statement {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(&self.sql)
+    }
+}
+
+impl TableChange {
+    /// The operation of the statement this change is rendered into
+    pub(crate) fn operation(&self) -> Operation {
+        match *self {
+            TableChange::AddColumn(..) => Operation::AddColumn,
+            TableChange::ChangeColumn(..)
+            | TableChange::SetDefault(..)
+            | TableChange::SetNotNull(..) => Operation::ChangeColumn,
+            TableChange::RenameColumn(..) => Operation::RenameColumn,
+            TableChange::DropColumn(..) => Operation::DropColumn,
+            TableChange::CustomLine(..) => Operation::Custom,
+            TableChange::AddConstraint(..) => Operation::AddConstraint,
+            TableChange::AddIndex(..) => Operation::CreateIndex,
+            TableChange::RenameConstraint(..) => Operation::RenameConstraint,
+            TableChange::DropConstraint(..) => Operation::DropConstraint,
+        }
+    }
+}
+
+/// Join statements into a single string, as `Migration::make` returns it
+pub(crate) fn join(stmts: &[Statement]) -> String {
+    stmts
+        .iter()
+        .map(|s| s.sql.as_str())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
@@ -8,8 +8,10 @@
 //! then access individual columns in that table.
 
 use super::backend::SqlGenerator;
-use super::{TableChange, Type};
+use super::schema::TableSchema;
+use super::{Error, TableChange, Type};
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::rc::Rc;
 
 impl Debug for TableChange {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
@@ -18,17 +20,24 @@ impl Debug for TableChange {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table {
     pub meta: TableMeta,
-    changes: Vec<TableChange>,
+    pub(crate) changes: Vec<TableChange>,
+
+    /// The definition of the table before the changes, if it's known
+    /// (see `Schema::complete`)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) known: Option<TableSchema>,
 }
 
 impl Table {
     pub fn new<S: Into<String>>(name: S) -> Table {
-        return Table {
+        Table {
             meta: TableMeta::new(name.into()),
             changes: Vec::new(),
-        };
+            known: None,
+        }
     }
 
     pub fn add_primary<S: Into<String>>(&mut self, name: S) -> &mut Column {
@@ -37,36 +46,89 @@ impl Table {
             Column {
                 indexed: true,
                 unique: true,
-                nullable: false,
                 increments: true,
-                _type: Type::Integer,
-                def: None,
+                ..Column::new(Type::Integer)
             },
         ));
 
-        return match self.changes.last_mut().unwrap() {
+        match self.changes.last_mut().unwrap() {
             &mut TableChange::AddColumn(_, ref mut c) => c,
             _ => unreachable!(),
-        };
+        }
     }
 
-    pub fn add_column<S: Into<String>>(&mut self, name: S, _type: Type) -> &mut Column {
-        self.changes.push(TableChange::AddColumn(
+    /// Add a column of a type, or a column built with the `types` module
+    ///
+    /// Enums built there are named after their table and column,
+    /// e.g. `users_status`.
+    pub fn add_column<S: Into<String>, C: Into<Column>>(
+        &mut self,
+        name: S,
+        column: C,
+    ) -> &mut Column {
+        let name = name.into();
+        let mut column = column.into();
+        column
+            ._type
+            .name_enum(&format!("{}_{}", self.meta.name, name));
+        self.changes.push(TableChange::AddColumn(name, column));
+
+        match self.changes.last_mut().unwrap() {
+            &mut TableChange::AddColumn(_, ref mut c) => c,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Change the type of an existing column
+    ///
+    /// MySQL replaces the whole definition of the column, so modifiers
+    /// which should be kept need to be set on the returned column again.
+    /// Keys of the column stay in place, only its base type is declared.
+    ///
+    /// This takes a `Type`: types built with the unstable `types` module
+    /// can be converted with `into`, which only keeps their base type.
+    pub fn change_column<S: Into<String>>(&mut self, name: S, _type: Type) -> &mut Column {
+        self.changes.push(TableChange::ChangeColumn(
             name.into(),
-            Column {
-                indexed: false,
-                unique: false,
-                nullable: false,
-                increments: false,
-                _type: _type,
-                def: None,
-            },
+            Column::new(_type),
+            Rc::new(|_| {}),
         ));
 
-        return match self.changes.last_mut().unwrap() {
-            &mut TableChange::AddColumn(_, ref mut c) => c,
+        match self.changes.last_mut().unwrap() {
+            &mut TableChange::ChangeColumn(_, ref mut c, _) => c,
             _ => unreachable!(),
-        };
+        }
+    }
+
+    /// Set the default of an existing column to an SQL expression,
+    /// e.g. `'active'` or `now()`
+    pub fn set_default<S: Into<String>, E: Into<String>>(&mut self, name: S, expr: E) {
+        self.changes
+            .push(TableChange::SetDefault(name.into(), Some(expr.into())));
+    }
+
+    /// Drop the default of an existing column
+    pub fn drop_default<S: Into<String>>(&mut self, name: S) {
+        self.changes
+            .push(TableChange::SetDefault(name.into(), None));
+    }
+
+    /// Make an existing column `NOT NULL`
+    ///
+    /// MySQL and MSSQL declare the column again for this. Its definition
+    /// is looked up in the tables created earlier in the same migration,
+    /// or filled in by `Schema::complete` from earlier ones.
+    pub fn set_not_null<S: Into<String>>(&mut self, name: S) {
+        self.changes
+            .push(TableChange::SetNotNull(name.into(), true, None));
+    }
+
+    /// Allow an existing column to be `NULL` again
+    ///
+    /// See `set_not_null` for MySQL and MSSQL.
+    pub fn drop_not_null<S: Into<String>>(&mut self, name: S) {
+        self.changes
+            .push(TableChange::SetNotNull(name.into(), false, None));
     }
 
     pub fn drop_column<S: Into<String>>(&mut self, name: S) {
@@ -78,47 +140,509 @@ impl Table {
             .push(TableChange::RenameColumn(old.into(), new.into()));
     }
 
-    pub fn make<T: SqlGenerator>(&mut self, ex: bool) -> Vec<String> {
-        use TableChange::*;
+    /// Inject a line of raw SQL into the table
+    ///
+    /// When creating a table the line becomes part of its definition,
+    /// e.g. for a constraint barrel can't express. When changing a
+    /// table it follows `ALTER TABLE <name>`. The SQL is not checked.
+    pub fn inject_custom<S: Into<String>>(&mut self, sql: S) {
+        self.changes.push(TableChange::CustomLine(sql.into()));
+    }
+
+    /// Set the primary key of a new table to span several columns
+    ///
+    /// The default `id` column is left out of tables with such a key.
+    pub fn set_primary_key(&mut self, columns: &[&str]) {
+        self.changes.push(TableChange::AddConstraint(
+            None,
+            Constraint::PrimaryKey(columns.iter().map(|c| c.to_string()).collect()),
+        ));
+    }
+
+    /// Add a named constraint that the values of several columns are unique together
+    pub fn add_unique<S: Into<String>>(&mut self, name: S, columns: &[&str]) {
+        self.changes.push(TableChange::AddConstraint(
+            Some(name.into()),
+            Constraint::Unique(columns.iter().map(|c| c.to_string()).collect()),
+        ));
+    }
+
+    /// Add a named constraint, e.g. to an existing table
+    ///
+    /// The other `add_*` methods cover the common constraints, this one
+    /// takes any of them.
+    pub fn add_constraint<S: Into<String>>(&mut self, name: S, constraint: Constraint) {
+        self.changes
+            .push(TableChange::AddConstraint(Some(name.into()), constraint));
+    }
+
+    /// Remove a named constraint from an existing table
+    ///
+    /// Not supported by Sqlite, which can't alter constraints at all.
+    pub fn drop_constraint<S: Into<String>>(&mut self, name: S) {
+        self.changes.push(TableChange::DropConstraint(name.into()));
+    }
+
+    /// Add a foreign key over several columns, e.g. the key of a line
+    /// in an order
+    ///
+    /// A single column can point to another table with `Type::foreign`.
+    pub fn add_foreign_key<S: Into<String>>(
+        &mut self,
+        columns: &[&str],
+        table: S,
+        references: &[&str],
+    ) {
+        self.changes.push(TableChange::AddConstraint(
+            None,
+            Table::foreign_key(columns, table.into(), references),
+        ));
+    }
+
+    /// Add a named foreign key over several columns
+    ///
+    /// Unlike with `add_foreign_key`, the key can be dropped again,
+    /// and reversed in migrations.
+    pub fn add_named_foreign_key<S: Into<String>, T: Into<String>>(
+        &mut self,
+        name: S,
+        columns: &[&str],
+        table: T,
+        references: &[&str],
+    ) {
+        self.changes.push(TableChange::AddConstraint(
+            Some(name.into()),
+            Table::foreign_key(columns, table.into(), references),
+        ));
+    }
+
+    fn foreign_key(columns: &[&str], table: String, references: &[&str]) -> Constraint {
+        Constraint::ForeignKey {
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            table,
+            references: references.iter().map(|c| c.to_string()).collect(),
+        }
+    }
+
+    /// Rename a constraint of an existing table, e.g. after renaming
+    /// the table itself
+    ///
+    /// Only supported by Postgres and CockroachDB.
+    pub fn rename_constraint<S: Into<String>>(&mut self, old: S, new: S) {
+        self.changes
+            .push(TableChange::RenameConstraint(old.into(), new.into()));
+    }
+
+    /// Describe this table with a comment
+    ///
+    /// Postgres and CockroachDB set it with `COMMENT ON TABLE`,
+    /// MySQL with the `COMMENT` table option.
+    pub fn set_comment<S: Into<String>>(&mut self, comment: S) {
+        self.meta.comment = Some(comment.into());
+    }
+
+    /// Inherit the columns of another table in Postgres
+    ///
+    /// Can be called more than once to inherit from several tables.
+    pub fn inherits<S: Into<String>>(&mut self, parent: S) {
+        self.meta.inherits.push(parent.into());
+    }
+
+    /// Only show the rows of this table that its policies allow
+    ///
+    /// Policies are created with `Migration::create_policy`. The owner
+    /// of the table is exempt from them.
+    pub fn enable_row_level_security(&mut self) {
+        self.meta.row_level_security = true;
+    }
+
+    /// Split the rows of this table into partitions by some columns
+    ///
+    /// On Postgres the partitions are tables of their own, see
+    /// `Migration::create_partition`. On MySQL they are defined with
+    /// the table, see `add_partition` and `partition_count`. Primary
+    /// keys of partitioned tables must include these columns, so the
+    /// default `id` column usually has to be left out.
+    pub fn partition_by(&mut self, kind: PartitionKind, columns: &[&str]) {
+        self.meta.partition_by = Some((kind, columns.iter().map(|c| c.to_string()).collect()));
+    }
+
+    /// Define a MySQL partition with the values it holds,
+    /// e.g. `VALUES LESS THAN (2024)` or `VALUES IN (1, 2)`
+    pub fn add_partition<S: Into<String>, V: Into<String>>(&mut self, name: S, values: V) {
+        self.meta.partitions.push((name.into(), values.into()));
+    }
+
+    /// Split a MySQL table into a number of hash partitions
+    pub fn partition_count(&mut self, count: u32) {
+        self.meta.partition_count = Some(count);
+    }
+
+    /// Add a named `CHECK` constraint, which may span several columns
+    pub fn add_check<S: Into<String>, E: Into<String>>(&mut self, name: S, expr: E) {
+        self.changes.push(TableChange::AddConstraint(
+            Some(name.into()),
+            Constraint::Check(expr.into()),
+        ));
+    }
+
+    /// Add a named Postgres exclusion constraint
+    ///
+    /// No two rows may match all pairs of a column and an operator, e.g.
+    /// `t.add_exclusion("no_overlap", "gist", &[("room", "="), ("during", "&&")])`
+    /// keeps bookings of a room from overlapping. Mixing scalar columns
+    /// into a GiST index needs the `btree_gist` extension.
+    pub fn add_exclusion<S: Into<String>, M: Into<String>>(
+        &mut self,
+        name: S,
+        method: M,
+        elements: &[(&str, &str)],
+    ) {
+        self.changes.push(TableChange::AddConstraint(
+            Some(name.into()),
+            Constraint::Exclusion {
+                method: method.into(),
+                elements: elements
+                    .iter()
+                    .map(|(col, op)| (col.to_string(), op.to_string()))
+                    .collect(),
+            },
+        ));
+    }
+
+    /// Add a named index over several columns, in the given order
+    ///
+    /// Indexes are created with their own statements after the table.
+    pub fn add_index<S: Into<String>>(&mut self, name: S, columns: &[&str]) -> &mut Index {
+        self.changes.push(TableChange::AddIndex(
+            name.into(),
+            Index::new(columns.iter().map(|c| c.to_string()).collect()),
+        ));
+
+        match self.changes.last_mut().unwrap() {
+            &mut TableChange::AddIndex(_, ref mut i) => i,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Add a named index over an SQL expression, e.g. `lower(email)`
+    ///
+    /// MySQL needs version 8.0.13 or later for these. Not supported by MSSQL.
+    pub fn add_index_expr<S: Into<String>, E: Into<String>>(
+        &mut self,
+        name: S,
+        expr: E,
+    ) -> &mut Index {
+        let mut index = Index::new(Vec::new());
+        index.expression = Some(expr.into());
+        self.changes.push(TableChange::AddIndex(name.into(), index));
+
+        match self.changes.last_mut().unwrap() {
+            &mut TableChange::AddIndex(_, ref mut i) => i,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Add the default columns of a new table and run the user code on it
+    pub(crate) fn prepare(&mut self, cb: &dyn Fn(&mut Table)) {
+        cb(self);
+
+        if self.meta.has_id && !self.has_primary_key() {
+            let mut id = Column::new(Type::Primary);
+            id.increments = true;
+            self.changes
+                .insert(0, TableChange::AddColumn("id".into(), id));
+        }
+    }
+
+    /// The names of the types that new columns are declared with,
+    /// e.g. enums, which Postgres creates ahead of the table
+    pub(crate) fn column_types(&self) -> Vec<String> {
+        self.changes
+            .iter()
+            .filter_map(|change| match *change {
+                TableChange::AddColumn(_, ref c) => c._type.named(),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Check whether a primary key was set on the columns of this table
+    fn has_primary_key(&self) -> bool {
+        self.changes.iter().any(|change| match *change {
+            TableChange::AddConstraint(_, Constraint::PrimaryKey(_)) => true,
+            TableChange::AddColumn(_, ref c) => c.primary,
+            _ => false,
+        })
+    }
+
+    /// Check that all changes on this table are supported by a backend
+    pub(crate) fn validate<T: SqlGenerator>(&self, gen: &T) -> Result<(), Error> {
+        gen.validate_table(&self.meta)?;
+        self.changes
+            .iter()
+            .try_for_each(|c| gen.validate_table_change(c))
+    }
+
+    /// Check that all changes on this existing table are supported by a backend
+    pub(crate) fn validate_alter<T: SqlGenerator>(&self, gen: &T) -> Result<(), Error> {
+        self.validate(gen)?;
+
+        /* Later changes can depend on earlier ones, e.g. a new column */
+        let mut table = self.known.clone();
+        for change in &self.changes {
+            gen.validate_alter_table(table.as_ref(), change)?;
+            if let Some(ref mut table) = table {
+                table.apply(change);
+            }
+        }
+        Ok(())
+    }
+
+    /// Create a table with the changes that undo the changes on this one
+    ///
+    /// Dropped or changed columns and the options of the table
+    /// can't be restored, because their previous definition isn't known.
+    pub(crate) fn reversed(&self) -> Result<Table, Error> {
+        use crate::TableChange::*;
+        if self.meta.has_options() {
+            return Err(Error::Irreversible(format!(
+                "Changing the options of table `{}`",
+                self.meta.name
+            )));
+        }
+
+        let mut t = Table::new(self.meta.name());
+        t.meta.has_id = false;
+
+        for change in self.changes.iter().rev() {
+            t.changes.push(match *change {
+                AddColumn(ref name, _) => DropColumn(name.clone()),
+                RenameColumn(ref old, ref new) => RenameColumn(new.clone(), old.clone()),
+                RenameConstraint(ref old, ref new) => RenameConstraint(new.clone(), old.clone()),
+                SetNotNull(ref name, not_null, ref c) => {
+                    SetNotNull(name.clone(), !not_null, c.clone())
+                }
+                DropColumn(ref name) => {
+                    return Err(Error::Irreversible(format!("Dropping column `{}`", name)))
+                }
+                ChangeColumn(ref name, _, _) | SetDefault(ref name, _) => {
+                    return Err(Error::Irreversible(format!("Changing column `{}`", name)))
+                }
+                CustomLine(ref sql) => {
+                    return Err(Error::Irreversible(format!("Custom SQL `{}`", sql)))
+                }
+                AddConstraint(Some(ref name), _) => DropConstraint(name.clone()),
+                AddConstraint(None, _) => {
+                    return Err(Error::Irreversible("Adding an unnamed constraint".into()))
+                }
+                DropConstraint(ref name) => {
+                    return Err(Error::Irreversible(format!(
+                        "Dropping constraint `{}`",
+                        name
+                    )))
+                }
+                AddIndex(ref name, _) => {
+                    return Err(Error::Irreversible(format!("Adding index `{}`", name)))
+                }
+            });
+        }
+
+        Ok(t)
+    }
+
+    pub fn make<T: SqlGenerator>(&mut self, gen: &T, ex: bool) -> Vec<String> {
+        self.make_guarded(gen, ex, false)
+    }
+
+    /// Generate the statements that create the indexes of this table,
+    /// with `IF NOT EXISTS` guards if `guarded` is set
+    pub(crate) fn make_indexes<T: SqlGenerator>(&self, gen: &T, guarded: bool) -> Vec<String> {
+        let table = self.meta.name();
+        self.changes
+            .iter()
+            .filter_map(|change| match *change {
+                TableChange::AddIndex(ref name, ref i) if guarded => {
+                    Some(gen.create_index_if_not_exists(&table, name, i))
+                }
+                TableChange::AddIndex(ref name, ref i) => Some(gen.create_index(&table, name, i)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Generate the column changes, with `IF (NOT) EXISTS`
+    /// guards on existing tables if `guarded` is set
+    ///
+    /// Indexes are left out, see `make_indexes`.
+    pub(crate) fn make_guarded<T: SqlGenerator>(
+        &mut self,
+        gen: &T,
+        ex: bool,
+        guarded: bool,
+    ) -> Vec<String> {
+        use crate::TableChange::*;
         let mut s = Vec::new();
+        let guarded = ex && guarded;
+
+        /* Constraints of a new table follow all of its columns */
+        if !ex {
+            self.changes
+                .sort_by_key(|change| matches!(change, AddConstraint(_, _)));
+        }
 
         for change in &mut self.changes {
-            s.push(match change {
-                &mut AddColumn(ref name, ref col) => {
-                    let mut s = T::add_column(ex, name, &col);
-                    s
+            s.push(match *change {
+                AddIndex(_, _) => continue,
+                AddColumn(ref name, ref col) if guarded => gen.add_column_if_not_exists(name, col),
+                AddColumn(ref name, ref col) => gen.add_table_column(&self.meta, ex, name, col),
+                DropColumn(ref name) if guarded => gen.drop_column_if_exists(name),
+                DropColumn(ref name) => gen.drop_column(name),
+                RenameColumn(ref old, ref new) => gen.rename_column(old, new),
+                RenameConstraint(ref old, ref new) => gen.rename_constraint(old, new),
+                DropConstraint(ref name) => gen.drop_constraint(name),
+                SetDefault(ref name, ref expr) => gen.set_default(name, expr.as_deref()),
+                SetNotNull(ref name, not_null, ref c) => {
+                    gen.set_not_null(name, not_null, c.as_ref())
+                }
+                ChangeColumn(ref name, ref col, ref cb) => {
+                    let mut col = col.clone();
+                    cb(&mut col);
+                    gen.change_column(name, &col)
                 }
-                &mut DropColumn(ref name) => T::drop_column(name),
-                &mut RenameColumn(ref old, ref new) => T::rename_column(old, new),
-                &mut ChangeColumn(ref mut name, _, _) => T::alter_table(name),
+                CustomLine(ref sql) => sql.clone(),
+                AddConstraint(ref name, ref c) => gen.add_constraint(ex, name.as_deref(), c),
             });
         }
 
-        return s;
+        s
     }
 }
 
 /// Some metadata about a table that was just created
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableMeta {
     pub name: String,
     pub has_id: bool,
     pub encoding: String,
+
+    /// The first value of the auto-incrementing column
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub auto_increment_start: Option<u64>,
+
+    /// A comment describing the table
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub comment: Option<String>,
+
+    /// The storage engine of the table (MySQL only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub engine: Option<String>,
+
+    /// The default character set of the columns (MySQL only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub charset: Option<String>,
+
+    /// The default collation of the columns (MySQL only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub collation: Option<String>,
+
+    /// How rows are stored, e.g. `DYNAMIC` (MySQL only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub row_format: Option<String>,
+
+    /// Only keep the table until the end of the session
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub temporary: bool,
+
+    /// Skip the write-ahead log for this table (Postgres only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unlogged: bool,
+
+    /// The tables whose columns this table inherits (Postgres only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub inherits: Vec<String>,
+
+    /// How the rows of this table are split into partitions
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub partition_by: Option<(PartitionKind, Vec<String>)>,
+
+    /// The names and values of the partitions (MySQL only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub partitions: Vec<(String, String)>,
+
+    /// The number of hash partitions (MySQL only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub partition_count: Option<u32>,
+
+    /// Store rows by their primary key instead of a rowid (Sqlite only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub without_rowid: bool,
+
+    /// Enforce the declared types of the columns (Sqlite only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub strict: bool,
+
+    /// Where the table is stored (Postgres only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tablespace: Option<String>,
+
+    /// Storage parameters such as `fillfactor` (Postgres and CockroachDB)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub storage_parameters: Vec<(String, String)>,
+
+    /// Only show the rows that policies allow (Postgres only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub row_level_security: bool,
 }
 
 impl TableMeta {
     /// Create a new tablemeta with default values
     pub fn new(name: String) -> TableMeta {
-        return TableMeta {
-            name: name,
+        TableMeta {
+            name,
             has_id: true,
             encoding: "utf-8".to_owned(),
-        };
+            auto_increment_start: None,
+            comment: None,
+            engine: None,
+            charset: None,
+            collation: None,
+            row_format: None,
+            temporary: false,
+            unlogged: false,
+            inherits: Vec::new(),
+            partition_by: None,
+            partitions: Vec::new(),
+            partition_count: None,
+            without_rowid: false,
+            strict: false,
+            tablespace: None,
+            storage_parameters: Vec::new(),
+            row_level_security: false,
+        }
     }
 
     /// Return a clone of the table name
     pub fn name(&self) -> String {
-        return self.name.clone();
+        self.name.clone()
+    }
+
+    /// Whether any options are set which change an existing table
+    pub(crate) fn has_options(&self) -> bool {
+        self.auto_increment_start.is_some()
+            || self.comment.is_some()
+            || self.engine.is_some()
+            || self.charset.is_some()
+            || self.collation.is_some()
+            || self.row_format.is_some()
+            || !self.inherits.is_empty()
+            || self.partition_by.is_some()
+            || !self.partitions.is_empty()
+            || self.partition_count.is_some()
+            || self.tablespace.is_some()
+            || !self.storage_parameters.is_empty()
+            || self.row_level_security
     }
 
     /// Disable the auto-key feature
@@ -129,18 +653,110 @@ impl TableMeta {
     /// init closure
     pub fn without_id(&mut self) -> &mut TableMeta {
         self.has_id = false;
-        return self;
+        self
+    }
+
+    /// Start counting the auto-incrementing column of this table at a value
+    ///
+    /// This sets `AUTO_INCREMENT` on MySQL and restarts the sequence of
+    /// the column on Postgres, where identity columns get `START WITH`
+    /// instead. A start set with `Identity::start` takes precedence.
+    pub fn auto_increment_start(&mut self, start: u64) -> &mut TableMeta {
+        self.auto_increment_start = Some(start);
+        self
+    }
+
+    /// Create a temporary table, which is dropped at the end of the session
+    ///
+    /// Useful for scratch data while migrating. Not supported by MSSQL,
+    /// whose temporary tables are named differently.
+    pub fn temporary(&mut self, temporary: bool) -> &mut TableMeta {
+        self.temporary = temporary;
+        self
+    }
+
+    /// Create an unlogged Postgres table, which is faster to write to
+    ///
+    /// The data of unlogged tables is lost after a crash and isn't
+    /// replicated, which suits caches and queues.
+    pub fn unlogged(&mut self, unlogged: bool) -> &mut TableMeta {
+        self.unlogged = unlogged;
+        self
+    }
+
+    /// Create a Sqlite table `WITHOUT ROWID`, clustered on its primary key
+    ///
+    /// Such tables need a primary key which isn't auto-incrementing, so
+    /// this is usually combined with `without_id`.
+    pub fn without_rowid(&mut self, without_rowid: bool) -> &mut TableMeta {
+        self.without_rowid = without_rowid;
+        self
+    }
+
+    /// Create a `STRICT` Sqlite table, which rejects values that don't
+    /// match the types of their columns
+    ///
+    /// Such tables only allow a few type names, so the types of their
+    /// columns are rendered as `INTEGER`, `REAL`, `TEXT`, `BLOB` or `ANY`.
+    pub fn strict(&mut self, strict: bool) -> &mut TableMeta {
+        self.strict = strict;
+        self
+    }
+
+    /// Store this Postgres table in a tablespace, e.g. on faster disks
+    ///
+    /// On an existing table this moves its data into the tablespace.
+    pub fn tablespace<S: Into<String>>(&mut self, tablespace: S) -> &mut TableMeta {
+        self.tablespace = Some(tablespace.into());
+        self
+    }
+
+    /// Set storage parameters of the table, rendered as `WITH (...)`
+    ///
+    /// A lower `fillfactor` leaves room for HOT updates, for example.
+    /// On an existing table the parameters are changed with `SET (...)`.
+    pub fn with_options(&mut self, options: &[(&str, &str)]) -> &mut TableMeta {
+        self.storage_parameters = options
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        self
+    }
+
+    /// Store this table with a MySQL engine, e.g. `InnoDB`
+    pub fn engine<S: Into<String>>(&mut self, engine: S) -> &mut TableMeta {
+        self.engine = Some(engine.into());
+        self
+    }
+
+    /// Set the default MySQL character set of the columns, e.g. `utf8mb4`
+    pub fn charset<S: Into<String>>(&mut self, charset: S) -> &mut TableMeta {
+        self.charset = Some(charset.into());
+        self
+    }
+
+    /// Set the default MySQL collation of the columns, e.g. `utf8mb4_unicode_ci`
+    pub fn collate<S: Into<String>>(&mut self, collation: S) -> &mut TableMeta {
+        self.collation = Some(collation.into());
+        self
+    }
+
+    /// Set the MySQL row format of this table, e.g. `DYNAMIC`
+    pub fn row_format<S: Into<String>>(&mut self, format: S) -> &mut TableMeta {
+        self.row_format = Some(format.into());
+        self
     }
 
     /// Specify an encoding for this table which might vary from the main encoding
     /// of your database
     pub fn encoding<S: Into<String>>(&mut self, enc: S) -> &mut TableMeta {
         self.encoding = enc.into();
-        return self;
+        self
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Column {
     /// Is this a unique key
     pub unique: bool,
@@ -154,36 +770,207 @@ pub struct Column {
     /// Does it auto-increment
     pub increments: bool,
 
+    /// Is this the primary key of its table
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub primary: bool,
+
     /// What's the column type
     pub _type: Type,
 
     /// What's default value records in this column
     pub def: Option<ColumnDefault>,
+
+    /// Is this an unsigned integer (MySQL only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unsigned: bool,
+
+    /// The expression this column is computed from, and how
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub generated: Option<(String, Generated)>,
+
+    /// Is this an identity column, and how does it count
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub identity: Option<Identity>,
+
+    /// Conditions all values of this column must meet, with optional names
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub checks: Vec<(Option<String>, String)>,
+
+    /// How values of this column are compared and sorted
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub collation: Option<String>,
+
+    /// The character set of this column (MySQL only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub charset: Option<String>,
+
+    /// An SQL expression the column is set to when its row changes (MySQL only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub on_update: Option<String>,
+
+    /// How Postgres stores large values of this column
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub storage: Option<Storage>,
+
+    /// The method Postgres compresses large values of this column with
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub compression: Option<String>,
+
+    /// How existing values are converted when the type of the
+    /// column is changed (Postgres only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub using: Option<String>,
 }
 
 impl Column {
     /// Lazy constructor mostly used in unit tests
     pub fn new(t: Type) -> Column {
-        return Column {
+        Column {
             indexed: false,
             unique: false,
             nullable: false,
             increments: false,
+            primary: false,
             _type: t,
             def: None,
-        };
+            unsigned: false,
+            generated: None,
+            identity: None,
+            checks: Vec::new(),
+            collation: None,
+            charset: None,
+            on_update: None,
+            storage: None,
+            compression: None,
+            using: None,
+        }
     }
 
     /// Set a default value for this column
     pub fn default<T: Into<ColumnDefault>>(&mut self, data: T) -> &mut Column {
         self.def = Some(data.into());
-        return self;
+        self
+    }
+
+    /// Set an SQL expression as the default value of this column
+    ///
+    /// The expression is rendered as is, e.g. `gen_random_uuid()`.
+    pub fn default_expr<S: Into<String>>(&mut self, expr: S) -> &mut Column {
+        self.def = Some(ColumnDefault::Expression(expr.into()));
+        self
+    }
+
+    /// Set the column to an SQL expression whenever its row changes
+    ///
+    /// Only MySQL can do this, other backends reject it.
+    pub fn on_update_expr<S: Into<String>>(&mut self, expr: S) -> &mut Column {
+        self.on_update = Some(expr.into());
+        self
+    }
+
+    /// Keep a timestamp column at the time its row last changed (MySQL only)
+    ///
+    /// This is the usual way to keep an `updated_at` column on MySQL.
+    pub fn on_update_current_timestamp(&mut self) -> &mut Column {
+        self.on_update_expr("CURRENT_TIMESTAMP")
     }
 
     /// Set a column to allow being null
     pub fn nullable(&mut self) -> &mut Column {
         self.nullable = true;
-        return self;
+        self
+    }
+
+    /// Set the collation of a text column, e.g. `de_DE` or `utf8mb4_unicode_ci`
+    pub fn collate<S: Into<String>>(&mut self, collation: S) -> &mut Column {
+        self.collation = Some(collation.into());
+        self
+    }
+
+    /// Set the character set of a text column, e.g. `utf8mb4`
+    ///
+    /// Only MySQL has per-column character sets, other backends reject them.
+    pub fn charset<S: Into<String>>(&mut self, charset: S) -> &mut Column {
+        self.charset = Some(charset.into());
+        self
+    }
+
+    /// Set how large values of this column are stored (Postgres only)
+    ///
+    /// Setting this on a new column needs Postgres 16.
+    pub fn storage(&mut self, storage: Storage) -> &mut Column {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Set the method large values of this column are compressed with,
+    /// e.g. `lz4` (Postgres 14 and later)
+    pub fn compression<S: Into<String>>(&mut self, method: S) -> &mut Column {
+        self.compression = Some(method.into());
+        self
+    }
+
+    /// Convert the existing values with an expression when changing the
+    /// type of the column, e.g. `age::bigint` or `NULLIF(code, '')::int`
+    ///
+    /// Postgres needs one for conversions it can't do implicitly.
+    pub fn using<S: Into<String>>(&mut self, expr: S) -> &mut Column {
+        self.using = Some(expr.into());
+        self
+    }
+
+    /// Make an integer column unsigned
+    ///
+    /// Only MySQL has unsigned integers, other backends reject them.
+    pub fn unsigned(&mut self) -> &mut Column {
+        self.unsigned = true;
+        self
+    }
+
+    /// Compute the values of this column from an SQL expression
+    ///
+    /// The expression can use other columns of the same row,
+    /// e.g. `price * quantity`.
+    pub fn generated<S: Into<String>>(&mut self, expr: S, kind: Generated) -> &mut Column {
+        self.generated = Some((expr.into(), kind));
+        self
+    }
+
+    /// Make this an identity column, which numbers rows by itself
+    ///
+    /// Unlike `SERIAL` this is standard SQL, e.g.
+    /// `GENERATED ALWAYS AS IDENTITY (START WITH 100)`.
+    pub fn identity(&mut self, identity: Identity) -> &mut Column {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Add a condition all values of this column must meet
+    ///
+    /// The condition is an SQL expression, e.g. `length(name) > 0`.
+    pub fn check<S: Into<String>>(&mut self, expr: S) -> &mut Column {
+        self.checks.push((None, expr.into()));
+        self
+    }
+
+    /// Add a condition all values of this column must meet, as a named constraint
+    pub fn check_named<N: Into<String>, S: Into<String>>(
+        &mut self,
+        name: N,
+        expr: S,
+    ) -> &mut Column {
+        self.checks.push((Some(name.into()), expr.into()));
+        self
+    }
+
+    /// Make this column the primary key of its table
+    ///
+    /// Unlike `Type::Primary` this works for columns of any type,
+    /// e.g. a `Type::Uuid`. The default `id` column is left out of
+    /// tables with such a column.
+    pub fn primary(&mut self) -> &mut Column {
+        self.primary = true;
+        self
     }
 
     /// Setup this column to automatically increment (such as integers)
@@ -191,11 +978,268 @@ impl Column {
     /// Throws an error if the column type *can't* increment (like booleans)
     pub fn increments(&mut self) -> &mut Column {
         self.increments = true;
-        return self;
+        self
     }
 }
 
-#[derive(Debug, Clone)]
+impl From<Type> for Column {
+    fn from(t: Type) -> Self {
+        Column::new(t)
+    }
+}
+
+/// An index over columns of a table
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Index {
+    pub(crate) columns: Vec<String>,
+    /// Indexed instead of the columns, if set
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) expression: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) unique: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) kind: Option<IndexKind>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) include: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) where_clause: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) tablespace: Option<String>,
+}
+
+impl Index {
+    pub(crate) fn new(columns: Vec<String>) -> Index {
+        Index {
+            columns,
+            expression: None,
+            unique: false,
+            kind: None,
+            include: Vec::new(),
+            where_clause: None,
+            tablespace: None,
+        }
+    }
+
+    /// Only allow one row for each combination of values
+    pub fn unique(&mut self) -> &mut Index {
+        self.unique = true;
+        self
+    }
+
+    /// Use a special kind of index, which only MySQL has
+    pub fn kind(&mut self, kind: IndexKind) -> &mut Index {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Store more columns in the index, so that queries can read them
+    /// without visiting the table
+    ///
+    /// Only supported by Postgres, CockroachDB and MSSQL.
+    pub fn include(&mut self, columns: &[&str]) -> &mut Index {
+        self.include = columns.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    /// Only index the rows that match a condition, e.g. `deleted_at IS NULL`
+    ///
+    /// Together with `unique` this keeps values unique among the rows
+    /// that were not soft-deleted. Not supported by MySQL.
+    pub fn where_clause<S: Into<String>>(&mut self, expr: S) -> &mut Index {
+        self.where_clause = Some(expr.into());
+        self
+    }
+
+    /// Store the index in a Postgres tablespace
+    pub fn tablespace<S: Into<String>>(&mut self, tablespace: S) -> &mut Index {
+        self.tablespace = Some(tablespace.into());
+        self
+    }
+}
+
+/// How rows are assigned to the partitions of a table
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PartitionKind {
+    /// By ranges of values, e.g. one partition per year
+    Range,
+    /// By lists of values
+    List,
+    /// By the hash of the values, to spread rows evenly
+    Hash,
+}
+
+impl PartitionKind {
+    /// The kind in SQL
+    #[cfg(any(feature = "pg", feature = "mysql"))]
+    pub(crate) fn sql(self) -> &'static str {
+        match self {
+            PartitionKind::Range => "RANGE",
+            PartitionKind::List => "LIST",
+            PartitionKind::Hash => "HASH",
+        }
+    }
+}
+
+/// Special kinds of indexes in MySQL
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IndexKind {
+    /// For `MATCH ... AGAINST` searches in text columns
+    Fulltext,
+    /// For geometry columns
+    Spatial,
+}
+
+impl IndexKind {
+    /// The kind in SQL
+    pub(crate) fn sql(self) -> &'static str {
+        match self {
+            IndexKind::Fulltext => "FULLTEXT",
+            IndexKind::Spatial => "SPATIAL",
+        }
+    }
+}
+
+/// A constraint over one or more columns of a table
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Constraint {
+    /// The columns which identify a row together
+    PrimaryKey(Vec<String>),
+    /// The columns whose values must not repeat together
+    Unique(Vec<String>),
+    /// An SQL condition that every row must meet
+    Check(String),
+    /// Columns and operators that no two rows may all match on, using
+    /// an index method such as `gist` (Postgres only)
+    Exclusion {
+        method: String,
+        elements: Vec<(String, String)>,
+    },
+    /// Columns which point to the columns of another table together
+    ForeignKey {
+        columns: Vec<String>,
+        table: String,
+        references: Vec<String>,
+    },
+}
+
+/// How Postgres stores large values of a column
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Storage {
+    /// Inline and uncompressed, for fixed-length types
+    Plain,
+    /// Compressed, and moved out of the row if still too large
+    Extended,
+    /// Moved out of the row, but never compressed
+    External,
+    /// Compressed, and only moved out of the row as a last resort
+    Main,
+}
+
+impl Storage {
+    /// The storage in SQL
+    #[cfg(feature = "pg")]
+    pub(crate) fn sql(self) -> &'static str {
+        match self {
+            Storage::Plain => "PLAIN",
+            Storage::Extended => "EXTENDED",
+            Storage::External => "EXTERNAL",
+            Storage::Main => "MAIN",
+        }
+    }
+}
+
+/// How the values of a generated column are kept
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Generated {
+    /// Computed on write and stored like other columns
+    Stored,
+    /// Computed every time the column is read
+    Virtual,
+}
+
+/// How an identity column counts
+///
+/// ```
+/// # use barrel::Identity;
+/// let id = Identity::by_default().start(100).increment(10);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Identity {
+    /// Reject explicit values on insert, unless they are overridden
+    pub always: bool,
+    /// The first value
+    pub start: Option<i64>,
+    /// The step between two values
+    pub increment: Option<i64>,
+}
+
+impl Identity {
+    /// An identity which can't be given a value on insert
+    pub fn always() -> Identity {
+        Identity {
+            always: true,
+            start: None,
+            increment: None,
+        }
+    }
+
+    /// An identity which is only used if no value is given on insert
+    pub fn by_default() -> Identity {
+        Identity {
+            always: false,
+            ..Identity::always()
+        }
+    }
+
+    /// Set the first value
+    pub fn start(self, start: i64) -> Identity {
+        Identity {
+            start: Some(start),
+            ..self
+        }
+    }
+
+    /// Set the step between two values
+    pub fn increment(self, increment: i64) -> Identity {
+        Identity {
+            increment: Some(increment),
+            ..self
+        }
+    }
+
+    /// Render the standard `GENERATED ... AS IDENTITY` clause
+    #[cfg(any(feature = "pg", feature = "ansi"))]
+    pub(crate) fn sql(&self) -> String {
+        let options: Vec<String> = self
+            .start
+            .map(|s| format!("START WITH {}", s))
+            .into_iter()
+            .chain(self.increment.map(|i| format!("INCREMENT BY {}", i)))
+            .collect();
+
+        format!(
+            "GENERATED {} AS IDENTITY{}",
+            match self.always {
+                true => "ALWAYS",
+                false => "BY DEFAULT",
+            },
+            match options.is_empty() {
+                true => String::new(),
+                false => format!(" ({})", options.join(" ")),
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ColumnDefault {
     Text(String),
     Varchar(usize),
@@ -205,62 +1249,85 @@ pub enum ColumnDefault {
 
     /// A foreign key has a table and id it points to
     Foreign(String, u64),
+
+    /// An SQL expression, which isn't quoted
+    Expression(String),
     // TODO: Figure out storage for other data types
 }
 
+impl ColumnDefault {
+    /// Render the default as it follows `DEFAULT` in a column definition
+    ///
+    /// Quotes in literals are doubled, expressions are left alone.
+    #[cfg(any(
+        feature = "pg",
+        feature = "mysql",
+        feature = "sqlite3",
+        feature = "mssql",
+        feature = "ansi"
+    ))]
+    pub(crate) fn sql(&self) -> String {
+        match *self {
+            ColumnDefault::Expression(ref expr) => expr.clone(),
+            ref lit => format!("'{}'", lit.to_string().replace('\'', "''")),
+        }
+    }
+}
+
 impl From<&'static str> for ColumnDefault {
     fn from(data: &'static str) -> Self {
-        return ColumnDefault::Text(data.into());
+        ColumnDefault::Text(data.into())
     }
 }
 
 impl From<i64> for ColumnDefault {
     fn from(data: i64) -> Self {
-        return ColumnDefault::Integer(data);
+        ColumnDefault::Integer(data)
     }
 }
 
 impl From<i32> for ColumnDefault {
     fn from(data: i32) -> Self {
-        return ColumnDefault::Integer(data as i64);
+        ColumnDefault::Integer(data as i64)
     }
 }
 
 impl From<usize> for ColumnDefault {
     fn from(data: usize) -> Self {
-        return ColumnDefault::Integer(data as i64);
+        ColumnDefault::Integer(data as i64)
     }
 }
 
 impl From<f64> for ColumnDefault {
     fn from(data: f64) -> Self {
-        return ColumnDefault::Float(data);
+        ColumnDefault::Float(data)
     }
 }
 
 impl From<bool> for ColumnDefault {
     fn from(data: bool) -> Self {
-        return ColumnDefault::Boolean(data);
+        ColumnDefault::Boolean(data)
     }
 }
 
 impl Display for ColumnDefault {
     fn fmt(&self, f: &mut Formatter) -> FmtResult {
         use self::ColumnDefault::*;
-        return write!(
+        write!(
             f,
             "{}",
             &match *self {
-                Text(ref val) => format!("{}", val),
+                Text(ref val) => val.to_string(),
                 Varchar(ref val) => format!("{}", val),
                 Integer(ref val) => format!("{}", val),
                 Float(ref val) => format!("{}", val),
-                Boolean(ref val) => match val {
-                    &true => format!("t"),
-                    &false => format!("f"),
+                Boolean(ref val) => match *val {
+                    true => "t".to_string(),
+                    false => "f".to_string(),
                 },
-                Foreign(ref val, _) => format!("{}", val),
+                Foreign(ref val, _) => val.to_string(),
+                Expression(ref expr) => expr.to_string(),
             }
-        );
+        )
     }
 }
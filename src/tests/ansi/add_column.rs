@@ -0,0 +1,119 @@
+//! All add_column combinations for ansi sql
+#![allow(unused_imports)]
+
+use crate::backend::{Ansi, SqlGenerator};
+use crate::Type::*;
+use crate::{Column, Error, TableChange};
+
+#[test]
+fn text() {
+    let sql = Ansi.add_column(true, "Text", &Column::new(Text));
+    assert_eq!(String::from("ADD COLUMN \"Text\" CLOB"), sql);
+}
+
+#[test]
+fn varchar() {
+    let sql = Ansi.add_column(true, "Varchar", &Column::new(Varchar(255)));
+    assert_eq!(String::from("ADD COLUMN \"Varchar\" VARCHAR(255)"), sql);
+}
+
+#[test]
+fn double() {
+    let sql = Ansi.add_column(true, "Double", &Column::new(Double));
+    assert_eq!(String::from("ADD COLUMN \"Double\" DOUBLE PRECISION"), sql);
+}
+
+#[test]
+fn binary() {
+    let sql = Ansi.add_column(true, "Binary", &Column::new(Binary));
+    assert_eq!(String::from("ADD COLUMN \"Binary\" BLOB"), sql);
+}
+
+#[test]
+fn foreign() {
+    let sql = Ansi.add_column(true, "Foreign", &Column::new(Foreign("posts".into())));
+    assert_eq!(
+        String::from("ADD COLUMN \"Foreign\" INTEGER REFERENCES \"posts\""),
+        sql
+    );
+}
+
+#[test]
+fn array_integer() {
+    let sql = Ansi.add_column(
+        true,
+        "Array of Integer",
+        &Column::new(Array(Box::new(Integer))),
+    );
+    assert_eq!(
+        String::from("ADD COLUMN \"Array of Integer\" INTEGER ARRAY"),
+        sql
+    );
+}
+
+#[test]
+fn unbounded_varchar() {
+    let change = TableChange::AddColumn("Varchar".into(), Column::new(Varchar(0)));
+    assert_eq!(
+        Ansi.validate_table_change(&change),
+        Err(Error::Unsupported(
+            "ANSI",
+            "VARCHAR without a length".into()
+        ))
+    );
+}
+
+#[test]
+fn interval() {
+    let sql = Ansi.add_column(
+        true,
+        "Interval",
+        &Column::new(Interval(Some("YEAR TO MONTH".into()))),
+    );
+    assert_eq!(
+        String::from("ADD COLUMN \"Interval\" INTERVAL YEAR TO MONTH"),
+        sql
+    );
+
+    let change = TableChange::AddColumn("Interval".into(), Column::new(Interval(None)));
+    assert_eq!(
+        Ansi.validate_table_change(&change),
+        Err(Error::Unsupported(
+            "ANSI",
+            "INTERVAL without a fields qualifier".into()
+        ))
+    );
+}
+
+#[test]
+fn enumeration() {
+    let t = crate::Type::enumeration("mood", &["happy", "sad"]);
+    let sql = Ansi.add_column(true, "Enum", &Column::new(t));
+    assert_eq!(
+        String::from("ADD COLUMN \"Enum\" VARCHAR(5) CHECK (\"Enum\" IN ('happy', 'sad'))"),
+        sql
+    );
+}
+
+#[test]
+fn macaddr() {
+    let sql = Ansi.add_column(true, "MacAddr", &Column::new(MacAddr));
+    assert_eq!(String::from("ADD COLUMN \"MacAddr\" VARCHAR(17)"), sql);
+}
+
+#[test]
+fn varbit() {
+    let sql = Ansi.add_column(true, "Varbit", &Column::new(Varbit(64)));
+    assert_eq!(String::from("ADD COLUMN \"Varbit\" VARBINARY(8)"), sql);
+}
+
+#[test]
+fn generated() {
+    let mut col = Column::new(Integer);
+    col.generated("price * quantity", crate::Generated::Stored);
+    let sql = Ansi.add_column(true, "total", &col);
+    assert_eq!(
+        String::from("ADD COLUMN \"total\" INTEGER GENERATED ALWAYS AS (price * quantity)"),
+        sql
+    );
+}
@@ -0,0 +1,84 @@
+//! Some unit tests that create and change tables
+#![allow(unused_imports)]
+
+use crate::backend::{Ansi, SqlGenerator};
+use crate::{Error, Migration, Table};
+
+#[test]
+fn simple_table() {
+    let mut m = Migration::new();
+    m.create_table("users", |_: &mut Table| {});
+    assert_eq!(
+        m.make::<Ansi>(),
+        String::from(
+            "CREATE TABLE \"users\" (\"id\" INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY)"
+        )
+    );
+}
+
+#[test]
+fn drop_table() {
+    let mut m = Migration::new();
+    m.drop_table("users");
+    assert_eq!(
+        m.try_make::<Ansi>(),
+        Ok(String::from("DROP TABLE \"users\""))
+    );
+}
+
+#[test]
+fn drop_table_if_exists() {
+    let mut m = Migration::new();
+    m.drop_table_if_exists("users");
+    assert_eq!(
+        m.try_make::<Ansi>(),
+        Err(Error::Unsupported("ANSI", "DROP TABLE IF EXISTS".into()))
+    );
+}
+
+#[test]
+fn idempotent() {
+    let mut m = Migration::new();
+    m.set_idempotent(true);
+    m.create_table("users", |_: &mut Table| {});
+    m.create_table_from_query("admins", "SELECT * FROM \"users\"");
+    m.drop_table("sessions");
+    assert_eq!(
+        m.try_make::<Ansi>(),
+        Ok(String::from(
+            "CREATE TABLE \"users\" (\"id\" INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY); \
+             CREATE TABLE \"admins\" AS SELECT * FROM \"users\"; \
+             DROP TABLE \"sessions\""
+        ))
+    );
+}
+
+#[test]
+fn rename_table() {
+    let mut m = Migration::new();
+    m.rename_table("users", "cool_users");
+    assert_eq!(
+        m.try_make::<Ansi>(),
+        Err(Error::Unsupported("ANSI", "Renaming a table".into()))
+    );
+}
+
+#[test]
+fn rename_column() {
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.rename_column("name", "nickname");
+    });
+    assert_eq!(
+        m.try_make::<Ansi>(),
+        Err(Error::Unsupported("ANSI", "Renaming a column".into()))
+    );
+}
+
+#[test]
+#[should_panic]
+fn make_unsupported() {
+    let mut m = Migration::new();
+    m.rename_table("users", "cool_users");
+    m.make::<Ansi>();
+}
@@ -0,0 +1,4 @@
+//! Test ansi generation
+
+mod add_column;
+mod create_table;
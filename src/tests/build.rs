@@ -0,0 +1,64 @@
+//! Render a directory of migration files
+
+use crate::build::{migration_files, render, RenderedMigration};
+
+use std::fs;
+
+#[test]
+fn render_directory() {
+    let base = std::env::temp_dir().join(format!("barrel-build-{}", std::process::id()));
+    let dir = base.join("migrations");
+    fs::create_dir_all(&dir).unwrap();
+
+    fs::write(
+        dir.join("0001_create_users.rs"),
+        "use barrel::*;
+
+pub fn up(m: &mut Migration) {
+    m.create_table(\"users\", |t| {
+        t.add_column(\"name\", Type::Text);
+    });
+}
+
+pub fn down(m: &mut Migration) {}
+",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("0002_drop_users.rs"),
+        "use barrel::*;
+
+pub fn up(m: &mut Migration) {
+    m.drop_table(\"users\");
+}
+
+pub fn down(m: &mut Migration) {}
+",
+    )
+    .unwrap();
+    fs::write(dir.join("README.md"), "Not a migration").unwrap();
+
+    assert_eq!(migration_files(&dir).unwrap().len(), 2);
+    assert_eq!(
+        render(&dir, "pg", base.join("work")).unwrap(),
+        vec![
+            RenderedMigration {
+                name: "0001_create_users".into(),
+                up: "CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY, \"name\" TEXT)".into(),
+                down: Ok("DROP TABLE \"users\"".into()),
+            },
+            RenderedMigration {
+                name: "0002_drop_users".into(),
+                up: "DROP TABLE \"users\"".into(),
+                down: Err("Dropping table `users` can not be reverted".into()),
+            },
+        ]
+    );
+
+    fs::remove_dir_all(&base).unwrap();
+}
+
+#[test]
+fn missing_directory() {
+    assert!(migration_files("does/not/exist").unwrap().is_empty());
+}
@@ -0,0 +1,167 @@
+//! add_column combinations that differ from pgsql
+#![allow(unused_imports)]
+
+use crate::backend::{Cockroach, SqlGenerator};
+use crate::Column;
+use crate::Type::*;
+
+#[test]
+fn text() {
+    let sql = Cockroach::default().add_column(true, "Text", &Column::new(Text));
+    assert_eq!(String::from("ADD COLUMN \"Text\" TEXT"), sql);
+}
+
+#[test]
+fn float() {
+    let sql = Cockroach::default().add_column(true, "Float", &Column::new(Float));
+    assert_eq!(String::from("ADD COLUMN \"Float\" FLOAT4"), sql);
+}
+
+#[test]
+fn double() {
+    let sql = Cockroach::default().add_column(true, "Double", &Column::new(Double));
+    assert_eq!(String::from("ADD COLUMN \"Double\" FLOAT8"), sql);
+}
+
+#[test]
+fn binary() {
+    let sql = Cockroach::default().add_column(true, "Binary", &Column::new(Binary));
+    assert_eq!(String::from("ADD COLUMN \"Binary\" BYTES"), sql);
+}
+
+#[test]
+fn array_binary() {
+    let sql = Cockroach::default().add_column(
+        true,
+        "Array of Binary",
+        &Column::new(Array(Box::new(Binary))),
+    );
+    assert_eq!(String::from("ADD COLUMN \"Array of Binary\" BYTES[]"), sql);
+}
+
+#[test]
+#[should_panic]
+fn array_array_integer() {
+    Cockroach::default().add_column(
+        true,
+        "Array of Array of Integer",
+        &Column::new(Array(Box::new(Array(Box::new(Integer))))),
+    );
+}
+
+#[test]
+fn money() {
+    let sql = Cockroach::default().add_column(true, "Money", &Column::new(Money));
+    assert_eq!(String::from("ADD COLUMN \"Money\" DECIMAL(19, 4)"), sql);
+}
+
+#[test]
+fn cidr() {
+    let sql = Cockroach::default().add_column(true, "Cidr", &Column::new(Cidr));
+    assert_eq!(String::from("ADD COLUMN \"Cidr\" INET"), sql);
+}
+
+#[test]
+fn xml() {
+    let sql = Cockroach::default().add_column(true, "Xml", &Column::new(Xml));
+    assert_eq!(String::from("ADD COLUMN \"Xml\" STRING"), sql);
+}
+
+#[test]
+fn hstore() {
+    use crate::{Error, TableChange};
+
+    let change = TableChange::AddColumn("Hstore".into(), Column::new(Hstore));
+    assert_eq!(
+        Cockroach::default().validate_table_change(&change),
+        Err(Error::Unsupported("CockroachDB", "HSTORE types".into()))
+    );
+}
+
+#[test]
+fn citext() {
+    use crate::{Migration, Table};
+
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_column("email", Citext);
+    });
+    assert_eq!(
+        m.make::<Cockroach>(),
+        String::from(
+            "ALTER TABLE \"users\" ADD COLUMN \"email\" STRING COLLATE \"und-u-ks-level2\""
+        )
+    );
+}
+
+#[test]
+fn range() {
+    use crate::{Error, RangeKind, TableChange};
+
+    let t = Range(RangeKind::Int8);
+    let change = TableChange::AddColumn("Range".into(), Column::new(t));
+    assert_eq!(
+        Cockroach::default().validate_table_change(&change),
+        Err(Error::Unsupported("CockroachDB", "Range types".into()))
+    );
+}
+
+#[test]
+#[cfg(feature = "postgis")]
+fn geometry() {
+    use crate::{GeometryKind, Migration, Table};
+
+    let mut m = Migration::new();
+    m.change_table("shops", |t: &mut Table| {
+        t.add_column("location", Geometry(GeometryKind::Point, None));
+    });
+    assert_eq!(
+        m.make::<Cockroach>(),
+        String::from("ALTER TABLE \"shops\" ADD COLUMN \"location\" GEOMETRY(POINT)")
+    );
+}
+
+#[test]
+fn nested_array() {
+    use crate::{Error, TableChange};
+
+    let t = crate::Type::array(Integer, 2);
+    let change = TableChange::AddColumn("Matrix".into(), Column::new(t));
+    assert_eq!(
+        Cockroach::default().validate_table_change(&change),
+        Err(Error::Unsupported(
+            "CockroachDB",
+            "Nested array types".into()
+        ))
+    );
+}
+
+#[test]
+fn array_of_unsupported_types() {
+    use crate::{Error, TableChange};
+
+    let change = TableChange::AddColumn("Tags".into(), Column::new(crate::Type::array(Hstore, 1)));
+    assert_eq!(
+        Cockroach::default().validate_table_change(&change),
+        Err(Error::Unsupported("CockroachDB", "HSTORE types".into()))
+    );
+
+    let t = crate::Type::custom_per(&[]);
+    let change = TableChange::AddColumn("Blobs".into(), Column::new(crate::Type::array(t, 1)));
+    assert_eq!(
+        Cockroach::default().validate_table_change(&change),
+        Err(Error::Unsupported(
+            "CockroachDB",
+            "A custom type without an entry for it".into()
+        ))
+    );
+}
+
+#[test]
+fn custom_per_falls_back_to_pg() {
+    use crate::backend::SqlVariant;
+
+    let t = crate::Type::custom_per(&[(SqlVariant::Pg, "LTREE")]);
+    let sql = Cockroach::default().add_column(true, "Path", &Column::new(t));
+    assert_eq!(String::from("ADD COLUMN \"Path\" LTREE"), sql);
+}
@@ -0,0 +1,6 @@
+//! Test cockroach generation
+//!
+//! Only the places where Cockroach differs from Postgres are tested here
+
+mod add_column;
+mod simple;
@@ -0,0 +1,39 @@
+//! Other simple table/ column migrations
+#![allow(unused_imports)]
+
+use crate::backend::{Cockroach, SqlGenerator};
+use crate::{Migration, Table};
+
+#[test]
+fn create_table_if_not_exists() {
+    let sql = Cockroach::default().create_table_if_not_exists("table_to_create");
+    assert_eq!(
+        String::from("CREATE TABLE IF NOT EXISTS \"table_to_create\""),
+        sql
+    );
+}
+
+#[test]
+fn drop_table_if_exists() {
+    let sql = Cockroach::default().drop_table_if_exists("table_to_drop");
+    assert_eq!(String::from("DROP TABLE IF EXISTS \"table_to_drop\""), sql);
+}
+
+#[test]
+fn rename_column() {
+    let sql = Cockroach::default().rename_column("old_column", "new_column");
+    assert_eq!(
+        String::from("RENAME COLUMN \"old_column\" TO \"new_column\""),
+        sql
+    );
+}
+
+#[test]
+fn simple_table() {
+    let mut m = Migration::new();
+    m.create_table("users", |_: &mut Table| {});
+    assert_eq!(
+        m.make::<Cockroach>(),
+        String::from("CREATE TABLE \"users\" (\"id\" INT8 DEFAULT unique_rowid() PRIMARY KEY)")
+    );
+}
@@ -0,0 +1,63 @@
+//! Checksums of migrations
+
+use crate::{Migration, Table, Type};
+
+fn users(cb: fn(&mut Table)) -> Migration {
+    let mut m = Migration::new();
+    m.create_table("users", cb);
+    m
+}
+
+#[test]
+fn same_changes() {
+    let a = users(|t| {
+        t.add_column("name", Type::Text);
+    });
+    let b = users(|t| {
+        t.add_column("name", Type::Text);
+    });
+
+    assert_eq!(a.checksum(), b.checksum());
+}
+
+#[test]
+fn changed_column() {
+    let a = users(|t| {
+        t.add_column("name", Type::Text);
+    });
+    let b = users(|t| {
+        t.add_column("name", Type::Text).nullable();
+    });
+
+    assert_ne!(a.checksum(), b.checksum());
+}
+
+#[test]
+fn changed_down() {
+    let a = users(|_| {});
+    let mut b = users(|_| {});
+    b.down(|m| m.drop_table("users"));
+
+    assert_ne!(a.checksum(), b.checksum());
+}
+
+/// Checksums are stored, so they must not change between runs
+#[test]
+fn stable() {
+    let m = users(|t| {
+        t.add_column("name", Type::Text);
+    });
+    assert_eq!(format!("{:016x}", m.checksum()), "5fcfe5ab1bbd5836");
+}
+
+#[test]
+fn unsigned_column() {
+    let a = users(|t| {
+        t.add_column("age", Type::Integer);
+    });
+    let b = users(|t| {
+        t.add_column("age", Type::Integer).unsigned();
+    });
+
+    assert_ne!(a.checksum(), b.checksum());
+}
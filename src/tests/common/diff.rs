@@ -0,0 +1,55 @@
+//! Compute migrations between schemas
+
+use crate::diff::diff;
+use crate::schema::Schema;
+use crate::{Migration, Table, Type};
+
+fn users(cb: fn(&mut Table)) -> Schema {
+    let mut m = Migration::new();
+    m.create_table("users", cb);
+    Schema::from_migrations(&[m])
+}
+
+#[test]
+fn identical() {
+    let a = users(|t| {
+        t.add_column("name", Type::Text);
+    });
+
+    assert!(diff(&a, &a.clone()).changes.is_empty());
+}
+
+#[test]
+fn create_and_drop_tables() {
+    let from = users(|_| {});
+    let mut m = Migration::new();
+    m.create_table("posts", |t: &mut Table| {
+        t.add_column("title", Type::Text);
+    });
+    let to = Schema::from_migrations(&[m]);
+
+    let m = diff(&from, &to);
+    let mut applied = from.clone();
+    applied.apply(&m);
+    assert_eq!(applied, to);
+    assert_eq!(m.changes.len(), 2);
+}
+
+#[test]
+fn change_columns() {
+    let from = users(|t| {
+        t.add_column("name", Type::Text);
+        t.add_column("age", Type::Integer);
+    });
+    let to = users(|t| {
+        t.add_column("name", Type::Varchar(255));
+        t.add_column("email", Type::Text).nullable();
+    });
+
+    let m = diff(&from, &to);
+    assert_eq!(m.changes.len(), 1);
+
+    let mut applied = from.clone();
+    applied.apply(&m);
+    assert_eq!(applied, to);
+}
@@ -1,4 +1,10 @@
-use Migration;
+use crate::Migration;
+
+mod checksum;
+mod diff;
+
+#[cfg(feature = "serde")]
+mod serde;
 
 #[test]
 fn create_multiple_tables() {
@@ -8,3 +14,31 @@ fn create_multiple_tables() {
 
     assert!(migr.changes.len() == 2);
 }
+
+#[test]
+fn unknown_backend() {
+    use crate::backend::SqlVariant;
+    use crate::Error;
+
+    let variant = "oracle".parse::<SqlVariant>();
+    assert_eq!(variant, Err(Error::UnknownBackend("oracle".into())));
+}
+
+#[test]
+#[cfg(feature = "unstable")]
+fn heterogeneous_type_defaults() {
+    use crate::types::{self, WrappedDefault};
+
+    let cols = [
+        types::integer().default(42),
+        types::text().default("it's"),
+        types::boolean().default(true),
+        types::datetime().default_expr("now()"),
+    ];
+
+    let defaults: Vec<_> = cols
+        .iter()
+        .filter_map(|t| t.default.as_ref().map(WrappedDefault::to_string))
+        .collect();
+    assert_eq!(defaults, vec!["42", "'it''s'", "true", "now()"]);
+}
@@ -0,0 +1,70 @@
+//! Round-trip the migration model through JSON
+
+use crate::schema::Schema;
+use crate::{Migration, Table, Type};
+
+fn migrations() -> Vec<Migration> {
+    let mut first = Migration::new();
+    first.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Varchar(255)).nullable();
+        t.add_column("legacy", Type::Custom("CITEXT".into()));
+    });
+    first.create_table("posts", |t: &mut Table| {
+        t.add_column("author", Type::Foreign("users".into()));
+        t.add_column("tags", Type::Array(Box::new(Type::Text)));
+    });
+
+    let mut second = Migration::new();
+    second.change_table("users", |t| {
+        t.rename_column("name", "username");
+        t.drop_column("legacy");
+    });
+    second.rename_table("posts", "articles");
+
+    vec![first, second]
+}
+
+#[test]
+fn schema_round_trip() {
+    let schema = Schema::from_migrations(&migrations());
+    let json = serde_json::to_string(&schema).unwrap();
+    let loaded: Schema = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(loaded, schema);
+}
+
+#[test]
+fn migration_round_trip() {
+    let migrations = migrations();
+    let json = serde_json::to_string(&migrations).unwrap();
+    let loaded: Vec<Migration> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(
+        Schema::from_migrations(&loaded),
+        Schema::from_migrations(&migrations)
+    );
+    assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+}
+
+#[test]
+fn foreign_key_formats() {
+    use crate::{ForeignKey, ReferentialAction};
+
+    let old: Type = serde_json::from_str(r#"{"Foreign":"users"}"#).unwrap();
+    assert_eq!(old, Type::foreign("users"));
+
+    let t = Type::foreign(
+        ForeignKey::new("users")
+            .column("uid")
+            .on_delete(ReferentialAction::SetNull),
+    );
+    let json = serde_json::to_string(&t).unwrap();
+    assert_eq!(serde_json::from_str::<Type>(&json).unwrap(), t);
+}
+
+#[test]
+fn interval_qualifiers() {
+    let t = Type::Interval(Some("DAY TO SECOND".into()));
+    let json = serde_json::to_string(&t).unwrap();
+    assert_eq!(serde_json::from_str::<Type>(&json).unwrap(), t);
+}
@@ -0,0 +1,74 @@
+//! Render Diesel schemas from migrations
+
+use crate::integrations::diesel_schema::generate;
+use crate::{Error, Migration, Table, Type};
+
+#[test]
+fn single_table() {
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Varchar(255)).nullable();
+        t.add_column("bio", Type::Text);
+        t.add_column("tags", Type::Array(Box::new(Type::Text)))
+            .nullable();
+    });
+
+    assert_eq!(
+        generate(&[m]).unwrap(),
+        "// This file is auto generated by barrel\n\n\
+         table! {\n    users (id) {\n        id -> Integer,\n        name -> Varchar,\n        \
+         bio -> Nullable<Text>,\n        tags -> Array<Text>,\n    }\n}\n"
+    );
+}
+
+#[test]
+fn replays_migrations() {
+    let mut m1 = Migration::new();
+    m1.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Text).nullable();
+        t.add_column("age", Type::Integer);
+    });
+    m1.create_table("posts", |t: &mut Table| {
+        t.add_column("author", Type::Foreign("users".into()))
+            .nullable();
+    });
+
+    let mut m2 = Migration::new();
+    m2.change_table("users", |t: &mut Table| {
+        t.rename_column("name", "nickname");
+        t.drop_column("age");
+    });
+    m2.create_table("tmp", |_| {});
+    m2.drop_table("tmp");
+
+    assert_eq!(
+        generate(&[m1, m2]).unwrap(),
+        "// This file is auto generated by barrel\n\n\
+         table! {\n    users (id) {\n        id -> Integer,\n        nickname -> Text,\n    }\n}\n\n\
+         table! {\n    posts (id) {\n        id -> Integer,\n        author -> Integer,\n    }\n}\n\n\
+         joinable!(posts -> users (author));\n\n\
+         allow_tables_to_appear_in_same_query!(\n    users,\n    posts,\n);\n"
+    );
+}
+
+#[test]
+fn custom_type() {
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("token", Type::Custom("UUID".into()));
+    });
+
+    assert_eq!(
+        generate(&[m]),
+        Err(Error::Unsupported("diesel", "Custom type `UUID`".into()))
+    );
+}
+
+#[test]
+fn migration_version() {
+    use crate::integrations::diesel_migrations::version;
+
+    assert_eq!(version(0), "1970-01-01-000000");
+    assert_eq!(version(951_782_400), "2000-02-29-000000");
+    assert_eq!(version(1_548_956_723), "2019-01-31-174523");
+}
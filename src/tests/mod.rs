@@ -3,8 +3,25 @@
 // We can always trust these tests 👍
 mod common;
 
+mod build;
+
 #[cfg(feature = "pg")]
 mod pg;
 
+#[cfg(feature = "ansi")]
+mod ansi;
+
+#[cfg(feature = "cockroach")]
+mod cockroach;
+
+#[cfg(feature = "mssql")]
+mod mssql;
+
+#[cfg(feature = "mysql")]
+mod mysql;
+
 #[cfg(feature = "sqlite3")]
 mod sqlite3;
+
+#[cfg(feature = "diesel")]
+mod diesel;
@@ -0,0 +1,223 @@
+//! All add_column combinations for mssql
+#![allow(unused_imports)]
+
+use crate::backend::{MsSql, SqlGenerator};
+use crate::Column;
+use crate::Type::*;
+
+#[test]
+fn text() {
+    let sql = MsSql::default().add_column(true, "Text", &Column::new(Text));
+    assert_eq!(String::from("ADD [Text] NVARCHAR(MAX)"), sql);
+}
+
+#[test]
+fn varchar() {
+    let sql = MsSql::default().add_column(true, "Varchar", &Column::new(Varchar(255)));
+    assert_eq!(String::from("ADD [Varchar] NVARCHAR(255)"), sql);
+}
+
+#[test]
+fn integer() {
+    let sql = MsSql::default().add_column(true, "Integer", &Column::new(Integer));
+    assert_eq!(String::from("ADD [Integer] INT"), sql);
+}
+
+#[test]
+fn float() {
+    let sql = MsSql::default().add_column(true, "Float", &Column::new(Float));
+    assert_eq!(String::from("ADD [Float] REAL"), sql);
+}
+
+#[test]
+fn double() {
+    let sql = MsSql::default().add_column(true, "Double", &Column::new(Double));
+    assert_eq!(String::from("ADD [Double] FLOAT"), sql);
+}
+
+#[test]
+fn boolean() {
+    let sql = MsSql::default().add_column(true, "Boolean", &Column::new(Boolean));
+    assert_eq!(String::from("ADD [Boolean] BIT"), sql);
+}
+
+#[test]
+fn binary() {
+    let sql = MsSql::default().add_column(true, "Binary", &Column::new(Binary));
+    assert_eq!(String::from("ADD [Binary] VARBINARY(MAX)"), sql);
+}
+
+#[test]
+fn foreign() {
+    let sql = MsSql::default().add_column(true, "Foreign", &Column::new(Foreign("posts".into())));
+    assert_eq!(
+        String::from("ADD [Foreign] INT REFERENCES [posts]([id])"),
+        sql
+    );
+}
+
+#[test]
+fn custom() {
+    let sql =
+        MsSql::default().add_column(true, "Location", &Column::new(Custom("GEOGRAPHY".into())));
+    assert_eq!(String::from("ADD [Location] GEOGRAPHY"), sql);
+}
+
+#[test]
+#[should_panic]
+fn array_text() {
+    MsSql::default().add_column(true, "Array of Text", &Column::new(Array(Box::new(Text))));
+}
+
+#[test]
+fn uuid() {
+    let mut col = Column::new(Uuid);
+    col.default_expr("NEWID()");
+    let sql = MsSql::default().add_column(true, "Uuid", &col);
+    assert_eq!(
+        String::from("ADD [Uuid] UNIQUEIDENTIFIER DEFAULT NEWID()"),
+        sql
+    );
+}
+
+#[test]
+fn json() {
+    let sql = MsSql::default().add_column(true, "Json", &Column::new(Json));
+    assert_eq!(String::from("ADD [Json] NVARCHAR(MAX)"), sql);
+}
+
+#[test]
+fn datetime_precision() {
+    let sql = MsSql::default().add_column(true, "DateTime", &Column::new(DateTime(Some(7))));
+    assert_eq!(String::from("ADD [DateTime] DATETIME2(7)"), sql);
+}
+
+#[test]
+fn timestamptz() {
+    let sql = MsSql::default().add_column(true, "TimestampTz", &Column::new(TimestampTz(None)));
+    assert_eq!(String::from("ADD [TimestampTz] DATETIMEOFFSET"), sql);
+}
+
+#[test]
+fn decimal() {
+    let sql = MsSql::default().add_column(true, "Decimal", &Column::new(Decimal(10, 0)));
+    assert_eq!(String::from("ADD [Decimal] DECIMAL(10, 0)"), sql);
+}
+
+#[test]
+fn bigint() {
+    let sql = MsSql::default().add_column(true, "BigInt", &Column::new(BigInt));
+    assert_eq!(String::from("ADD [BigInt] BIGINT"), sql);
+}
+
+#[test]
+fn bigserial() {
+    let sql = MsSql::default().add_column(true, "BigSerial", &Column::new(BigSerial));
+    assert_eq!(String::from("ADD [BigSerial] BIGINT IDENTITY(1,1)"), sql);
+}
+
+#[test]
+fn char() {
+    let sql = MsSql::default().add_column(true, "Char", &Column::new(Char(3)));
+    assert_eq!(String::from("ADD [Char] NCHAR(3)"), sql);
+}
+
+#[test]
+fn enumeration() {
+    let t = crate::Type::enumeration("mood", &["happy", "sad"]);
+    let sql = MsSql::default().add_column(true, "Enum", &Column::new(t));
+    assert_eq!(
+        String::from("ADD [Enum] NVARCHAR(5) CHECK ([Enum] IN (N'happy', N'sad'))"),
+        sql
+    );
+}
+
+#[test]
+fn cidr() {
+    let sql = MsSql::default().add_column(true, "Cidr", &Column::new(Cidr));
+    assert_eq!(String::from("ADD [Cidr] NVARCHAR(43)"), sql);
+}
+
+#[test]
+fn xml() {
+    let sql = MsSql::default().add_column(true, "Xml", &Column::new(Xml));
+    assert_eq!(String::from("ADD [Xml] XML"), sql);
+}
+
+#[test]
+fn citext() {
+    let sql = MsSql::default().add_column(true, "Citext", &Column::new(Citext));
+    assert_eq!(
+        String::from("ADD [Citext] NVARCHAR(MAX) COLLATE Latin1_General_CI_AS"),
+        sql
+    );
+}
+
+#[test]
+fn bit() {
+    let sql = MsSql::default().add_column(true, "Bit", &Column::new(Bit(12)));
+    assert_eq!(String::from("ADD [Bit] BINARY(2)"), sql);
+}
+
+#[test]
+fn tinytext() {
+    let sql = MsSql::default().add_column(true, "TinyText", &Column::new(TinyText));
+    assert_eq!(String::from("ADD [TinyText] NVARCHAR(255)"), sql);
+}
+
+#[test]
+fn generated() {
+    let mut col = Column::new(Integer);
+    col.generated("price * quantity", crate::Generated::Stored);
+    let sql = MsSql::default().add_column(true, "total", &col);
+    assert_eq!(
+        String::from("ADD [total] AS (price * quantity) PERSISTED"),
+        sql
+    );
+}
+
+#[test]
+fn identity() {
+    let mut col = Column::new(Primary);
+    col.identity(crate::Identity::always().start(100));
+    let sql = MsSql::default().add_column(true, "id", &col);
+    assert_eq!(
+        String::from("ADD [id] INT IDENTITY(100,1) PRIMARY KEY"),
+        sql
+    );
+}
+
+#[test]
+fn check() {
+    let mut col = Column::new(Integer);
+    col.check("age >= 0");
+    let sql = MsSql::default().add_column(true, "age", &col);
+    assert_eq!(String::from("ADD [age] INT CHECK (age >= 0)"), sql);
+}
+
+#[test]
+fn foreign_restrict() {
+    use crate::{Error, ForeignKey, ReferentialAction, TableChange};
+
+    let fk = ForeignKey::new("posts").on_update(ReferentialAction::Restrict);
+    let change = TableChange::AddColumn("post".into(), Column::new(Foreign(fk)));
+    assert_eq!(
+        MsSql::default().validate_table_change(&change),
+        Err(Error::Unsupported(
+            "MSSQL",
+            "RESTRICT referential actions".into()
+        ))
+    );
+}
+
+#[test]
+fn foreign_named() {
+    use crate::ForeignKey;
+
+    let fk = ForeignKey::new("posts").constraint_name("fk_post");
+    let sql = MsSql::default().add_column(true, "post", &Column::new(Foreign(fk)));
+    assert_eq!(
+        String::from("ADD [post] INT CONSTRAINT [fk_post] REFERENCES [posts]([id])"),
+        sql
+    );
+}
@@ -0,0 +1,269 @@
+//! Some unit tests that create create tables
+#![allow(unused_imports)]
+
+use crate::backend::{MsSql, SqlGenerator};
+use crate::{Migration, Table};
+
+#[test]
+fn simple_table() {
+    let mut m = Migration::new();
+    m.create_table("users", |_: &mut Table| {});
+    assert_eq!(
+        m.make::<MsSql>(),
+        String::from("CREATE TABLE [users] ([id] INT IDENTITY(1,1) PRIMARY KEY)")
+    );
+}
+
+#[test]
+fn basic_fields() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Varchar(255));
+        t.add_column("age", Integer);
+        t.add_column("plushy_sharks_owned", Boolean);
+    });
+
+    assert_eq!(
+        m.make::<MsSql>(),
+        String::from("CREATE TABLE [users] ([id] INT IDENTITY(1,1) PRIMARY KEY, [name] NVARCHAR(255), [age] INT, [plushy_sharks_owned] BIT)")
+    );
+}
+
+#[test]
+fn create_table_if_not_exists() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table_if_not_exists("users", |t: &mut Table| {
+        t.add_column("name", Text);
+    });
+
+    assert_eq!(
+        m.make::<MsSql>(),
+        String::from("IF OBJECT_ID(N'users', N'U') IS NULL CREATE TABLE [users] ([id] INT IDENTITY(1,1) PRIMARY KEY, [name] NVARCHAR(MAX))")
+    );
+}
+
+#[test]
+fn add_columns() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_column("age", Integer);
+        t.drop_column("name");
+    });
+
+    assert_eq!(
+        m.make::<MsSql>(),
+        String::from("ALTER TABLE [users] ADD [age] INT; ALTER TABLE [users] DROP COLUMN [name]")
+    );
+}
+
+#[test]
+fn idempotent_columns() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.set_idempotent(true);
+    m.change_table("users", |t: &mut Table| {
+        t.add_column("age", Integer);
+        t.drop_column("name");
+    });
+
+    assert_eq!(
+        m.make::<MsSql>(),
+        String::from(
+            "ALTER TABLE [users] ADD [age] INT; ALTER TABLE [users] DROP COLUMN IF EXISTS [name]"
+        )
+    );
+}
+
+#[test]
+fn transactional() {
+    let mut m = Migration::new();
+    m.drop_table("users");
+
+    assert_eq!(
+        m.make_transactional::<MsSql>(),
+        String::from("BEGIN TRANSACTION; DROP TABLE [users]; COMMIT TRANSACTION")
+    );
+}
+
+#[test]
+fn auto_increment_start() {
+    let mut m = Migration::new();
+    m.create_table("users", |_: &mut Table| {})
+        .auto_increment_start(1000);
+
+    assert_eq!(
+        m.make::<MsSql>(),
+        String::from("CREATE TABLE [users] ([id] INT IDENTITY(1,1) PRIMARY KEY); DBCC CHECKIDENT ('users', RESEED, 1000)")
+    );
+
+    /* An identity with a start of its own isn't reseeded */
+    let mut m = Migration::new();
+    m.create_table("events", |t: &mut Table| {
+        t.add_column("id", crate::Type::Primary)
+            .identity(crate::Identity::always().start(5));
+    })
+    .without_id()
+    .auto_increment_start(1000);
+
+    assert_eq!(
+        m.make::<MsSql>(),
+        String::from("CREATE TABLE [events] ([id] INT IDENTITY(5,1) PRIMARY KEY)")
+    );
+}
+
+#[test]
+fn unique_constraint() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("tenant_id", Integer);
+        t.add_column("email", Varchar(255));
+        t.add_unique("uq_user_email_tenant", &["tenant_id", "email"]);
+    })
+    .without_id();
+
+    assert_eq!(
+        m.make::<MsSql>(),
+        String::from("CREATE TABLE [users] ([tenant_id] INT, [email] NVARCHAR(255), CONSTRAINT [uq_user_email_tenant] UNIQUE ([tenant_id], [email]))")
+    );
+}
+
+#[test]
+fn multi_column_index() {
+    let mut m = Migration::new();
+    m.change_table("people", |t: &mut Table| {
+        t.add_index("idx_people_name", &["last_name", "first_name"]);
+    });
+
+    assert_eq!(
+        m.make::<MsSql>(),
+        String::from("CREATE INDEX [idx_people_name] ON [people] ([last_name], [first_name])")
+    );
+}
+
+#[test]
+fn expression_index() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_index_expr("idx_lower_email", "lower(email)");
+    });
+
+    assert_eq!(
+        m.try_make::<MsSql>(),
+        Err(Error::Unsupported("MSSQL", "Expression indexes".into()))
+    );
+}
+
+#[test]
+fn covering_index() {
+    let mut m = Migration::new();
+    m.change_table("orders", |t: &mut Table| {
+        t.add_index("idx_orders_customer", &["customer_id"])
+            .include(&["total"]);
+    });
+
+    assert_eq!(
+        m.make::<MsSql>(),
+        String::from(
+            "CREATE INDEX [idx_orders_customer] ON [orders] ([customer_id]) INCLUDE ([total])"
+        )
+    );
+}
+
+#[test]
+fn temporary_table() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.create_table("scratch", |_: &mut Table| {})
+        .temporary(true);
+
+    assert_eq!(
+        m.try_make::<MsSql>(),
+        Err(Error::Unsupported("MSSQL", "Temporary tables".into()))
+    );
+}
+
+#[test]
+fn create_table_like() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.create_table_like("audit_users", "users");
+
+    assert_eq!(
+        m.try_make::<MsSql>(),
+        Err(Error::Unsupported("MSSQL", "CREATE TABLE LIKE".into()))
+    );
+}
+
+#[test]
+fn create_table_from_query() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.create_table_from_query("daily_totals", "SELECT day FROM orders GROUP BY day");
+
+    assert_eq!(
+        m.try_make::<MsSql>(),
+        Err(Error::Unsupported("MSSQL", "CREATE TABLE AS".into()))
+    );
+}
+
+#[test]
+fn set_and_drop_default() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.change_table("users", |t| t.set_default("status", "'active'"));
+    assert_eq!(
+        m.make::<MsSql>(),
+        String::from("ALTER TABLE [users] ADD DEFAULT 'active' FOR [status]")
+    );
+
+    let mut m = Migration::new();
+    m.change_table("users", |t| t.drop_default("status"));
+    assert_eq!(
+        m.try_make::<MsSql>(),
+        Err(Error::Unsupported("MSSQL", "Dropping a default".into()))
+    );
+}
+
+#[test]
+fn drop_constraint() {
+    let mut m = Migration::new();
+    m.change_table("orders", |t| t.drop_constraint("fk_orders_user"));
+    assert_eq!(
+        m.make::<MsSql>(),
+        String::from("ALTER TABLE [orders] DROP CONSTRAINT [fk_orders_user]")
+    );
+}
+
+#[test]
+fn set_not_null() {
+    use crate::Type;
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("email", Type::Varchar(255));
+    });
+    m.change_table("users", |t| t.set_not_null("email"));
+    assert_eq!(
+        m.make::<MsSql>(),
+        String::from(
+            "CREATE TABLE [users] ([id] INT IDENTITY(1,1) PRIMARY KEY, [email] NVARCHAR(255)); \
+             ALTER TABLE [users] ALTER COLUMN [email] NVARCHAR(255) NOT NULL"
+        )
+    );
+}
+
+#[test]
+fn rename_column() {
+    let mut m = Migration::new();
+    m.change_table("users", |t| t.rename_column("name", "nickname"));
+    assert_eq!(
+        m.try_make::<MsSql>(),
+        Ok(String::from(
+            "EXEC sp_rename N'users.name', N'nickname', N'COLUMN'"
+        ))
+    );
+}
@@ -0,0 +1,5 @@
+//! Test mssql generation
+
+mod add_column;
+mod create_table;
+mod simple;
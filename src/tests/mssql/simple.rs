@@ -0,0 +1,96 @@
+//! Other simple table/ column migrations
+#![allow(unused_imports)]
+
+use crate::backend::{MsSql, SqlGenerator};
+
+#[test]
+fn create_table() {
+    let sql = MsSql::default().create_table("table_to_create");
+    assert_eq!(String::from("CREATE TABLE [table_to_create]"), sql);
+}
+
+#[test]
+fn create_table_if_not_exists() {
+    let sql = MsSql::default().create_table_if_not_exists("table_to_create");
+    assert_eq!(
+        String::from(
+            "IF OBJECT_ID(N'table_to_create', N'U') IS NULL CREATE TABLE [table_to_create]"
+        ),
+        sql
+    );
+}
+
+#[test]
+fn drop_table() {
+    let sql = MsSql::default().drop_table("table_to_drop");
+    assert_eq!(String::from("DROP TABLE [table_to_drop]"), sql);
+}
+
+#[test]
+fn drop_table_if_exists() {
+    let sql = MsSql::default().drop_table_if_exists("table_to_drop");
+    assert_eq!(
+        String::from("IF OBJECT_ID(N'table_to_drop', N'U') IS NOT NULL DROP TABLE [table_to_drop]"),
+        sql
+    );
+}
+
+#[test]
+fn rename_table() {
+    let sql = MsSql::default().rename_table("old_table", "new_table");
+    assert_eq!(
+        String::from("EXEC sp_rename N'old_table', N'new_table'"),
+        sql
+    );
+}
+
+#[test]
+fn alter_table() {
+    let sql = MsSql::default().alter_table("table_to_alter");
+    assert_eq!(String::from("ALTER TABLE [table_to_alter]"), sql);
+}
+
+#[test]
+fn drop_column() {
+    let sql = MsSql::default().drop_column("column_to_drop");
+    assert_eq!(String::from("DROP COLUMN [column_to_drop]"), sql);
+}
+
+#[test]
+fn rename_column() {
+    let sql =
+        MsSql::default()
+            .schema("sales")
+            .rename_table_column("table", "old_column", "new_column");
+    assert_eq!(
+        String::from("EXEC sp_rename N'sales.table.old_column', N'new_column', N'COLUMN'"),
+        sql
+    );
+}
+
+#[test]
+fn drop_table_if_exists_with_schema() {
+    let sql = MsSql::default()
+        .schema("sales")
+        .drop_table_if_exists("table_to_drop");
+    assert_eq!(
+        String::from("IF OBJECT_ID(N'sales.table_to_drop', N'U') IS NOT NULL DROP TABLE [sales].[table_to_drop]"),
+        sql
+    );
+}
+
+#[test]
+fn insert() {
+    use crate::Value;
+    let sql = MsSql::default().insert(
+        "users",
+        &[
+            ("name".into(), Value::from("Zoë")),
+            ("admin".into(), Value::from(true)),
+        ],
+    );
+    assert_eq!(
+        String::from("INSERT INTO [users] ([name], [admin]) VALUES (N'Zoë', 1)"),
+        sql
+    );
+}
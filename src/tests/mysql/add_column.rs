@@ -0,0 +1,417 @@
+//! All add_column combinations for mysql
+#![allow(unused_imports)]
+
+use crate::backend::{MySql, SqlGenerator};
+use crate::Column;
+use crate::Type::*;
+
+#[test]
+fn text() {
+    let sql = MySql::default().add_column(true, "Text", &Column::new(Text));
+    assert_eq!(String::from("ADD COLUMN `Text` TEXT"), sql);
+}
+
+#[test]
+fn varchar() {
+    let sql = MySql::default().add_column(true, "Varchar", &Column::new(Varchar(255)));
+    assert_eq!(String::from("ADD COLUMN `Varchar` VARCHAR(255)"), sql);
+}
+
+#[test]
+fn unbounded_varchar() {
+    let sql = MySql::default().add_column(true, "Varchar", &Column::new(Varchar(0)));
+    assert_eq!(String::from("ADD COLUMN `Varchar` TEXT"), sql);
+}
+
+#[test]
+fn integer() {
+    let sql = MySql::default().add_column(true, "Integer", &Column::new(Integer));
+    assert_eq!(String::from("ADD COLUMN `Integer` INTEGER"), sql);
+}
+
+#[test]
+fn float() {
+    let sql = MySql::default().add_column(true, "Float", &Column::new(Float));
+    assert_eq!(String::from("ADD COLUMN `Float` FLOAT"), sql);
+}
+
+#[test]
+fn double() {
+    let sql = MySql::default().add_column(true, "Double", &Column::new(Double));
+    assert_eq!(String::from("ADD COLUMN `Double` DOUBLE"), sql);
+}
+
+#[test]
+fn boolean() {
+    let sql = MySql::default().add_column(true, "Boolean", &Column::new(Boolean));
+    assert_eq!(String::from("ADD COLUMN `Boolean` BOOLEAN"), sql);
+}
+
+#[test]
+fn binary() {
+    let sql = MySql::default().add_column(true, "Binary", &Column::new(Binary));
+    assert_eq!(String::from("ADD COLUMN `Binary` BLOB"), sql);
+}
+
+#[test]
+fn foreign() {
+    let sql = MySql::default().add_column(true, "Foreign", &Column::new(Foreign("posts".into())));
+    assert_eq!(
+        String::from(
+            "ADD COLUMN `Foreign` INTEGER, ADD FOREIGN KEY (`Foreign`) REFERENCES `posts`(`id`)"
+        ),
+        sql
+    );
+}
+
+#[test]
+fn custom() {
+    let sql = MySql::default().add_column(true, "Point", &Column::new(Custom("POINT".into())));
+    assert_eq!(String::from("ADD COLUMN `Point` POINT"), sql);
+}
+
+#[test]
+#[should_panic]
+fn array_text() {
+    MySql::default().add_column(true, "Array of Text", &Column::new(Array(Box::new(Text))));
+}
+
+#[test]
+fn uuid() {
+    let sql = MySql::default().add_column(true, "Uuid", &Column::new(Uuid));
+    assert_eq!(String::from("ADD COLUMN `Uuid` CHAR(36)"), sql);
+}
+
+#[test]
+fn jsonb() {
+    let sql = MySql::default().add_column(true, "Jsonb", &Column::new(Jsonb));
+    assert_eq!(String::from("ADD COLUMN `Jsonb` JSON"), sql);
+}
+
+#[test]
+fn time_precision() {
+    let sql = MySql::default().add_column(true, "Time", &Column::new(Time(Some(6))));
+    assert_eq!(String::from("ADD COLUMN `Time` TIME(6)"), sql);
+}
+
+#[test]
+fn datetime() {
+    let sql = MySql::default().add_column(true, "DateTime", &Column::new(DateTime(None)));
+    assert_eq!(String::from("ADD COLUMN `DateTime` DATETIME"), sql);
+}
+
+#[test]
+fn timestamptz() {
+    let sql = MySql::default().add_column(true, "TimestampTz", &Column::new(TimestampTz(None)));
+    assert_eq!(String::from("ADD COLUMN `TimestampTz` TIMESTAMP"), sql);
+}
+
+#[test]
+fn interval() {
+    let sql = MySql::default().add_column(true, "Interval", &Column::new(Interval(None)));
+    assert_eq!(String::from("ADD COLUMN `Interval` BIGINT"), sql);
+}
+
+#[test]
+fn decimal() {
+    let sql = MySql::default().add_column(true, "Decimal", &Column::new(Decimal(19, 4)));
+    assert_eq!(String::from("ADD COLUMN `Decimal` DECIMAL(19, 4)"), sql);
+}
+
+#[test]
+fn money() {
+    let sql = MySql::default().add_column(true, "Money", &Column::new(Money));
+    assert_eq!(String::from("ADD COLUMN `Money` DECIMAL(19, 4)"), sql);
+}
+
+#[test]
+fn unsigned() {
+    let mut col = Column::new(BigInt);
+    col.unsigned();
+    let sql = MySql::default().add_column(true, "Unsigned", &col);
+    assert_eq!(String::from("ADD COLUMN `Unsigned` BIGINT UNSIGNED"), sql);
+}
+
+#[test]
+fn serial() {
+    let sql = MySql::default().add_column(true, "Serial", &Column::new(Serial));
+    assert_eq!(
+        String::from("ADD COLUMN `Serial` INTEGER NOT NULL AUTO_INCREMENT UNIQUE"),
+        sql
+    );
+}
+
+#[test]
+fn varbinary() {
+    let sql = MySql::default().add_column(true, "Varbinary", &Column::new(Varbinary(16)));
+    assert_eq!(String::from("ADD COLUMN `Varbinary` VARBINARY(16)"), sql);
+}
+
+#[test]
+fn char() {
+    let sql = MySql::default().add_column(true, "Char", &Column::new(Char(3)));
+    assert_eq!(String::from("ADD COLUMN `Char` CHAR(3)"), sql);
+}
+
+#[test]
+fn enumeration() {
+    let t = crate::Type::enumeration("mood", &["happy", "it's"]);
+    let sql = MySql::default().add_column(true, "Enum", &Column::new(t));
+    assert_eq!(
+        String::from("ADD COLUMN `Enum` ENUM('happy', 'it''s')"),
+        sql
+    );
+}
+
+#[test]
+fn set() {
+    let t = crate::Type::set(&["read", "write"]);
+    let sql = MySql::default().add_column(true, "Set", &Column::new(t));
+    assert_eq!(String::from("ADD COLUMN `Set` SET('read', 'write')"), sql);
+}
+
+#[test]
+fn inet() {
+    let sql = MySql::default().add_column(true, "Inet", &Column::new(Inet));
+    assert_eq!(String::from("ADD COLUMN `Inet` VARCHAR(43)"), sql);
+}
+
+#[test]
+fn macaddr() {
+    let sql = MySql::default().add_column(true, "MacAddr", &Column::new(MacAddr));
+    assert_eq!(String::from("ADD COLUMN `MacAddr` VARCHAR(17)"), sql);
+}
+
+#[test]
+fn xml() {
+    let sql = MySql::default().add_column(true, "Xml", &Column::new(Xml));
+    assert_eq!(String::from("ADD COLUMN `Xml` TEXT"), sql);
+}
+
+#[test]
+fn hstore() {
+    let sql = MySql::default().add_column(true, "Hstore", &Column::new(Hstore));
+    assert_eq!(String::from("ADD COLUMN `Hstore` JSON"), sql);
+}
+
+#[test]
+fn citext() {
+    let sql = MySql::default().add_column(true, "Citext", &Column::new(Citext));
+    assert_eq!(
+        String::from("ADD COLUMN `Citext` VARCHAR(255) COLLATE utf8mb4_unicode_ci"),
+        sql
+    );
+}
+
+#[test]
+fn tsvector() {
+    use crate::{Error, TableChange};
+
+    let change = TableChange::AddColumn("TsVector".into(), Column::new(TsVector));
+    assert_eq!(
+        MySql::default().validate_table_change(&change),
+        Err(Error::Unsupported("MySQL", "Full-text search types".into()))
+    );
+}
+
+#[test]
+fn range() {
+    use crate::{Error, RangeKind, TableChange};
+
+    let t = Range(RangeKind::Date);
+    let change = TableChange::AddColumn("Range".into(), Column::new(t));
+    assert_eq!(
+        MySql::default().validate_table_change(&change),
+        Err(Error::Unsupported("MySQL", "Range types".into()))
+    );
+}
+
+#[test]
+fn point() {
+    let sql = MySql::default().add_column(true, "Point", &Column::new(Point));
+    assert_eq!(String::from("ADD COLUMN `Point` POINT"), sql);
+}
+
+#[test]
+#[cfg(feature = "postgis")]
+fn geometry() {
+    use crate::GeometryKind;
+
+    let t = Geometry(GeometryKind::Polygon, Some(4326));
+    let sql = MySql::default().add_column(true, "Geometry", &Column::new(t));
+    assert_eq!(String::from("ADD COLUMN `Geometry` POLYGON SRID 4326"), sql);
+}
+
+#[test]
+fn bit() {
+    let sql = MySql::default().add_column(true, "Bit", &Column::new(Bit(8)));
+    assert_eq!(String::from("ADD COLUMN `Bit` BIT(8)"), sql);
+}
+
+#[test]
+fn varbit() {
+    let sql = MySql::default().add_column(true, "Varbit", &Column::new(Varbit(16)));
+    assert_eq!(String::from("ADD COLUMN `Varbit` BIT(16)"), sql);
+}
+
+#[test]
+fn array() {
+    use crate::{Error, TableChange};
+
+    let t = crate::Type::array(Integer, 1);
+    let change = TableChange::AddColumn("Array".into(), Column::new(t));
+    assert_eq!(
+        MySql::default().validate_table_change(&change),
+        Err(Error::Unsupported("MySQL", "Array types".into()))
+    );
+}
+
+#[test]
+fn custom_per() {
+    use crate::backend::SqlVariant;
+
+    let t = crate::Type::custom_per(&[(SqlVariant::MySql, "VARCHAR(255)")]);
+    let sql = MySql::default().add_column(true, "Path", &Column::new(t));
+    assert_eq!(String::from("ADD COLUMN `Path` VARCHAR(255)"), sql);
+}
+
+#[test]
+fn text_sizes() {
+    let sql = MySql::default().add_column(true, "Tiny", &Column::new(TinyText));
+    assert_eq!(String::from("ADD COLUMN `Tiny` TINYTEXT"), sql);
+
+    let sql = MySql::default().add_column(true, "Medium", &Column::new(MediumText));
+    assert_eq!(String::from("ADD COLUMN `Medium` MEDIUMTEXT"), sql);
+
+    let sql = MySql::default().add_column(true, "Long", &Column::new(LongText));
+    assert_eq!(String::from("ADD COLUMN `Long` LONGTEXT"), sql);
+}
+
+#[test]
+fn generated() {
+    let mut col = Column::new(Integer);
+    col.generated("price * quantity", crate::Generated::Virtual);
+    let sql = MySql::default().add_column(true, "total", &col);
+    assert_eq!(
+        String::from("ADD COLUMN `total` INTEGER GENERATED ALWAYS AS (price * quantity) VIRTUAL"),
+        sql
+    );
+}
+
+#[test]
+fn identity() {
+    use crate::{Error, Identity, TableChange};
+
+    let mut col = Column::new(Integer);
+    col.identity(Identity::always());
+    let change = TableChange::AddColumn("id".into(), col);
+    assert_eq!(
+        MySql::default().validate_table_change(&change),
+        Err(Error::Unsupported("MySQL", "Identity columns".into()))
+    );
+}
+
+#[test]
+fn check() {
+    let mut col = Column::new(Integer);
+    col.check_named("age_positive", "age >= 0");
+    let sql = MySql::default().add_column(true, "age", &col);
+    assert_eq!(
+        String::from("ADD COLUMN `age` INTEGER CONSTRAINT `age_positive` CHECK (age >= 0)"),
+        sql
+    );
+}
+
+#[test]
+fn charset_collate() {
+    let mut col = Column::new(Varchar(255));
+    col.charset("utf8mb4").collate("utf8mb4_unicode_ci");
+    let sql = MySql::default().add_column(true, "name", &col);
+    assert_eq!(
+        String::from(
+            "ADD COLUMN `name` VARCHAR(255) CHARACTER SET utf8mb4 COLLATE utf8mb4_unicode_ci"
+        ),
+        sql
+    );
+}
+
+#[test]
+fn on_update_current_timestamp() {
+    let mut col = Column::new(DateTime(None));
+    col.default_expr("CURRENT_TIMESTAMP")
+        .on_update_current_timestamp();
+    let sql = MySql::default().add_column(true, "updated_at", &col);
+    assert_eq!(
+        String::from(
+            "ADD COLUMN `updated_at` DATETIME DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP"
+        ),
+        sql
+    );
+}
+
+#[test]
+fn foreign_actions() {
+    use crate::{ForeignKey, ReferentialAction};
+
+    let fk = ForeignKey::new("posts").on_delete(ReferentialAction::SetNull);
+    let sql = MySql::default().add_column(true, "post", &Column::new(Foreign(fk)));
+    assert_eq!(
+        String::from(
+            "ADD COLUMN `post` INTEGER, ADD FOREIGN KEY (`post`) REFERENCES `posts`(`id`) ON DELETE SET NULL"
+        ),
+        sql
+    );
+}
+
+#[test]
+fn foreign_deferrable() {
+    use crate::{Error, ForeignKey, TableChange};
+
+    let fk = ForeignKey::new("users").deferrable();
+    let change = TableChange::AddColumn("author".into(), Column::new(Foreign(fk)));
+    assert_eq!(
+        MySql::default().validate_table_change(&change),
+        Err(Error::Unsupported("MySQL", "Deferrable constraints".into()))
+    );
+}
+
+#[test]
+fn foreign_named() {
+    use crate::ForeignKey;
+
+    let fk = ForeignKey::new("posts").constraint_name("fk_post");
+    let sql = MySql::default().add_column(true, "post", &Column::new(Foreign(fk)));
+    assert_eq!(
+        String::from(
+            "ADD COLUMN `post` INTEGER, ADD CONSTRAINT `fk_post` FOREIGN KEY (`post`) REFERENCES `posts`(`id`)"
+        ),
+        sql
+    );
+}
+
+#[test]
+fn primary_varchar() {
+    let mut col = Column::new(Varchar(64));
+    col.primary();
+    let sql = MySql::default().add_column(false, "slug", &col);
+    assert_eq!(String::from("`slug` VARCHAR(64) PRIMARY KEY"), sql);
+}
+
+#[test]
+fn datetime_precision() {
+    let sql = MySql::default().add_column(true, "at", &Column::new(DateTime(Some(3))));
+    assert_eq!(String::from("ADD COLUMN `at` DATETIME(3)"), sql);
+}
+
+#[test]
+fn storage() {
+    use crate::{Error, Storage, TableChange};
+
+    let mut col = Column::new(Text);
+    col.storage(Storage::Main);
+    let change = TableChange::AddColumn("body".into(), col);
+    assert_eq!(
+        MySql::default().validate_table_change(&change),
+        Err(Error::Unsupported("MySQL", "Column storage options".into()))
+    );
+}
@@ -0,0 +1,514 @@
+//! Some unit tests that create create tables
+#![allow(unused_imports)]
+
+use crate::backend::{MySql, SqlGenerator};
+use crate::{Migration, Table};
+
+#[test]
+fn simple_table() {
+    let mut m = Migration::new();
+    m.create_table("users", |_: &mut Table| {});
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("CREATE TABLE `users` (`id` INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY)")
+    );
+}
+
+#[test]
+fn basic_fields() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Varchar(255));
+        t.add_column("age", Integer);
+        t.add_column("plushy_sharks_owned", Boolean);
+    });
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("CREATE TABLE `users` (`id` INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY, `name` VARCHAR(255), `age` INTEGER, `plushy_sharks_owned` BOOLEAN)")
+    );
+}
+
+#[test]
+fn simple_foreign_fields() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("posts", Foreign("posts".into()));
+    });
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("CREATE TABLE `users` (`id` INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY, `posts` INTEGER, FOREIGN KEY (`posts`) REFERENCES `posts`(`id`))")
+    );
+}
+
+#[test]
+fn rename_table() {
+    let mut m = Migration::new();
+    m.rename_table("users", "cool_users");
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("RENAME TABLE `users` TO `cool_users`")
+    );
+}
+
+#[test]
+fn auto_increment_start() {
+    let mut m = Migration::new();
+    m.create_table("users", |_: &mut Table| {})
+        .auto_increment_start(1000);
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("CREATE TABLE `users` (`id` INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY) AUTO_INCREMENT = 1000")
+    );
+}
+
+#[test]
+fn composite_primary_key() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("memberships", |t: &mut Table| {
+        t.add_column("tenant_id", Integer);
+        t.add_column("id", Integer);
+        t.set_primary_key(&["tenant_id", "id"]);
+    });
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("CREATE TABLE `memberships` (`tenant_id` INTEGER, `id` INTEGER, PRIMARY KEY (`tenant_id`, `id`))")
+    );
+}
+
+#[test]
+fn multi_column_index() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("people", |t: &mut Table| {
+        t.add_column("first_name", Varchar(255));
+        t.add_column("last_name", Varchar(255));
+        t.add_index("idx_people_name", &["last_name", "first_name"]);
+    })
+    .without_id();
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("CREATE TABLE `people` (`first_name` VARCHAR(255), `last_name` VARCHAR(255)); CREATE INDEX `idx_people_name` ON `people` (`last_name`, `first_name`)")
+    );
+}
+
+#[test]
+fn partial_index() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_index("idx_users_email", &["email"])
+            .where_clause("deleted_at IS NULL");
+    });
+
+    assert_eq!(
+        m.try_make::<MySql>(),
+        Err(Error::Unsupported("MySQL", "Partial indexes".into()))
+    );
+}
+
+#[test]
+fn expression_index() {
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_index_expr("idx_lower_email", "lower(email)");
+    });
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("CREATE INDEX `idx_lower_email` ON `users` ((lower(email)))")
+    );
+}
+
+#[test]
+fn fulltext_and_spatial_indexes() {
+    use crate::IndexKind;
+    let mut m = Migration::new();
+    m.change_table("places", |t: &mut Table| {
+        t.add_index("ft_places_description", &["name", "description"])
+            .kind(IndexKind::Fulltext);
+        t.add_index("sp_places_location", &["location"])
+            .kind(IndexKind::Spatial);
+    });
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("CREATE FULLTEXT INDEX `ft_places_description` ON `places` (`name`, `description`); CREATE SPATIAL INDEX `sp_places_location` ON `places` (`location`)")
+    );
+}
+
+#[test]
+fn check_constraint() {
+    let mut m = Migration::new();
+    m.change_table("events", |t: &mut Table| {
+        t.add_check("chk_dates", "starts_at < ends_at");
+    });
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("ALTER TABLE `events` ADD CONSTRAINT `chk_dates` CHECK (starts_at < ends_at)")
+    );
+}
+
+#[test]
+fn exclusion_constraint() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.change_table("bookings", |t: &mut Table| {
+        t.add_exclusion("no_overlap", "gist", &[("room", "="), ("during", "&&")]);
+    });
+
+    assert_eq!(
+        m.try_make::<MySql>(),
+        Err(Error::Unsupported("MySQL", "Exclusion constraints".into()))
+    );
+}
+
+#[test]
+fn table_comment() {
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.set_comment("People who can log in");
+    });
+    m.change_table("posts", |t: &mut Table| {
+        t.set_comment("Everything written");
+    });
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("CREATE TABLE `users` (`id` INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY) COMMENT = 'People who can log in'; ALTER TABLE `posts` COMMENT = 'Everything written'")
+    );
+}
+
+#[test]
+fn table_options() {
+    let mut m = Migration::new();
+    m.create_table("users", |_: &mut Table| {})
+        .engine("InnoDB")
+        .charset("utf8mb4")
+        .collate("utf8mb4_unicode_ci")
+        .row_format("DYNAMIC");
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("CREATE TABLE `users` (`id` INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY) ENGINE = InnoDB DEFAULT CHARSET = utf8mb4 COLLATE = utf8mb4_unicode_ci ROW_FORMAT = DYNAMIC")
+    );
+}
+
+#[test]
+fn temporary_table() {
+    let mut m = Migration::new();
+    m.create_table("scratch", |_: &mut Table| {})
+        .temporary(true);
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from(
+            "CREATE TEMPORARY TABLE `scratch` (`id` INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY)"
+        )
+    );
+}
+
+#[test]
+fn unlogged_table() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.create_table("jobs", |_: &mut Table| {}).unlogged(true);
+
+    assert_eq!(
+        m.try_make::<MySql>(),
+        Err(Error::Unsupported("MySQL", "Unlogged tables".into()))
+    );
+}
+
+#[test]
+fn inherits() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.create_table("capitals", |t: &mut Table| {
+        t.inherits("cities");
+    });
+
+    assert_eq!(
+        m.try_make::<MySql>(),
+        Err(Error::Unsupported("MySQL", "Table inheritance".into()))
+    );
+}
+
+#[test]
+fn partitioned_table() {
+    use crate::PartitionKind;
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("events", |t: &mut Table| {
+        t.add_column("year", Integer);
+        t.partition_by(PartitionKind::Range, &["year"]);
+        t.add_partition("p2023", "VALUES LESS THAN (2024)");
+        t.add_partition("pmax", "VALUES LESS THAN MAXVALUE");
+    })
+    .without_id()
+    .engine("InnoDB");
+    m.create_table("sessions", |t: &mut Table| {
+        t.add_column("user_id", Integer);
+        t.partition_by(PartitionKind::Hash, &["user_id"]);
+        t.partition_count(4);
+    })
+    .without_id();
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("CREATE TABLE `events` (`year` INTEGER) ENGINE = InnoDB PARTITION BY RANGE (`year`) (PARTITION `p2023` VALUES LESS THAN (2024), PARTITION `pmax` VALUES LESS THAN MAXVALUE); CREATE TABLE `sessions` (`user_id` INTEGER) PARTITION BY HASH (`user_id`) PARTITIONS 4")
+    );
+}
+
+#[test]
+fn create_table_like() {
+    let mut m = Migration::new();
+    m.set_idempotent(true);
+    m.create_table_like("audit_users", "users");
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("CREATE TABLE IF NOT EXISTS `audit_users` LIKE `users`")
+    );
+}
+
+#[test]
+fn create_table_from_query() {
+    let mut m = Migration::new();
+    m.set_idempotent(true);
+    m.create_table_from_query(
+        "daily_totals",
+        "SELECT day, SUM(amount) FROM orders GROUP BY day",
+    );
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from(
+            "CREATE TABLE IF NOT EXISTS `daily_totals` AS SELECT day, SUM(amount) FROM orders GROUP BY day"
+        )
+    );
+}
+
+#[test]
+fn tablespace() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.create_table("events", |_| {}).tablespace("fast_ssd");
+
+    assert_eq!(
+        m.try_make::<MySql>(),
+        Err(Error::Unsupported("MySQL", "Tablespaces".into()))
+    );
+}
+
+#[test]
+fn storage_parameters() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.create_table("counters", |_| {})
+        .with_options(&[("fillfactor", "70")]);
+
+    assert_eq!(
+        m.try_make::<MySql>(),
+        Err(Error::Unsupported("MySQL", "Storage parameters".into()))
+    );
+}
+
+#[test]
+fn row_level_security() {
+    use crate::{Error, Policy};
+    let mut m = Migration::new();
+    m.create_policy("tenant_isolation", "users", Policy::new().using("true"));
+
+    assert_eq!(
+        m.try_make::<MySql>(),
+        Err(Error::Unsupported(
+            "MySQL",
+            "Row-level security policies".into()
+        ))
+    );
+}
+
+#[test]
+fn composite_foreign_key() {
+    let mut m = Migration::new();
+    m.change_table("shipments", |t| {
+        t.add_foreign_key(
+            &["order_id", "line_no"],
+            "order_lines",
+            &["order_id", "line_no"],
+        );
+    });
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("ALTER TABLE `shipments` ADD FOREIGN KEY (`order_id`, `line_no`) REFERENCES `order_lines` (`order_id`, `line_no`)")
+    );
+}
+
+#[test]
+fn change_column_type() {
+    use crate::{Error, Type};
+    let mut m = Migration::new();
+    m.change_table("users", |t| {
+        t.change_column("age", Type::BigInt);
+        t.change_column("team_id", Type::foreign("teams"));
+    });
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from(
+            "ALTER TABLE `users` MODIFY COLUMN `age` BIGINT; \
+             ALTER TABLE `users` MODIFY COLUMN `team_id` INTEGER"
+        )
+    );
+
+    let mut m = Migration::new();
+    m.change_table("users", |t| {
+        t.change_column("age", Type::BigInt).using("age * 2");
+    });
+    assert_eq!(
+        m.try_make::<MySql>(),
+        Err(Error::Unsupported("MySQL", "USING expressions".into()))
+    );
+}
+
+#[test]
+fn set_and_drop_default() {
+    let mut m = Migration::new();
+    m.change_table("users", |t| {
+        t.set_default("status", "'active'");
+        t.drop_default("plan");
+    });
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("ALTER TABLE `users` ALTER COLUMN `status` SET DEFAULT 'active'; ALTER TABLE `users` ALTER COLUMN `plan` DROP DEFAULT")
+    );
+}
+
+#[test]
+fn set_not_null() {
+    use crate::schema::Schema;
+    use crate::{Error, Type};
+
+    let mut create = Migration::new();
+    create.create_table("users", |t| {
+        t.add_column("email", Type::Varchar(255));
+    });
+
+    let mut m = Migration::new();
+    m.change_table("users", |t| t.set_not_null("email"));
+    assert_eq!(
+        m.try_make::<MySql>(),
+        Err(Error::Unsupported(
+            "MySQL",
+            "Changing the nullability of a column whose definition isn't known".into()
+        ))
+    );
+
+    let m = Schema::from_migrations(&[create]).complete(&m);
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("ALTER TABLE `users` MODIFY COLUMN `email` VARCHAR(255) NOT NULL")
+    );
+    assert_eq!(
+        m.revert::<MySql>(),
+        Ok(String::from(
+            "ALTER TABLE `users` MODIFY COLUMN `email` VARCHAR(255)"
+        ))
+    );
+
+    /* Tables created by the same migration are known, keys stay in place */
+    let mut m = Migration::new();
+    m.create_table("orders", |t| {
+        t.add_column("user_id", Type::foreign("users"));
+    });
+    m.change_table("orders", |t| t.set_not_null("user_id"));
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from(
+            "CREATE TABLE `orders` (`id` INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY, \
+             `user_id` INTEGER, FOREIGN KEY (`user_id`) REFERENCES `users`(`id`)); \
+             ALTER TABLE `orders` MODIFY COLUMN `user_id` INTEGER NOT NULL"
+        )
+    );
+}
+
+#[test]
+fn change_column_then_set_not_null() {
+    use crate::Type;
+    let mut m = Migration::new();
+    m.create_table("counters", |t| {
+        t.add_column("hits", Type::Integer);
+    });
+    m.change_table("counters", |t| {
+        t.change_column("hits", Type::BigInt);
+        t.set_not_null("hits");
+    });
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from(
+            "CREATE TABLE `counters` (`id` INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY, \
+             `hits` INTEGER); \
+             ALTER TABLE `counters` MODIFY COLUMN `hits` BIGINT; \
+             ALTER TABLE `counters` MODIFY COLUMN `hits` BIGINT NOT NULL"
+        )
+    );
+}
+
+#[test]
+fn set_not_null_keeps_default() {
+    use crate::Type;
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("plan", Type::Varchar(10)).default("free");
+    });
+    m.change_table("users", |t| t.set_not_null("plan"));
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from(
+            "CREATE TABLE `users` (`id` INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY, \
+             `plan` VARCHAR(10) DEFAULT 'free'); \
+             ALTER TABLE `users` MODIFY COLUMN `plan` VARCHAR(10) DEFAULT 'free' NOT NULL"
+        )
+    );
+}
+
+#[test]
+fn rename_constraint_and_index() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.change_table("accounts", |t| {
+        t.rename_constraint("users_check", "accounts_check")
+    });
+    assert_eq!(
+        m.try_make::<MySql>(),
+        Err(Error::Unsupported("MySQL", "Renaming a constraint".into()))
+    );
+
+    let mut m = Migration::new();
+    m.rename_index("users_name_idx", "accounts_name_idx");
+    assert_eq!(
+        m.try_make::<MySql>(),
+        Err(Error::Unsupported("MySQL", "Renaming an index".into()))
+    );
+}
+
+#[test]
+fn drop_constraint() {
+    let mut m = Migration::new();
+    m.change_table("orders", |t| t.drop_constraint("fk_orders_user"));
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("ALTER TABLE `orders` DROP CONSTRAINT `fk_orders_user`")
+    );
+}
@@ -0,0 +1,8 @@
+//! Test mysql generation
+
+mod add_column;
+mod create_table;
+mod simple;
+
+#[cfg(feature = "unstable")]
+mod types;
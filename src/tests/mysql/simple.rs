@@ -0,0 +1,97 @@
+//! Other simple table/ column migrations
+#![allow(unused_imports)]
+
+use crate::backend::{MySql, SqlGenerator};
+
+#[test]
+fn create_table() {
+    let sql = MySql::default().create_table("table_to_create");
+    assert_eq!(String::from("CREATE TABLE `table_to_create`"), sql);
+}
+
+#[test]
+fn create_table_if_not_exists() {
+    let sql = MySql::default().create_table_if_not_exists("table_to_create");
+    assert_eq!(
+        String::from("CREATE TABLE IF NOT EXISTS `table_to_create`"),
+        sql
+    );
+}
+
+#[test]
+fn drop_table() {
+    let sql = MySql::default().drop_table("table_to_drop");
+    assert_eq!(String::from("DROP TABLE `table_to_drop`"), sql);
+}
+
+#[test]
+fn drop_table_if_exists() {
+    let sql = MySql::default().drop_table_if_exists("table_to_drop");
+    assert_eq!(String::from("DROP TABLE IF EXISTS `table_to_drop`"), sql);
+}
+
+#[test]
+fn rename_table() {
+    let sql = MySql::default().rename_table("old_table", "new_table");
+    assert_eq!(String::from("RENAME TABLE `old_table` TO `new_table`"), sql);
+}
+
+#[test]
+fn alter_table() {
+    let sql = MySql::default().alter_table("table_to_alter");
+    assert_eq!(String::from("ALTER TABLE `table_to_alter`"), sql);
+}
+
+#[test]
+fn drop_column() {
+    let sql = MySql::default().drop_column("column_to_drop");
+    assert_eq!(String::from("DROP COLUMN `column_to_drop`"), sql);
+}
+
+#[test]
+fn rename_column() {
+    let sql = MySql::default().rename_column("old_column", "new_column");
+    assert_eq!(
+        String::from("RENAME COLUMN `old_column` TO `new_column`"),
+        sql
+    );
+}
+
+#[test]
+fn rename_table_with_database() {
+    let sql = MySql::default()
+        .database("app")
+        .rename_table("old_table", "new_table");
+    assert_eq!(
+        String::from("RENAME TABLE `app`.`old_table` TO `app`.`new_table`"),
+        sql
+    );
+}
+
+#[test]
+fn insert() {
+    use crate::Value;
+    let sql = MySql::default().insert(
+        "users",
+        &[
+            ("path".into(), Value::from("C:\\it's")),
+            ("admin".into(), Value::from(false)),
+        ],
+    );
+    assert_eq!(
+        String::from("INSERT INTO `users` (`path`, `admin`) VALUES ('C:\\\\it''s', FALSE)"),
+        sql
+    );
+    assert_eq!(
+        MySql::default().insert("users", &[]),
+        String::from("INSERT INTO `users` () VALUES ()")
+    );
+}
+
+#[test]
+fn default_statements_quote_names() {
+    assert_eq!(
+        MySql::default().rename_constraint("users_check", "accounts_check"),
+        String::from("RENAME CONSTRAINT `users_check` TO `accounts_check`")
+    );
+}
@@ -0,0 +1,29 @@
+//! Columns built with the `types` module
+#![allow(unused_imports)]
+
+use crate::backend::{MySql, SqlGenerator};
+use crate::types;
+use crate::{Column, Migration, Table};
+
+#[test]
+fn timestamp_fraction_digits() {
+    let col: Column = types::timestamp().fraction_digits(3).into();
+    assert_eq!(
+        MySql::default().add_column(true, "created_at", &col),
+        String::from("ADD COLUMN `created_at` DATETIME(3) NOT NULL")
+    );
+}
+
+#[test]
+fn enumeration() {
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("status", types::enumeration(&["active", "banned"]));
+    });
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("CREATE TABLE `users` (`id` INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY, `status` ENUM('active', 'banned') NOT NULL)")
+    );
+    assert_eq!(m.revert::<MySql>(), Ok(String::from("DROP TABLE `users`")));
+}
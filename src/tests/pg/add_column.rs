@@ -1,55 +1,55 @@
 //! All add_column combinations for pgsql
 #![allow(unused_imports)]
 
-use backend::{Pg, SqlGenerator};
-use Column;
-use Type::*;
+use crate::backend::{Pg, SqlGenerator};
+use crate::Column;
+use crate::Type::*;
 
 #[test]
 fn text() {
-    let sql = Pg::add_column(true, "Text", &Column::new(Text));
+    let sql = Pg::default().add_column(true, "Text", &Column::new(Text));
     assert_eq!(String::from("ADD COLUMN \"Text\" TEXT"), sql);
 }
 
 #[test]
 fn varchar() {
-    let sql = Pg::add_column(true, "Varchar", &Column::new(Varchar(255)));
+    let sql = Pg::default().add_column(true, "Varchar", &Column::new(Varchar(255)));
     assert_eq!(String::from("ADD COLUMN \"Varchar\" VARCHAR(255)"), sql);
 }
 
 #[test]
 fn integer() {
-    let sql = Pg::add_column(true, "Integer", &Column::new(Integer));
+    let sql = Pg::default().add_column(true, "Integer", &Column::new(Integer));
     assert_eq!(String::from("ADD COLUMN \"Integer\" INTEGER"), sql);
 }
 
 #[test]
 fn float() {
-    let sql = Pg::add_column(true, "Float", &Column::new(Float));
+    let sql = Pg::default().add_column(true, "Float", &Column::new(Float));
     assert_eq!(String::from("ADD COLUMN \"Float\" FLOAT"), sql);
 }
 
 #[test]
 fn double() {
-    let sql = Pg::add_column(true, "Double", &Column::new(Double));
+    let sql = Pg::default().add_column(true, "Double", &Column::new(Double));
     assert_eq!(String::from("ADD COLUMN \"Double\" DOUBLE"), sql);
 }
 
 #[test]
 fn boolean() {
-    let sql = Pg::add_column(true, "Boolean", &Column::new(Boolean));
+    let sql = Pg::default().add_column(true, "Boolean", &Column::new(Boolean));
     assert_eq!(String::from("ADD COLUMN \"Boolean\" BOOLEAN"), sql);
 }
 
 #[test]
 fn binary() {
-    let sql = Pg::add_column(true, "Binary", &Column::new(Binary));
-    assert_eq!(String::from("ADD COLUMN \"Binary\" BINARY"), sql);
+    let sql = Pg::default().add_column(true, "Binary", &Column::new(Binary));
+    assert_eq!(String::from("ADD COLUMN \"Binary\" BYTEA"), sql);
 }
 
 #[test]
 fn foreign() {
-    let sql = Pg::add_column(true, "Foreign", &Column::new(Foreign("posts")));
+    let sql = Pg::default().add_column(true, "Foreign", &Column::new(Foreign("posts".into())));
     assert_eq!(
         String::from("ADD COLUMN \"Foreign\" INTEGER REFERENCES posts"),
         sql
@@ -58,19 +58,19 @@ fn foreign() {
 
 #[test]
 fn custom() {
-    let sql = Pg::add_column(true, "Point", &Column::new(Custom("POINT")));
+    let sql = Pg::default().add_column(true, "Point", &Column::new(Custom("POINT".into())));
     assert_eq!(String::from("ADD COLUMN \"Point\" POINT"), sql);
 }
 
 #[test]
 fn array_text() {
-    let sql = Pg::add_column(true, "Array of Text", &Column::new(Array(Box::new(Text))));
+    let sql = Pg::default().add_column(true, "Array of Text", &Column::new(Array(Box::new(Text))));
     assert_eq!(String::from("ADD COLUMN \"Array of Text\" TEXT[]"), sql);
 }
 
 #[test]
 fn array_varchar() {
-    let sql = Pg::add_column(
+    let sql = Pg::default().add_column(
         true,
         "Array of Varchar",
         &Column::new(Array(Box::new(Varchar(255)))),
@@ -83,7 +83,7 @@ fn array_varchar() {
 
 #[test]
 fn array_integer() {
-    let sql = Pg::add_column(
+    let sql = Pg::default().add_column(
         true,
         "Array of Integer",
         &Column::new(Array(Box::new(Integer))),
@@ -96,13 +96,14 @@ fn array_integer() {
 
 #[test]
 fn array_float() {
-    let sql = Pg::add_column(true, "Array of Float", &Column::new(Array(Box::new(Float))));
+    let sql =
+        Pg::default().add_column(true, "Array of Float", &Column::new(Array(Box::new(Float))));
     assert_eq!(String::from("ADD COLUMN \"Array of Float\" FLOAT[]"), sql);
 }
 
 #[test]
 fn array_double() {
-    let sql = Pg::add_column(
+    let sql = Pg::default().add_column(
         true,
         "Array of Double",
         &Column::new(Array(Box::new(Double))),
@@ -112,7 +113,7 @@ fn array_double() {
 
 #[test]
 fn array_boolean() {
-    let sql = Pg::add_column(
+    let sql = Pg::default().add_column(
         true,
         "Array of Boolean",
         &Column::new(Array(Box::new(Boolean))),
@@ -125,27 +126,27 @@ fn array_boolean() {
 
 #[test]
 fn array_binary() {
-    let sql = Pg::add_column(
+    let sql = Pg::default().add_column(
         true,
         "Array of Binary",
         &Column::new(Array(Box::new(Binary))),
     );
-    assert_eq!(String::from("ADD COLUMN \"Array of Binary\" BINARY[]"), sql);
+    assert_eq!(String::from("ADD COLUMN \"Array of Binary\" BYTEA[]"), sql);
 }
 
 #[test]
 fn array_custom() {
-    let sql = Pg::add_column(
+    let sql = Pg::default().add_column(
         true,
         "Array of Point",
-        &Column::new(Array(Box::new(Custom("POINT")))),
+        &Column::new(Array(Box::new(Custom("POINT".into())))),
     );
     assert_eq!(String::from("ADD COLUMN \"Array of Point\" POINT[]"), sql);
 }
 
 #[test]
 fn array_array_integer() {
-    let sql = Pg::add_column(
+    let sql = Pg::default().add_column(
         true,
         "Array of Array of Integer",
         &Column::new(Array(Box::new(Array(Box::new(Integer))))),
@@ -155,3 +156,481 @@ fn array_array_integer() {
         sql
     );
 }
+
+#[test]
+fn uuid() {
+    let sql = Pg::default().add_column(true, "Uuid", &Column::new(Uuid));
+    assert_eq!(String::from("ADD COLUMN \"Uuid\" UUID"), sql);
+}
+
+#[test]
+fn uuid_default_expr() {
+    let mut col = Column::new(Uuid);
+    col.default_expr("gen_random_uuid()");
+    let sql = Pg::default().add_column(true, "Uuid", &col);
+    assert_eq!(
+        String::from("ADD COLUMN \"Uuid\" UUID DEFAULT gen_random_uuid()"),
+        sql
+    );
+}
+
+#[test]
+fn json() {
+    let sql = Pg::default().add_column(true, "Json", &Column::new(Json));
+    assert_eq!(String::from("ADD COLUMN \"Json\" JSON"), sql);
+}
+
+#[test]
+fn jsonb() {
+    let sql = Pg::default().add_column(true, "Jsonb", &Column::new(Jsonb));
+    assert_eq!(String::from("ADD COLUMN \"Jsonb\" JSONB"), sql);
+}
+
+#[test]
+fn date() {
+    let sql = Pg::default().add_column(true, "Date", &Column::new(Date));
+    assert_eq!(String::from("ADD COLUMN \"Date\" DATE"), sql);
+}
+
+#[test]
+fn time() {
+    let sql = Pg::default().add_column(true, "Time", &Column::new(Time(None)));
+    assert_eq!(String::from("ADD COLUMN \"Time\" TIME"), sql);
+}
+
+#[test]
+fn datetime_precision() {
+    let sql = Pg::default().add_column(true, "DateTime", &Column::new(DateTime(Some(3))));
+    assert_eq!(String::from("ADD COLUMN \"DateTime\" TIMESTAMP(3)"), sql);
+}
+
+#[test]
+fn timestamptz() {
+    let sql = Pg::default().add_column(true, "TimestampTz", &Column::new(TimestampTz(None)));
+    assert_eq!(
+        String::from("ADD COLUMN \"TimestampTz\" TIMESTAMP WITH TIME ZONE"),
+        sql
+    );
+}
+
+#[test]
+fn timestamptz_precision() {
+    let sql = Pg::default().add_column(true, "TimestampTz", &Column::new(TimestampTz(Some(3))));
+    assert_eq!(
+        String::from("ADD COLUMN \"TimestampTz\" TIMESTAMP(3) WITH TIME ZONE"),
+        sql
+    );
+}
+
+#[test]
+fn interval() {
+    let sql = Pg::default().add_column(true, "Interval", &Column::new(Interval(None)));
+    assert_eq!(String::from("ADD COLUMN \"Interval\" INTERVAL"), sql);
+}
+
+#[test]
+fn interval_fields() {
+    let sql = Pg::default().add_column(
+        true,
+        "Interval",
+        &Column::new(Interval(Some("DAY TO SECOND(3)".into()))),
+    );
+    assert_eq!(
+        String::from("ADD COLUMN \"Interval\" INTERVAL DAY TO SECOND(3)"),
+        sql
+    );
+}
+
+#[test]
+fn decimal() {
+    let sql = Pg::default().add_column(true, "Decimal", &Column::new(Decimal(10, 2)));
+    assert_eq!(String::from("ADD COLUMN \"Decimal\" NUMERIC(10, 2)"), sql);
+}
+
+#[test]
+fn money() {
+    let sql = Pg::default().add_column(true, "Money", &Column::new(Money));
+    assert_eq!(String::from("ADD COLUMN \"Money\" MONEY"), sql);
+}
+
+#[test]
+fn smallint() {
+    let sql = Pg::default().add_column(true, "SmallInt", &Column::new(SmallInt));
+    assert_eq!(String::from("ADD COLUMN \"SmallInt\" SMALLINT"), sql);
+}
+
+#[test]
+fn bigint() {
+    let sql = Pg::default().add_column(true, "BigInt", &Column::new(BigInt));
+    assert_eq!(String::from("ADD COLUMN \"BigInt\" BIGINT"), sql);
+}
+
+#[test]
+fn unsigned() {
+    use crate::{Error, Migration, Table};
+
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("age", Integer).unsigned();
+    });
+    assert_eq!(
+        m.try_make::<Pg>(),
+        Err(Error::Unsupported("Postgres", "Unsigned integers".into()))
+    );
+}
+
+#[test]
+fn bigserial() {
+    let sql = Pg::default().add_column(true, "BigSerial", &Column::new(BigSerial));
+    assert_eq!(String::from("ADD COLUMN \"BigSerial\" BIGSERIAL"), sql);
+}
+
+#[test]
+fn varbinary() {
+    let sql = Pg::default().add_column(true, "Varbinary", &Column::new(Varbinary(16)));
+    assert_eq!(String::from("ADD COLUMN \"Varbinary\" BYTEA"), sql);
+}
+
+#[test]
+fn char() {
+    let sql = Pg::default().add_column(true, "Char", &Column::new(Char(2)));
+    assert_eq!(String::from("ADD COLUMN \"Char\" CHAR(2)"), sql);
+}
+
+#[test]
+fn enumeration() {
+    let t = crate::Type::enumeration("mood", &["happy", "sad"]);
+    let sql = Pg::default().add_column(true, "Enum", &Column::new(t));
+    assert_eq!(String::from("ADD COLUMN \"Enum\" \"mood\""), sql);
+}
+
+#[test]
+fn set() {
+    use crate::{Error, Migration, Table};
+
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("rights", crate::Type::set(&["read", "write"]));
+    });
+    assert_eq!(
+        m.try_make::<Pg>(),
+        Err(Error::Unsupported("Postgres", "SET types".into()))
+    );
+
+    /* Element types are validated as well */
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("rights", crate::Type::array(crate::Type::set(&["read"]), 1));
+    });
+    assert_eq!(
+        m.try_make::<Pg>(),
+        Err(Error::Unsupported("Postgres", "SET types".into()))
+    );
+}
+
+#[test]
+fn inet() {
+    let sql = Pg::default().add_column(true, "Inet", &Column::new(Inet));
+    assert_eq!(String::from("ADD COLUMN \"Inet\" INET"), sql);
+}
+
+#[test]
+fn cidr() {
+    let sql = Pg::default().add_column(true, "Cidr", &Column::new(Cidr));
+    assert_eq!(String::from("ADD COLUMN \"Cidr\" CIDR"), sql);
+}
+
+#[test]
+fn macaddr() {
+    let sql = Pg::default().add_column(true, "MacAddr", &Column::new(MacAddr));
+    assert_eq!(String::from("ADD COLUMN \"MacAddr\" MACADDR"), sql);
+}
+
+#[test]
+fn xml() {
+    let sql = Pg::default().add_column(true, "Xml", &Column::new(Xml));
+    assert_eq!(String::from("ADD COLUMN \"Xml\" XML"), sql);
+}
+
+#[test]
+fn hstore() {
+    let sql = Pg::default().add_column(true, "Hstore", &Column::new(Hstore));
+    assert_eq!(String::from("ADD COLUMN \"Hstore\" HSTORE"), sql);
+}
+
+#[test]
+fn citext() {
+    let sql = Pg::default().add_column(true, "Citext", &Column::new(Citext));
+    assert_eq!(String::from("ADD COLUMN \"Citext\" CITEXT"), sql);
+}
+
+#[test]
+fn tsvector() {
+    let sql = Pg::default().add_column(true, "TsVector", &Column::new(TsVector));
+    assert_eq!(String::from("ADD COLUMN \"TsVector\" TSVECTOR"), sql);
+}
+
+#[test]
+fn tsquery() {
+    let sql = Pg::default().add_column(true, "TsQuery", &Column::new(TsQuery));
+    assert_eq!(String::from("ADD COLUMN \"TsQuery\" TSQUERY"), sql);
+}
+
+#[test]
+fn range() {
+    use crate::RangeKind;
+
+    let t = Range(RangeKind::Timestamptz);
+    let sql = Pg::default().add_column(true, "Range", &Column::new(t));
+    assert_eq!(String::from("ADD COLUMN \"Range\" TSTZRANGE"), sql);
+
+    let sql = Pg::default().add_column(true, "Range", &Column::new(Range(RangeKind::Int4)));
+    assert_eq!(String::from("ADD COLUMN \"Range\" INT4RANGE"), sql);
+}
+
+#[test]
+fn point() {
+    let sql = Pg::default().add_column(true, "Point", &Column::new(Point));
+    assert_eq!(String::from("ADD COLUMN \"Point\" POINT"), sql);
+}
+
+#[test]
+#[cfg(feature = "postgis")]
+fn geometry() {
+    use crate::GeometryKind;
+
+    let t = Geometry(GeometryKind::Point, Some(4326));
+    let sql = Pg::default().add_column(true, "Geometry", &Column::new(t));
+    assert_eq!(
+        String::from("ADD COLUMN \"Geometry\" GEOMETRY(POINT, 4326)"),
+        sql
+    );
+
+    let t = Geography(GeometryKind::MultiPolygon, None);
+    let sql = Pg::default().add_column(true, "Geography", &Column::new(t));
+    assert_eq!(
+        String::from("ADD COLUMN \"Geography\" GEOGRAPHY(MULTIPOLYGON)"),
+        sql
+    );
+}
+
+#[test]
+fn bit() {
+    let sql = Pg::default().add_column(true, "Bit", &Column::new(Bit(8)));
+    assert_eq!(String::from("ADD COLUMN \"Bit\" BIT(8)"), sql);
+}
+
+#[test]
+fn varbit() {
+    let sql = Pg::default().add_column(true, "Varbit", &Column::new(Varbit(16)));
+    assert_eq!(String::from("ADD COLUMN \"Varbit\" VARBIT(16)"), sql);
+
+    let sql = Pg::default().add_column(true, "Varbit", &Column::new(Varbit(0)));
+    assert_eq!(String::from("ADD COLUMN \"Varbit\" VARBIT"), sql);
+}
+
+#[test]
+fn array_dimensions() {
+    let t = crate::Type::array(Text, 2);
+    let sql = Pg::default().add_column(true, "Matrix", &Column::new(t));
+    assert_eq!(String::from("ADD COLUMN \"Matrix\" TEXT[][]"), sql);
+}
+
+#[test]
+fn foreign_dynamic() {
+    let tenant = 7;
+    let t = crate::Type::foreign(format!("tenant_{}_users", tenant));
+    let sql = Pg::default().add_column(true, "Owner", &Column::new(t));
+    assert_eq!(
+        String::from("ADD COLUMN \"Owner\" INTEGER REFERENCES tenant_7_users"),
+        sql
+    );
+}
+
+#[test]
+fn custom_per() {
+    use crate::backend::SqlVariant;
+
+    let t = crate::Type::custom_per(&[(SqlVariant::Pg, "LTREE")]);
+    let sql = Pg::default().add_column(true, "Path", &Column::new(t));
+    assert_eq!(String::from("ADD COLUMN \"Path\" LTREE"), sql);
+}
+
+#[test]
+fn longtext() {
+    let sql = Pg::default().add_column(true, "LongText", &Column::new(LongText));
+    assert_eq!(String::from("ADD COLUMN \"LongText\" TEXT"), sql);
+}
+
+#[test]
+fn generated() {
+    let mut col = Column::new(Integer);
+    col.generated("price * quantity", crate::Generated::Stored);
+    let sql = Pg::default().add_column(true, "total", &col);
+    assert_eq!(
+        String::from("ADD COLUMN \"total\" INTEGER GENERATED ALWAYS AS (price * quantity) STORED"),
+        sql
+    );
+}
+
+#[test]
+fn identity() {
+    use crate::Identity;
+
+    let mut col = Column::new(BigInt);
+    col.identity(Identity::always().start(100).increment(10));
+    let sql = Pg::default().add_column(true, "id", &col);
+    assert_eq!(
+        String::from(
+            "ADD COLUMN \"id\" BIGINT GENERATED ALWAYS AS IDENTITY (START WITH 100 INCREMENT BY 10)"
+        ),
+        sql
+    );
+}
+
+#[test]
+fn identity_primary() {
+    let mut col = Column::new(Primary);
+    col.identity(crate::Identity::by_default());
+    let sql = Pg::default().add_column(true, "id", &col);
+    assert_eq!(
+        String::from("ADD COLUMN \"id\" INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY"),
+        sql
+    );
+}
+
+#[test]
+fn check() {
+    let mut col = Column::new(Text);
+    col.check("length(name) > 0")
+        .check_named("name_short", "length(name) < 64");
+    let sql = Pg::default().add_column(true, "name", &col);
+    assert_eq!(
+        String::from("ADD COLUMN \"name\" TEXT CHECK (length(name) > 0) CONSTRAINT \"name_short\" CHECK (length(name) < 64)"),
+        sql
+    );
+}
+
+#[test]
+fn collate() {
+    let mut col = Column::new(Text);
+    col.collate("de_DE");
+    let sql = Pg::default().add_column(true, "name", &col);
+    assert_eq!(
+        String::from("ADD COLUMN \"name\" TEXT COLLATE \"de_DE\""),
+        sql
+    );
+}
+
+#[test]
+fn charset() {
+    use crate::{Error, TableChange};
+
+    let mut col = Column::new(Text);
+    col.charset("utf8mb4");
+    let change = TableChange::AddColumn("name".into(), col);
+    assert_eq!(
+        Pg::default().validate_table_change(&change),
+        Err(Error::Unsupported(
+            "Postgres",
+            "Column character sets".into()
+        ))
+    );
+}
+
+#[test]
+fn on_update() {
+    use crate::{Error, TableChange};
+
+    let mut col = Column::new(DateTime(None));
+    col.on_update_current_timestamp();
+    let change = TableChange::AddColumn("updated_at".into(), col);
+    assert_eq!(
+        Pg::default().validate_table_change(&change),
+        Err(Error::Unsupported(
+            "Postgres",
+            "ON UPDATE expressions".into()
+        ))
+    );
+}
+
+#[test]
+fn foreign_actions() {
+    use crate::{ForeignKey, ReferentialAction};
+
+    let fk = ForeignKey::new("users")
+        .column("uid")
+        .on_delete(ReferentialAction::Cascade)
+        .on_update(ReferentialAction::NoAction);
+    let sql = Pg::default().add_column(true, "author", &Column::new(Foreign(fk)));
+    assert_eq!(
+        String::from("ADD COLUMN \"author\" INTEGER REFERENCES users(uid) ON DELETE CASCADE ON UPDATE NO ACTION"),
+        sql
+    );
+}
+
+#[test]
+fn foreign_deferrable() {
+    use crate::ForeignKey;
+
+    let fk = ForeignKey::new("users").initially_deferred();
+    let sql = Pg::default().add_column(true, "author", &Column::new(Foreign(fk)));
+    assert_eq!(
+        String::from(
+            "ADD COLUMN \"author\" INTEGER REFERENCES users DEFERRABLE INITIALLY DEFERRED"
+        ),
+        sql
+    );
+}
+
+#[test]
+fn foreign_named() {
+    use crate::ForeignKey;
+
+    let fk = ForeignKey::new("users").constraint_name("posts_author_fk");
+    let sql = Pg::default().add_column(true, "author", &Column::new(Foreign(fk)));
+    assert_eq!(
+        String::from(
+            "ADD COLUMN \"author\" INTEGER CONSTRAINT \"posts_author_fk\" REFERENCES users"
+        ),
+        sql
+    );
+}
+
+#[test]
+fn primary_uuid() {
+    let mut col = Column::new(Uuid);
+    col.primary();
+    let sql = Pg::default().add_column(false, "id", &col);
+    assert_eq!(String::from("\"id\" UUID PRIMARY KEY"), sql);
+}
+
+#[test]
+fn timestamp_precision() {
+    use crate::{Error, TableChange};
+
+    let sql = Pg::default().add_column(true, "at", &Column::new(DateTime(Some(3))));
+    assert_eq!(String::from("ADD COLUMN \"at\" TIMESTAMP(3)"), sql);
+
+    let change = TableChange::AddColumn("at".into(), Column::new(TimestampTz(Some(9))));
+    assert_eq!(
+        Pg::default().validate_table_change(&change),
+        Err(Error::Unsupported(
+            "Postgres",
+            "More than 6 fractional second digits".into()
+        ))
+    );
+}
+
+#[test]
+fn storage_compression() {
+    use crate::Storage;
+
+    let mut col = Column::new(Text);
+    col.storage(Storage::External).compression("lz4");
+    let sql = Pg::default().add_column(true, "body", &col);
+    assert_eq!(
+        String::from("ADD COLUMN \"body\" TEXT STORAGE EXTERNAL COMPRESSION lz4"),
+        sql
+    );
+}
@@ -1,12 +1,12 @@
 //! Some unit tests that create create tables
 #![allow(unused_imports)]
 
-use backend::{Pg, SqlGenerator};
-use {Migration, Table};
+use crate::backend::{Pg, SqlGenerator};
+use crate::{Migration, Table};
 
 #[test]
 fn simple_table() {
-    use Type::*;
+    use crate::Type::*;
     let mut m = Migration::new();
     m.create_table("users", |_: &mut Table| {});
     assert_eq!(
@@ -17,7 +17,7 @@ fn simple_table() {
 
 #[test]
 fn basic_fields() {
-    use Type::*;
+    use crate::Type::*;
     let mut m = Migration::new();
     m.create_table("users", |t: &mut Table| {
         t.add_column("name", Varchar(255));
@@ -33,7 +33,7 @@ fn basic_fields() {
 
 #[test]
 fn basic_fields_with_defaults() {
-    use Type::*;
+    use crate::Type::*;
     let mut m = Migration::new();
     m.create_table("users", |t: &mut Table| {
         t.add_column("name", Varchar(255)).default("Anonymous");
@@ -49,10 +49,10 @@ fn basic_fields_with_defaults() {
 
 #[test]
 fn simple_foreign_fields() {
-    use Type::*;
+    use crate::Type::*;
     let mut m = Migration::new();
     m.create_table("users", |t: &mut Table| {
-        t.add_column("posts", Foreign("posts"));
+        t.add_column("posts", Foreign("posts".into()));
     });
 
     assert_eq!(
@@ -74,16 +74,535 @@ fn drop_table_if_exists() {
     let mut m = Migration::new();
     m.drop_table_if_exists("users");
 
-    assert_eq!(m.make::<Pg>(), String::from("DROP TABLE \"users\" IF EXISTS"));
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("DROP TABLE IF EXISTS \"users\"")
+    );
 }
 
 #[test]
 fn rename_table() {
     let mut m = Migration::new();
     m.rename_table("users", "cool_users");
-    assert_eq!(m.make::<Pg>(), String::from("ALTER TABLE \"users\" RENAME TO \"cool_users\""));
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("ALTER TABLE \"users\" RENAME TO \"cool_users\"")
+    );
 }
 
 // m.change_table("users", |t| {
 
 // });
+
+#[test]
+fn make_from_variant() {
+    use crate::backend::SqlVariant;
+    let mut m = Migration::new();
+    m.drop_table("users");
+
+    let variant: SqlVariant = "postgres".parse().unwrap();
+    assert_eq!(variant, SqlVariant::Pg);
+    assert_eq!(m.make_from(variant), m.make::<Pg>());
+}
+
+#[test]
+fn make_with_schema() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Text);
+    });
+
+    assert_eq!(
+        m.make_with(&Pg::default().schema("app")),
+        String::from("CREATE TABLE \"app\".\"users\" (\"id\" SERIAL PRIMARY KEY, \"name\" TEXT)")
+    );
+}
+
+#[test]
+fn inject_custom() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Text);
+        t.inject_custom("CONSTRAINT name_length CHECK (length(name) > 2)");
+    });
+    m.inject_custom("CREATE INDEX users_name ON users (name)");
+    m.change_table("users", |t: &mut Table| {
+        t.inject_custom("SET UNLOGGED");
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY, \"name\" TEXT, CONSTRAINT name_length CHECK (length(name) > 2)); CREATE INDEX users_name ON users (name); ALTER TABLE \"users\" SET UNLOGGED")
+    );
+}
+
+#[test]
+fn uuid_primary_key() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("sessions", |t: &mut Table| {
+        t.add_column("id", Uuid).primary();
+        t.add_column("data", Jsonb);
+    })
+    .without_id();
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"sessions\" (\"id\" UUID PRIMARY KEY, \"data\" JSONB)")
+    );
+}
+
+#[test]
+fn composite_primary_key() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("memberships", |t: &mut Table| {
+        t.set_primary_key(&["tenant_id", "id"]);
+        t.add_column("tenant_id", Integer);
+        t.add_column("id", Integer);
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"memberships\" (\"tenant_id\" INTEGER, \"id\" INTEGER, PRIMARY KEY (\"tenant_id\", \"id\"))")
+    );
+}
+
+#[test]
+fn unique_constraint() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_unique("uq_user_email_tenant", &["tenant_id", "email"]);
+        t.add_column("tenant_id", Integer);
+        t.add_column("email", Text);
+    });
+    m.change_table("accounts", |t: &mut Table| {
+        t.add_unique("uq_account_name", &["name"]);
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY, \"tenant_id\" INTEGER, \"email\" TEXT, CONSTRAINT \"uq_user_email_tenant\" UNIQUE (\"tenant_id\", \"email\")); ALTER TABLE \"accounts\" ADD CONSTRAINT \"uq_account_name\" UNIQUE (\"name\")")
+    );
+}
+
+#[test]
+fn multi_column_index() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("people", |t: &mut Table| {
+        t.add_column("first_name", Text);
+        t.add_column("last_name", Text);
+        t.add_index("idx_people_name", &["last_name", "first_name"]);
+    });
+    m.change_table("posts", |t: &mut Table| {
+        t.add_column("author", Text);
+        t.add_index("idx_posts_author", &["author"]);
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"people\" (\"id\" SERIAL PRIMARY KEY, \"first_name\" TEXT, \"last_name\" TEXT); CREATE INDEX \"idx_people_name\" ON \"people\" (\"last_name\", \"first_name\"); ALTER TABLE \"posts\" ADD COLUMN \"author\" TEXT; CREATE INDEX \"idx_posts_author\" ON \"posts\" (\"author\")")
+    );
+}
+
+#[test]
+fn partial_index() {
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_index("uq_users_email", &["email"])
+            .unique()
+            .where_clause("deleted_at IS NULL");
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE UNIQUE INDEX \"uq_users_email\" ON \"users\" (\"email\") WHERE deleted_at IS NULL")
+    );
+}
+
+#[test]
+fn expression_index() {
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_index_expr("idx_lower_email", "lower(email)").unique();
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE UNIQUE INDEX \"idx_lower_email\" ON \"users\" (lower(email))")
+    );
+}
+
+#[test]
+fn covering_index() {
+    let mut m = Migration::new();
+    m.change_table("orders", |t: &mut Table| {
+        t.add_index("idx_orders_customer", &["customer_id"])
+            .include(&["total", "created_at"])
+            .where_clause("total > 0");
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE INDEX \"idx_orders_customer\" ON \"orders\" (\"customer_id\") INCLUDE (\"total\", \"created_at\") WHERE total > 0")
+    );
+}
+
+#[test]
+fn fulltext_index() {
+    use crate::{Error, IndexKind};
+    let mut m = Migration::new();
+    m.change_table("places", |t: &mut Table| {
+        t.add_index("ft_places_description", &["description"])
+            .kind(IndexKind::Fulltext);
+    });
+
+    assert_eq!(
+        m.try_make::<Pg>(),
+        Err(Error::Unsupported("Postgres", "FULLTEXT indexes".into()))
+    );
+}
+
+#[test]
+fn check_constraint() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("events", |t: &mut Table| {
+        t.add_column("starts_at", Integer);
+        t.add_column("ends_at", Integer);
+        t.add_check("chk_dates", "starts_at < ends_at");
+    })
+    .without_id();
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"events\" (\"starts_at\" INTEGER, \"ends_at\" INTEGER, CONSTRAINT \"chk_dates\" CHECK (starts_at < ends_at))")
+    );
+}
+
+#[test]
+fn exclusion_constraint() {
+    let mut m = Migration::new();
+    m.change_table("bookings", |t: &mut Table| {
+        t.add_exclusion("no_overlap", "gist", &[("room", "="), ("during", "&&")]);
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("ALTER TABLE \"bookings\" ADD CONSTRAINT \"no_overlap\" EXCLUDE USING gist (\"room\" WITH =, \"during\" WITH &&)")
+    );
+}
+
+#[test]
+fn table_comment() {
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.set_comment("People who can log in");
+    });
+    m.change_table("posts", |t: &mut Table| {
+        t.set_comment("Everything that's been written, even drafts");
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY); COMMENT ON TABLE \"users\" IS 'People who can log in'; COMMENT ON TABLE \"posts\" IS 'Everything that''s been written, even drafts'")
+    );
+}
+
+#[test]
+fn mysql_table_options() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.create_table("users", |_: &mut Table| {}).engine("InnoDB");
+
+    assert_eq!(
+        m.try_make::<Pg>(),
+        Err(Error::Unsupported("Postgres", "MySQL table options".into()))
+    );
+}
+
+#[test]
+fn temporary_table() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("scratch", |t: &mut Table| {
+        t.add_column("value", Text);
+    })
+    .temporary(true);
+    m.create_table_if_not_exists("more_scratch", |_: &mut Table| {})
+        .temporary(true)
+        .without_id();
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TEMPORARY TABLE \"scratch\" (\"id\" SERIAL PRIMARY KEY, \"value\" TEXT); CREATE TEMPORARY TABLE IF NOT EXISTS \"more_scratch\" ()")
+    );
+}
+
+#[test]
+fn unlogged_table() {
+    let mut m = Migration::new();
+    m.create_table("jobs", |_: &mut Table| {}).unlogged(true);
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE UNLOGGED TABLE \"jobs\" (\"id\" SERIAL PRIMARY KEY)")
+    );
+}
+
+#[test]
+fn inherits() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("capitals", |t: &mut Table| {
+        t.inherits("cities");
+        t.add_column("state", Text);
+    })
+    .without_id();
+    m.change_table("villages", |t: &mut Table| {
+        t.inherits("cities");
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"capitals\" (\"state\" TEXT) INHERITS (\"cities\"); ALTER TABLE \"villages\" INHERIT \"cities\"")
+    );
+}
+
+#[test]
+fn partitioned_table() {
+    use crate::PartitionKind;
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("events", |t: &mut Table| {
+        t.add_column("created_at", Date);
+        t.partition_by(PartitionKind::Range, &["created_at"]);
+    })
+    .without_id();
+    m.create_partition(
+        "events_2024",
+        "events",
+        "FOR VALUES FROM ('2024-01-01') TO ('2025-01-01')",
+    );
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"events\" (\"created_at\" DATE) PARTITION BY RANGE (\"created_at\"); CREATE TABLE \"events_2024\" PARTITION OF \"events\" FOR VALUES FROM ('2024-01-01') TO ('2025-01-01')")
+    );
+    assert_eq!(
+        m.revert::<Pg>(),
+        Ok(String::from(
+            "DROP TABLE \"events_2024\"; DROP TABLE \"events\""
+        ))
+    );
+}
+
+#[test]
+fn partition_definitions() {
+    use crate::{Error, PartitionKind};
+    let mut m = Migration::new();
+    m.create_table("events", |t: &mut Table| {
+        t.partition_by(PartitionKind::Hash, &["id"]);
+        t.partition_count(4);
+    });
+
+    assert_eq!(
+        m.try_make::<Pg>(),
+        Err(Error::Unsupported(
+            "Postgres",
+            "Partition definitions".into()
+        ))
+    );
+}
+
+#[test]
+fn create_table_like() {
+    let mut m = Migration::new();
+    m.create_table_like("audit_users", "users");
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"audit_users\" (LIKE \"users\" INCLUDING ALL)")
+    );
+}
+
+#[test]
+fn create_table_from_query() {
+    let mut m = Migration::new();
+    m.create_table_from_query(
+        "daily_totals",
+        "SELECT day, SUM(amount) AS total FROM orders GROUP BY day",
+    );
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from(
+            "CREATE TABLE \"daily_totals\" AS SELECT day, SUM(amount) AS total FROM orders GROUP BY day"
+        )
+    );
+    assert_eq!(
+        m.revert::<Pg>(),
+        Ok(String::from("DROP TABLE \"daily_totals\""))
+    );
+}
+
+#[test]
+fn sqlite_table_options() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.create_table("tags", |_| {}).strict(true);
+
+    assert_eq!(
+        m.try_make::<Pg>(),
+        Err(Error::Unsupported("Postgres", "STRICT tables".into()))
+    );
+}
+
+#[test]
+fn tablespace() {
+    use crate::Type;
+    let mut m = Migration::new();
+    m.create_table("events", |t| {
+        t.add_column("user_id", Type::Integer);
+        t.add_index("events_user_id", &["user_id"])
+            .tablespace("fast_ssd");
+    })
+    .tablespace("fast_ssd");
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"events\" (\"id\" SERIAL PRIMARY KEY, \"user_id\" INTEGER) TABLESPACE \"fast_ssd\"; CREATE INDEX \"events_user_id\" ON \"events\" (\"user_id\") TABLESPACE \"fast_ssd\"")
+    );
+}
+
+#[test]
+fn storage_parameters() {
+    let mut m = Migration::new();
+    m.create_table("counters", |_| {}).with_options(&[
+        ("fillfactor", "70"),
+        ("autovacuum_vacuum_scale_factor", "0.01"),
+    ]);
+    m.change_table("users", |t| {
+        t.meta.with_options(&[("fillfactor", "90")]);
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"counters\" (\"id\" SERIAL PRIMARY KEY) WITH (fillfactor=70, autovacuum_vacuum_scale_factor=0.01); ALTER TABLE \"users\" SET (fillfactor=90)")
+    );
+}
+
+#[test]
+fn row_level_security() {
+    use crate::{Policy, PolicyCommand};
+    let mut m = Migration::new();
+    m.change_table("users", |t| t.enable_row_level_security());
+    m.create_policy(
+        "tenant_isolation",
+        "users",
+        Policy::new()
+            .command(PolicyCommand::Select)
+            .to(&["app_user"])
+            .using("tenant_id = current_setting('app.tenant_id')::int"),
+    );
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("ALTER TABLE \"users\" ENABLE ROW LEVEL SECURITY; CREATE POLICY \"tenant_isolation\" ON \"users\" FOR SELECT TO app_user USING (tenant_id = current_setting('app.tenant_id')::int)")
+    );
+}
+
+#[test]
+fn composite_foreign_key() {
+    use crate::Type;
+    let mut m = Migration::new();
+    m.create_table("shipments", |t| {
+        t.add_column("order_id", Type::Integer);
+        t.add_column("line_no", Type::Integer);
+        t.add_foreign_key(
+            &["order_id", "line_no"],
+            "order_lines",
+            &["order_id", "line_no"],
+        );
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"shipments\" (\"id\" SERIAL PRIMARY KEY, \"order_id\" INTEGER, \"line_no\" INTEGER, FOREIGN KEY (\"order_id\", \"line_no\") REFERENCES \"order_lines\" (\"order_id\", \"line_no\"))")
+    );
+}
+
+#[test]
+fn named_foreign_key() {
+    let mut m = Migration::new();
+    m.change_table("shipments", |t| {
+        t.add_named_foreign_key(
+            "shipments_line_fk",
+            &["order_id", "line_no"],
+            "order_lines",
+            &["order_id", "line_no"],
+        );
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("ALTER TABLE \"shipments\" ADD CONSTRAINT \"shipments_line_fk\" FOREIGN KEY (\"order_id\", \"line_no\") REFERENCES \"order_lines\" (\"order_id\", \"line_no\")")
+    );
+    assert_eq!(
+        m.revert::<Pg>(),
+        Ok(String::from(
+            "ALTER TABLE \"shipments\" DROP CONSTRAINT \"shipments_line_fk\""
+        ))
+    );
+}
+
+#[test]
+fn auto_increment_start() {
+    use crate::{Identity, Type};
+
+    let mut m = Migration::new();
+    m.create_table("users", |_: &mut Table| {})
+        .auto_increment_start(1000);
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY); SELECT setval(pg_get_serial_sequence('\"users\"', 'id'), 1000, false)")
+    );
+
+    /* Identity columns start with the table, unless they have their own start */
+    let mut m = Migration::new();
+    m.create_table("events", |t: &mut Table| {
+        t.add_column("id", Type::Primary)
+            .identity(Identity::always());
+        t.add_column("seq", Type::BigInt)
+            .identity(Identity::always().start(5));
+    })
+    .without_id()
+    .auto_increment_start(1000);
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from(
+            "CREATE TABLE \"events\" (\"id\" INTEGER GENERATED ALWAYS AS IDENTITY (START WITH 1000) PRIMARY KEY, \
+             \"seq\" BIGINT GENERATED ALWAYS AS IDENTITY (START WITH 5))"
+        )
+    );
+}
+
+#[test]
+fn default_with_quotes() {
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("motto", crate::Type::Text)
+            .default("it's 'fine'");
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY, \"motto\" TEXT DEFAULT 'it''s ''fine''')")
+    );
+}
@@ -0,0 +1,36 @@
+//! Write migrations in the diesel_migrations layout
+
+use crate::backend::Pg;
+use crate::integrations::diesel_migrations::write_version;
+use crate::{Migration, Table, Type};
+
+use std::fs;
+
+#[test]
+fn up_and_down() {
+    let dir = std::env::temp_dir().join(format!("barrel-diesel-{}", std::process::id()));
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Text);
+    });
+
+    let path = write_version(
+        &dir,
+        "2019-01-31-174523",
+        "create_users",
+        &m,
+        &Pg::default(),
+    )
+    .unwrap();
+    assert_eq!(path, dir.join("2019-01-31-174523_create_users"));
+    assert_eq!(
+        fs::read_to_string(path.join("up.sql")).unwrap(),
+        "CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY, \"name\" TEXT);\n"
+    );
+    assert_eq!(
+        fs::read_to_string(path.join("down.sql")).unwrap(),
+        "DROP TABLE \"users\";\n"
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,512 @@
+//! Rendering both steps of a migration
+#![allow(unused_imports)]
+
+use crate::backend::{Pg, SqlGenerator};
+use crate::connectors::DatabaseExecutor;
+use crate::{Error, Migration, Table};
+
+#[test]
+fn up_and_down() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Text);
+    });
+    m.down(|m| m.drop_table("users"));
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY, \"name\" TEXT)")
+    );
+    assert_eq!(m.make_down::<Pg>(), String::from("DROP TABLE \"users\""));
+}
+
+#[test]
+fn down_with_schema() {
+    let mut m = Migration::new();
+    m.rename_table("users", "cool_users");
+    m.down(|m| m.rename_table("cool_users", "users"));
+
+    assert_eq!(
+        m.make_down_with(&Pg::default().schema("app")),
+        String::from("ALTER TABLE \"app\".\"cool_users\" RENAME TO \"users\"")
+    );
+}
+
+#[test]
+fn inferred_down() {
+    let mut m = Migration::new();
+    m.create_table("users", |_: &mut Table| {});
+
+    assert_eq!(m.make_down::<Pg>(), String::from("DROP TABLE \"users\""));
+}
+
+#[test]
+fn revert_tables() {
+    let mut m = Migration::new();
+    m.create_table("users", |_: &mut Table| {});
+    m.create_table_if_not_exists("posts", |_: &mut Table| {});
+    m.rename_table("users", "cool_users");
+
+    assert_eq!(
+        m.revert::<Pg>(),
+        Ok(String::from("ALTER TABLE \"cool_users\" RENAME TO \"users\"; DROP TABLE IF EXISTS \"posts\"; DROP TABLE \"users\""))
+    );
+}
+
+#[test]
+fn revert_columns() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_column("age", Integer);
+        t.rename_column("name", "nickname");
+    });
+
+    assert_eq!(
+        m.revert::<Pg>(),
+        Ok(String::from("ALTER TABLE \"users\" ALTER COLUMN \"nickname\" RENAME TO \"name\"; ALTER TABLE \"users\" DROP COLUMN \"age\""))
+    );
+}
+
+#[test]
+fn revert_drop_table() {
+    let mut m = Migration::new();
+    m.drop_table("users");
+
+    assert_eq!(
+        m.revert::<Pg>(),
+        Err(Error::Irreversible("Dropping table `users`".into()))
+    );
+    assert!(m.try_make_down::<Pg>().is_err());
+}
+
+#[test]
+fn revert_drop_column() {
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.drop_column("age");
+    });
+
+    assert_eq!(
+        m.revert::<Pg>(),
+        Err(Error::Irreversible("Dropping column `age`".into()))
+    );
+}
+
+#[test]
+fn idempotent() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.set_idempotent(true);
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Text);
+    });
+    m.change_table("posts", |t: &mut Table| {
+        t.add_column("title", Text);
+        t.drop_column("body");
+        t.add_index("idx_posts_title", &["title"]);
+    });
+    m.drop_table("sessions");
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE IF NOT EXISTS \"users\" (\"id\" SERIAL PRIMARY KEY, \"name\" TEXT); ALTER TABLE \"posts\" ADD COLUMN IF NOT EXISTS \"title\" TEXT; ALTER TABLE \"posts\" DROP COLUMN IF EXISTS \"body\"; CREATE INDEX IF NOT EXISTS \"idx_posts_title\" ON \"posts\" (\"title\"); DROP TABLE IF EXISTS \"sessions\"")
+    );
+}
+
+#[test]
+fn idempotent_down() {
+    let mut m = Migration::new();
+    m.set_idempotent(true);
+    m.create_table("users", |_: &mut Table| {});
+
+    assert_eq!(
+        m.make_down::<Pg>(),
+        String::from("DROP TABLE IF EXISTS \"users\"")
+    );
+
+    m.down(|m| m.drop_table("users"));
+    assert_eq!(
+        m.make_down::<Pg>(),
+        String::from("DROP TABLE IF EXISTS \"users\"")
+    );
+}
+
+#[test]
+fn transactional() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Text);
+    });
+    m.drop_table("sessions");
+
+    assert_eq!(
+        m.make_transactional::<Pg>(),
+        String::from("BEGIN; CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY, \"name\" TEXT); DROP TABLE \"sessions\"; COMMIT")
+    );
+    assert_eq!(Migration::new().make_transactional::<Pg>(), String::new());
+}
+
+#[test]
+fn statements_outside_transactions() {
+    let pg = Pg::default();
+    assert!(pg.transactional("DROP TABLE \"users\""));
+    assert!(!pg.transactional("CREATE INDEX CONCURRENTLY \"idx\" ON \"users\" (\"name\")"));
+    assert!(!pg.transactional("vacuum"));
+}
+
+/// Remembers all SQL it was asked to execute
+struct Recorder(Vec<String>);
+
+impl DatabaseExecutor for Recorder {
+    fn batch_execute(&mut self, sql: &str) -> Result<(), Error> {
+        if sql.contains("fail") {
+            return Err(Error::Execution("relation \"fail\" does not exist".into()));
+        }
+
+        self.0.push(sql.into());
+        Ok(())
+    }
+}
+
+#[test]
+fn execute() {
+    let mut m = Migration::new();
+    m.create_table("users", |_: &mut Table| {});
+
+    let mut runner = Recorder(Vec::new());
+    assert_eq!(m.execute::<_, Pg>(&mut runner), Ok(()));
+    assert_eq!(
+        runner.0,
+        vec![String::from(
+            "CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY)"
+        )]
+    );
+}
+
+#[test]
+fn execute_error() {
+    let mut m = Migration::new();
+    m.drop_table("fail");
+
+    let mut runner = Recorder(Vec::new());
+    assert_eq!(
+        m.execute::<_, Pg>(&mut runner),
+        Err(Error::Execution("relation \"fail\" does not exist".into()))
+    );
+    assert!(runner.0.is_empty());
+}
+
+#[test]
+fn statements() {
+    use crate::backend::SqlVariant;
+    use crate::statement::Operation;
+    use crate::Type::*;
+
+    let mut m = Migration::new();
+    m.create_table("users", |_: &mut Table| {});
+    m.change_table("users", |t: &mut Table| {
+        t.add_column("name", Text);
+        t.rename_column("name", "username");
+    });
+    m.rename_table("users", "people");
+
+    let stmts = m.statements(SqlVariant::Pg);
+    assert_eq!(
+        stmts
+            .iter()
+            .map(|s| (s.table.as_str(), s.operation))
+            .collect::<Vec<_>>(),
+        vec![
+            ("users", Operation::CreateTable),
+            ("users", Operation::AddColumn),
+            ("users", Operation::RenameColumn),
+            ("users", Operation::RenameTable),
+        ]
+    );
+    assert_eq!(
+        stmts[1].to_string(),
+        "ALTER TABLE \"users\" ADD COLUMN \"name\" TEXT"
+    );
+    assert_eq!(
+        stmts
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .join("; "),
+        m.make::<Pg>()
+    );
+}
+
+#[test]
+fn revert_custom() {
+    let mut m = Migration::new();
+    m.inject_custom("VACUUM");
+
+    assert_eq!(
+        m.revert::<Pg>(),
+        Err(Error::Irreversible("Custom SQL `VACUUM`".into()))
+    );
+}
+
+#[test]
+fn enum_types_are_created_once() {
+    use crate::statement::Operation;
+    use crate::Type;
+
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("mood", Type::enumeration("mood", &["happy", "sad"]));
+    });
+    m.change_table("users", |t: &mut Table| {
+        t.add_column("previous", Type::enumeration("mood", &["happy", "sad"]));
+    });
+
+    let stmts = m.try_statements_with(&Pg::default()).unwrap();
+    assert_eq!(stmts[0].operation, Operation::CreateType);
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from(
+            "CREATE TYPE \"mood\" AS ENUM ('happy', 'sad'); \
+             CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY, \"mood\" \"mood\"); \
+             ALTER TABLE \"users\" ADD COLUMN \"previous\" \"mood\""
+        )
+    );
+}
+
+#[test]
+fn enum_types_are_dropped_last() {
+    use crate::Type;
+
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("mood", Type::enumeration("mood", &["happy", "sad"]));
+    });
+    m.change_table("users", |t: &mut Table| {
+        t.add_column("previous", Type::enumeration("mood", &["happy", "sad"]));
+    });
+
+    assert_eq!(
+        m.revert::<Pg>(),
+        Ok(String::from(
+            "ALTER TABLE \"users\" DROP COLUMN \"previous\"; \
+             DROP TABLE \"users\"; \
+             DROP TYPE \"mood\""
+        ))
+    );
+
+    m.set_idempotent(true);
+    assert_eq!(
+        m.revert::<Pg>(),
+        Ok(String::from(
+            "ALTER TABLE \"users\" DROP COLUMN IF EXISTS \"previous\"; \
+             DROP TABLE IF EXISTS \"users\"; \
+             DROP TYPE IF EXISTS \"mood\""
+        ))
+    );
+}
+
+#[test]
+fn extensions_are_created_first() {
+    use crate::statement::Operation;
+    use crate::Type::*;
+
+    let mut m = Migration::new();
+    m.create_table("settings", |t: &mut Table| {
+        t.add_column("user", Hstore);
+        t.add_column("system", Hstore);
+    });
+
+    let stmts = m.try_statements_with(&Pg::default()).unwrap();
+    assert_eq!(
+        stmts.iter().map(|s| s.operation).collect::<Vec<_>>(),
+        vec![Operation::CreateExtension, Operation::CreateTable]
+    );
+    assert_eq!(
+        stmts[0].to_string(),
+        "CREATE EXTENSION IF NOT EXISTS hstore"
+    );
+}
+
+#[test]
+fn citext_needs_an_extension() {
+    use crate::Type::*;
+
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_column("email", Citext);
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from(
+            "CREATE EXTENSION IF NOT EXISTS citext; \
+             ALTER TABLE \"users\" ADD COLUMN \"email\" CITEXT"
+        )
+    );
+}
+
+#[test]
+#[cfg(feature = "postgis")]
+fn postgis_extension() {
+    use crate::GeometryKind;
+    use crate::Type::*;
+
+    let mut m = Migration::new();
+    m.change_table("shops", |t: &mut Table| {
+        t.add_column("location", Geography(GeometryKind::Point, Some(4326)));
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from(
+            "CREATE EXTENSION IF NOT EXISTS postgis; \
+             ALTER TABLE \"shops\" ADD COLUMN \"location\" GEOGRAPHY(POINT, 4326)"
+        )
+    );
+}
+
+#[test]
+fn change_column_type() {
+    use crate::Type;
+    use crate::Type::*;
+
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.change_column("age", BigInt);
+        t.change_column("zip", Integer)
+            .using("NULLIF(zip, '')::int");
+        t.change_column("team_id", Type::foreign("teams"));
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from(
+            "ALTER TABLE \"users\" ALTER COLUMN \"age\" TYPE BIGINT; \
+             ALTER TABLE \"users\" ALTER COLUMN \"zip\" TYPE INTEGER USING NULLIF(zip, '')::int; \
+             ALTER TABLE \"users\" ALTER COLUMN \"team_id\" TYPE INTEGER"
+        )
+    );
+    assert_eq!(
+        m.revert::<Pg>(),
+        Err(Error::Irreversible("Changing column `team_id`".into()))
+    );
+}
+
+#[test]
+fn set_and_drop_default() {
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.set_default("status", "'active'");
+        t.drop_default("plan");
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from(
+            "ALTER TABLE \"users\" ALTER COLUMN \"status\" SET DEFAULT 'active'; \
+             ALTER TABLE \"users\" ALTER COLUMN \"plan\" DROP DEFAULT"
+        )
+    );
+}
+
+#[test]
+fn set_and_drop_not_null() {
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.set_not_null("email");
+        t.drop_not_null("nickname");
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from(
+            "ALTER TABLE \"users\" ALTER COLUMN \"email\" SET NOT NULL; \
+             ALTER TABLE \"users\" ALTER COLUMN \"nickname\" DROP NOT NULL"
+        )
+    );
+    assert_eq!(
+        m.revert::<Pg>(),
+        Ok(String::from(
+            "ALTER TABLE \"users\" ALTER COLUMN \"nickname\" SET NOT NULL; \
+             ALTER TABLE \"users\" ALTER COLUMN \"email\" DROP NOT NULL"
+        ))
+    );
+}
+
+#[test]
+fn rename_constraint_and_index() {
+    let mut m = Migration::new();
+    m.rename_table("users", "accounts");
+    m.change_table("accounts", |t: &mut Table| {
+        t.rename_constraint("users_email_key", "accounts_email_key");
+    });
+    m.rename_index("users_name_idx", "accounts_name_idx");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from(
+            "ALTER TABLE \"users\" RENAME TO \"accounts\"; \
+             ALTER TABLE \"accounts\" RENAME CONSTRAINT \"users_email_key\" TO \"accounts_email_key\"; \
+             ALTER INDEX \"users_name_idx\" RENAME TO \"accounts_name_idx\""
+        )
+    );
+    assert_eq!(
+        m.revert::<Pg>(),
+        Ok(String::from(
+            "ALTER INDEX \"accounts_name_idx\" RENAME TO \"users_name_idx\"; \
+             ALTER TABLE \"accounts\" RENAME CONSTRAINT \"accounts_email_key\" TO \"users_email_key\"; \
+             ALTER TABLE \"accounts\" RENAME TO \"users\""
+        ))
+    );
+}
+
+#[test]
+fn add_and_drop_constraint() {
+    use crate::Constraint;
+    let mut m = Migration::new();
+    m.change_table("orders", |t: &mut Table| {
+        t.add_constraint(
+            "fk_orders_user",
+            Constraint::ForeignKey {
+                columns: vec!["user_id".into()],
+                table: "users".into(),
+                references: vec!["id".into()],
+            },
+        );
+        t.drop_constraint("orders_total_check");
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from(
+            "ALTER TABLE \"orders\" \
+             ADD CONSTRAINT \"fk_orders_user\" FOREIGN KEY (\"user_id\") REFERENCES \"users\" (\"id\"); \
+             ALTER TABLE \"orders\" DROP CONSTRAINT \"orders_total_check\""
+        )
+    );
+    assert_eq!(
+        m.revert::<Pg>(),
+        Err(Error::Irreversible(
+            "Dropping constraint `orders_total_check`".into()
+        ))
+    );
+}
+
+#[test]
+fn revert_add_constraint() {
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_check("users_age_check", "age >= 0");
+    });
+    assert_eq!(
+        m.revert::<Pg>(),
+        Ok(String::from(
+            "ALTER TABLE \"users\" DROP CONSTRAINT \"users_age_check\""
+        ))
+    );
+
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| t.set_comment("Everyone"));
+    assert_eq!(
+        m.revert::<Pg>(),
+        Err(Error::Irreversible(
+            "Changing the options of table `users`".into()
+        ))
+    );
+}
@@ -1,5 +1,15 @@
 //! Test pgsql generation
 
-mod simple;
 mod add_column;
-mod create_table;
\ No newline at end of file
+mod create_table;
+mod migration;
+mod simple;
+
+#[cfg(feature = "unstable")]
+mod types;
+
+#[cfg(feature = "diesel")]
+mod diesel;
+
+#[cfg(feature = "refinery")]
+mod refinery;
@@ -0,0 +1,33 @@
+//! Turn migrations into refinery migrations
+
+use crate::backend::Pg;
+use crate::integrations::refinery::migration;
+use crate::{Error, Migration, Table, Type};
+
+#[test]
+fn unapplied() {
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Text);
+    });
+
+    let r = migration(1, "create_users", &m, &Pg::default()).unwrap();
+    assert_eq!(r.version(), 1);
+    assert_eq!(r.name(), "create_users");
+    assert_eq!(
+        r.sql(),
+        Some("CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY, \"name\" TEXT)")
+    );
+}
+
+#[test]
+fn invalid_name() {
+    let m = Migration::new();
+    assert_eq!(
+        migration(1, "create users", &m, &Pg::default()).err(),
+        Some(Error::Unsupported(
+            "refinery",
+            "Migration name `create users`".into()
+        ))
+    );
+}
@@ -1,54 +1,106 @@
 //! Other simple table/ column migrations
 #![allow(unused_imports)]
 
-use backend::{SqlGenerator, Pg};
-use Type::*;
-
+use crate::backend::{Pg, SqlGenerator};
+use crate::Type::*;
 
 #[test]
 fn create_table() {
-    let sql = Pg::create_table("table_to_create");
+    let sql = Pg::default().create_table("table_to_create");
     assert_eq!(String::from("CREATE TABLE \"table_to_create\""), sql);
 }
 
 #[test]
 fn create_table_if_not_exists() {
-    let sql = Pg::create_table_if_not_exists("table_to_create");
-    assert_eq!(String::from("CREATE TABLE \"table_to_create\" IF NOT EXISTS"), sql);
+    let sql = Pg::default().create_table_if_not_exists("table_to_create");
+    assert_eq!(
+        String::from("CREATE TABLE IF NOT EXISTS \"table_to_create\""),
+        sql
+    );
 }
 
 #[test]
 fn drop_table() {
-    let sql = Pg::drop_table("table_to_drop");
+    let sql = Pg::default().drop_table("table_to_drop");
     assert_eq!(String::from("DROP TABLE \"table_to_drop\""), sql);
 }
 
 #[test]
 fn drop_table_if_exists() {
-    let sql = Pg::drop_table_if_exists("table_to_drop");
-    assert_eq!(String::from("DROP TABLE \"table_to_drop\" IF EXISTS"), sql);
+    let sql = Pg::default().drop_table_if_exists("table_to_drop");
+    assert_eq!(String::from("DROP TABLE IF EXISTS \"table_to_drop\""), sql);
 }
 
 #[test]
 fn rename_table() {
-    let sql = Pg::rename_table("old_table", "new_table");
-    assert_eq!(String::from("ALTER TABLE \"old_table\" RENAME TO \"new_table\""), sql);
+    let sql = Pg::default().rename_table("old_table", "new_table");
+    assert_eq!(
+        String::from("ALTER TABLE \"old_table\" RENAME TO \"new_table\""),
+        sql
+    );
 }
 
 #[test]
 fn alter_table() {
-    let sql = Pg::alter_table("table_to_alter");
+    let sql = Pg::default().alter_table("table_to_alter");
     assert_eq!(String::from("ALTER TABLE \"table_to_alter\""), sql);
 }
 
 #[test]
 fn drop_column() {
-    let sql = Pg::drop_column("column_to_drop");
+    let sql = Pg::default().drop_column("column_to_drop");
     assert_eq!(String::from("DROP COLUMN \"column_to_drop\""), sql);
 }
 
 #[test]
 fn rename_column() {
-    let sql = Pg::rename_column("old_column", "new_column");
-    assert_eq!(String::from("ALTER COLUMN \"old_column\" RENAME TO \"new_column\""), sql);
+    let sql = Pg::default().rename_column("old_column", "new_column");
+    assert_eq!(
+        String::from("ALTER COLUMN \"old_column\" RENAME TO \"new_column\""),
+        sql
+    );
+}
+
+#[test]
+fn create_table_with_schema() {
+    let sql = Pg::default()
+        .schema("public")
+        .create_table("table_to_create");
+    assert_eq!(
+        String::from("CREATE TABLE \"public\".\"table_to_create\""),
+        sql
+    );
+}
+
+#[test]
+fn rename_table_with_schema() {
+    let sql = Pg::default()
+        .schema("public")
+        .rename_table("old_table", "new_table");
+    assert_eq!(
+        String::from("ALTER TABLE \"public\".\"old_table\" RENAME TO \"new_table\""),
+        sql
+    );
+}
+
+#[test]
+fn insert() {
+    use crate::Value;
+    let sql = Pg::default().insert(
+        "users",
+        &[
+            ("name".into(), Value::from("O'Brien")),
+            ("age".into(), Value::from(42)),
+            ("admin".into(), Value::from(true)),
+            ("bio".into(), Value::Null),
+        ],
+    );
+    assert_eq!(
+        String::from("INSERT INTO \"users\" (\"name\", \"age\", \"admin\", \"bio\") VALUES ('O''Brien', 42, TRUE, NULL)"),
+        sql
+    );
+    assert_eq!(
+        Pg::default().insert("users", &[]),
+        String::from("INSERT INTO \"users\" DEFAULT VALUES")
+    );
 }
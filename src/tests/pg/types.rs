@@ -0,0 +1,71 @@
+//! Columns built with the `types` module
+#![allow(unused_imports)]
+
+use crate::backend::{Pg, SqlGenerator};
+use crate::types;
+use crate::{Column, Migration, Table};
+
+#[test]
+fn uuid() {
+    let mut m = Migration::new();
+    m.create_table("sessions", |t: &mut Table| {
+        t.add_column("token", types::uuid().default_expr("gen_random_uuid()"));
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"sessions\" (\"id\" SERIAL PRIMARY KEY, \"token\" UUID DEFAULT gen_random_uuid() NOT NULL)")
+    );
+}
+
+#[test]
+fn column_metadata() {
+    let col: Column = types::varchar().size(64).nullable(true).into();
+    assert_eq!(
+        Pg::default().add_column(true, "name", &col),
+        String::from("ADD COLUMN \"name\" VARCHAR(64)")
+    );
+
+    let col: Column = types::integer().default(42).into();
+    assert_eq!(
+        Pg::default().add_column(true, "score", &col),
+        String::from("ADD COLUMN \"score\" INTEGER DEFAULT '42' NOT NULL")
+    );
+}
+
+#[test]
+fn base_type() {
+    let col = Column::new(types::array(types::decimal(10, 2)).into());
+    assert_eq!(
+        Pg::default().add_column(true, "prices", &col),
+        String::from("ADD COLUMN \"prices\" NUMERIC(10, 2)[]")
+    );
+}
+
+#[test]
+fn timestamp_fraction_digits() {
+    let col: Column = types::timestamp().fraction_digits(3).into();
+    assert_eq!(
+        Pg::default().add_column(true, "created_at", &col),
+        String::from("ADD COLUMN \"created_at\" TIMESTAMP(3) NOT NULL")
+    );
+}
+
+#[test]
+fn enumeration() {
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("status", types::enumeration(&["active", "banned"]));
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TYPE \"users_status\" AS ENUM ('active', 'banned'); CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY, \"status\" \"users_status\" NOT NULL)")
+    );
+    assert_eq!(
+        m.revert::<Pg>(),
+        Ok(String::from(
+            "DROP TABLE \"users\"; DROP TYPE \"users_status\""
+        ))
+    );
+}
@@ -0,0 +1,181 @@
+//! All add_column combinations for sqlite3
+#![allow(unused_imports)]
+
+use crate::backend::{SqlGenerator, Sqlite};
+use crate::Column;
+use crate::Type::*;
+
+#[test]
+fn text() {
+    let sql = Sqlite::default().add_column(true, "Text", &Column::new(Text));
+    assert_eq!(String::from("ADD COLUMN \"Text\" TEXT"), sql);
+}
+
+#[test]
+fn uuid() {
+    let sql = Sqlite::default().add_column(true, "Uuid", &Column::new(Uuid));
+    assert_eq!(String::from("ADD COLUMN \"Uuid\" TEXT"), sql);
+}
+
+#[test]
+fn json() {
+    let sql = Sqlite::default().add_column(true, "Json", &Column::new(Json));
+    assert_eq!(String::from("ADD COLUMN \"Json\" TEXT"), sql);
+}
+
+#[test]
+fn datetime() {
+    let sql = Sqlite::default().add_column(true, "DateTime", &Column::new(DateTime(Some(3))));
+    assert_eq!(String::from("ADD COLUMN \"DateTime\" DATETIME"), sql);
+}
+
+#[test]
+fn interval() {
+    let sql =
+        Sqlite::default().add_column(true, "Interval", &Column::new(Interval(Some("DAY".into()))));
+    assert_eq!(String::from("ADD COLUMN \"Interval\" INTEGER"), sql);
+}
+
+#[test]
+fn bigint() {
+    let sql = Sqlite::default().add_column(true, "BigInt", &Column::new(BigInt));
+    assert_eq!(String::from("ADD COLUMN \"BigInt\" BIGINT"), sql);
+}
+
+#[test]
+fn serial() {
+    use crate::{Error, TableChange};
+
+    let change = TableChange::AddColumn("Serial".into(), Column::new(Serial));
+    assert_eq!(
+        Sqlite::default().validate_table_change(&change),
+        Err(Error::Unsupported(
+            "Sqlite",
+            "Auto-incrementing columns other than the primary key".into()
+        ))
+    );
+}
+
+#[test]
+fn binary() {
+    let sql = Sqlite::default().add_column(true, "Binary", &Column::new(Varbinary(16)));
+    assert_eq!(String::from("ADD COLUMN \"Binary\" BLOB"), sql);
+}
+
+#[test]
+fn enumeration() {
+    let t = crate::Type::enumeration("mood", &["happy", "sad"]);
+    let sql = Sqlite::default().add_column(true, "Enum", &Column::new(t));
+    assert_eq!(
+        String::from("ADD COLUMN \"Enum\" TEXT CHECK (\"Enum\" IN ('happy', 'sad'))"),
+        sql
+    );
+}
+
+#[test]
+fn set() {
+    use crate::{Error, TableChange};
+
+    let t = crate::Type::set(&["read", "write"]);
+    let change = TableChange::AddColumn("Set".into(), Column::new(t));
+    assert_eq!(
+        Sqlite::default().validate_table_change(&change),
+        Err(Error::Unsupported("Sqlite", "SET types".into()))
+    );
+}
+
+#[test]
+fn inet() {
+    let sql = Sqlite::default().add_column(true, "Inet", &Column::new(Inet));
+    assert_eq!(String::from("ADD COLUMN \"Inet\" TEXT"), sql);
+}
+
+#[test]
+fn xml() {
+    let sql = Sqlite::default().add_column(true, "Xml", &Column::new(Xml));
+    assert_eq!(String::from("ADD COLUMN \"Xml\" TEXT"), sql);
+}
+
+#[test]
+fn citext() {
+    let sql = Sqlite::default().add_column(true, "Citext", &Column::new(Citext));
+    assert_eq!(
+        String::from("ADD COLUMN \"Citext\" TEXT COLLATE NOCASE"),
+        sql
+    );
+}
+
+#[test]
+fn tsquery() {
+    use crate::{Error, TableChange};
+
+    let change = TableChange::AddColumn("TsQuery".into(), Column::new(TsQuery));
+    assert_eq!(
+        Sqlite::default().validate_table_change(&change),
+        Err(Error::Unsupported(
+            "Sqlite",
+            "Full-text search types".into()
+        ))
+    );
+}
+
+#[test]
+fn array() {
+    use crate::{Error, TableChange};
+
+    let t = crate::Type::array(Text, 1);
+    let change = TableChange::AddColumn("Array".into(), Column::new(t));
+    assert_eq!(
+        Sqlite::default().validate_table_change(&change),
+        Err(Error::Unsupported("Sqlite", "Array types".into()))
+    );
+}
+
+#[test]
+fn custom_per_without_entry() {
+    use crate::{Error, TableChange};
+
+    /* Other backends are only available with their features */
+    let t = crate::Type::custom_per(&[]);
+    let change = TableChange::AddColumn("Path".into(), Column::new(t));
+    assert_eq!(
+        Sqlite::default().validate_table_change(&change),
+        Err(Error::Unsupported(
+            "Sqlite",
+            "A custom type without an entry for it".into()
+        ))
+    );
+}
+
+#[test]
+fn generated() {
+    let mut col = Column::new(Integer);
+    col.generated("price * quantity", crate::Generated::Virtual);
+    let sql = Sqlite::default().add_column(true, "total", &col);
+    assert_eq!(
+        String::from("ADD COLUMN \"total\" INTEGER GENERATED ALWAYS AS (price * quantity) VIRTUAL"),
+        sql
+    );
+}
+
+#[test]
+fn collate() {
+    let mut col = Column::new(Text);
+    col.collate("NOCASE");
+    let sql = Sqlite::default().add_column(true, "name", &col);
+    assert_eq!(String::from("ADD COLUMN \"name\" TEXT COLLATE NOCASE"), sql);
+}
+
+#[test]
+fn foreign_deferrable() {
+    use crate::{ForeignKey, ReferentialAction};
+
+    let fk = ForeignKey::new("users")
+        .on_delete(ReferentialAction::Cascade)
+        .deferrable();
+    let sql = Sqlite::default().add_column(true, "author", &Column::new(Foreign(fk)));
+    assert_eq!(
+        String::from("ADD COLUMN \"author\" INTEGER REFERENCES users ON DELETE CASCADE DEFERRABLE INITIALLY IMMEDIATE"),
+        sql
+    );
+}
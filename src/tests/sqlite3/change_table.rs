@@ -0,0 +1,205 @@
+//! Make sure that table changes are split into single statements
+#![allow(unused_imports)]
+
+use crate::backend::{SqlGenerator, Sqlite};
+use crate::{Migration, Table};
+
+#[test]
+fn add_column() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_column("age", Integer);
+    });
+
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from("ALTER TABLE \"users\" ADD COLUMN \"age\" INTEGER")
+    );
+}
+
+#[test]
+fn multiple_changes() {
+    use crate::Type::*;
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_column("age", Integer);
+        t.rename_column("name", "nickname");
+        t.drop_column("plushy_sharks_owned");
+    });
+
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from("ALTER TABLE \"users\" ADD COLUMN \"age\" INTEGER; ALTER TABLE \"users\" RENAME COLUMN \"name\" TO \"nickname\"; ALTER TABLE \"users\" DROP COLUMN \"plushy_sharks_owned\"")
+    );
+}
+
+#[test]
+fn create_multiple_tables() {
+    let mut m = Migration::new();
+    m.create_table("users", |_: &mut Table| {});
+    m.create_table("posts", |_: &mut Table| {});
+
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from("CREATE TABLE \"users\" (\"id\" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT); CREATE TABLE \"posts\" (\"id\" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT)")
+    );
+}
+
+#[test]
+fn make_from_variant() {
+    use crate::backend::SqlVariant;
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.drop_column("age");
+    });
+
+    let variant: SqlVariant = "sqlite3".parse().unwrap();
+    assert_eq!(variant, SqlVariant::Sqlite);
+    assert_eq!(m.make_from(variant), m.make::<Sqlite>());
+}
+
+#[test]
+fn drop_column_on_old_version() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.drop_column("age");
+    });
+
+    assert_eq!(
+        m.try_make_with(&Sqlite::default().version(3, 34)),
+        Err(Error::Unsupported(
+            "Sqlite",
+            "Dropping a column before version 3.35".into()
+        ))
+    );
+    assert!(m.try_make_with(&Sqlite::default().version(3, 35)).is_ok());
+}
+
+#[test]
+fn add_primary_key() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.change_table("memberships", |t: &mut Table| {
+        t.set_primary_key(&["tenant_id", "id"]);
+    });
+
+    assert_eq!(
+        m.try_make::<Sqlite>(),
+        Err(Error::Unsupported(
+            "Sqlite",
+            "Adding a constraint to an existing table".into()
+        ))
+    );
+}
+
+#[test]
+fn add_index() {
+    let mut m = Migration::new();
+    m.change_table("people", |t: &mut Table| {
+        t.add_index("idx_people_name", &["last_name", "first_name"]);
+    });
+
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from(
+            "CREATE INDEX \"idx_people_name\" ON \"people\" (\"last_name\", \"first_name\")"
+        )
+    );
+}
+
+#[test]
+fn add_partial_index() {
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_index("idx_users_email", &["email"])
+            .where_clause("deleted_at IS NULL");
+    });
+
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from(
+            "CREATE INDEX \"idx_users_email\" ON \"users\" (\"email\") WHERE deleted_at IS NULL"
+        )
+    );
+}
+
+#[test]
+fn add_covering_index() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.change_table("orders", |t: &mut Table| {
+        t.add_index("idx_orders_customer", &["customer_id"])
+            .include(&["total"]);
+    });
+
+    assert_eq!(
+        m.try_make::<Sqlite>(),
+        Err(Error::Unsupported("Sqlite", "INCLUDE columns".into()))
+    );
+}
+
+#[test]
+fn set_comment() {
+    use crate::Error;
+    let mut m = Migration::new();
+    m.change_table("posts", |t: &mut Table| {
+        t.set_comment("Everything written");
+    });
+
+    assert_eq!(
+        m.try_make::<Sqlite>(),
+        Err(Error::Unsupported("Sqlite", "Table comments".into()))
+    );
+}
+
+#[test]
+fn change_column() {
+    use crate::schema::Schema;
+    use crate::{Error, Type};
+
+    let mut create = Migration::new();
+    create.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Varchar(64));
+        t.add_column("age", Type::Integer);
+    });
+
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.change_column("name", Type::Text);
+    });
+    assert_eq!(
+        m.try_make::<Sqlite>(),
+        Err(Error::Unsupported(
+            "Sqlite",
+            "Changing column `name` without the definition of its table".into()
+        ))
+    );
+
+    let m = Schema::from_migrations(&[create]).complete(&m);
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from("CREATE TABLE \"new_users\" (\"id\" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT, \"name\" TEXT, \"age\" INTEGER); INSERT INTO \"new_users\" (\"id\", \"name\", \"age\") SELECT \"id\", \"name\", \"age\" FROM \"users\"; DROP TABLE \"users\"; ALTER TABLE \"new_users\" RENAME TO \"users\"")
+    );
+}
+
+#[test]
+fn set_default_and_not_null() {
+    use crate::Type;
+
+    /* Tables created by the same migration are known */
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Varchar(64));
+    });
+    m.change_table("users", |t: &mut Table| {
+        t.add_column("age", Type::Integer);
+        t.set_default("age", "0");
+        t.set_not_null("age");
+    });
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from("CREATE TABLE \"users\" (\"id\" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT, \"name\" VARCHAR(64)); ALTER TABLE \"users\" ADD COLUMN \"age\" INTEGER; CREATE TABLE \"new_users\" (\"id\" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT, \"name\" VARCHAR(64), \"age\" INTEGER DEFAULT 0 NOT NULL); INSERT INTO \"new_users\" (\"id\", \"name\", \"age\") SELECT \"id\", \"name\", \"age\" FROM \"users\"; DROP TABLE \"users\"; ALTER TABLE \"new_users\" RENAME TO \"users\"")
+    );
+}
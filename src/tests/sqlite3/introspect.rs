@@ -0,0 +1,28 @@
+//! Read back the schema of an SQLite database
+
+use crate::backend::Sqlite;
+use crate::introspect::Introspect;
+use crate::schema::Schema;
+use crate::{Migration, Table, Type};
+use rusqlite::Connection;
+
+#[test]
+fn round_trip() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Varchar(255)).nullable();
+        t.add_column("bio", Type::Text);
+        t.add_column("score", Type::Double);
+        t.add_column("active", Type::Boolean);
+    });
+    m.create_table("posts", |t: &mut Table| {
+        t.add_column("author", Type::Foreign("users".into()));
+        t.add_column("views", Type::Custom("MEDIUMINT".into()));
+    });
+    m.execute::<_, Sqlite>(&mut conn).unwrap();
+
+    let mut expected = Schema::from_migrations(&[m]);
+    expected.tables.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_eq!(conn.introspect().unwrap(), expected);
+}
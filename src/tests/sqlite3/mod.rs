@@ -1,3 +1,14 @@
 //! A few simple tests for the sqlite3 string backend
 
-mod simple;
\ No newline at end of file
+mod add_column;
+mod change_table;
+mod simple;
+
+#[cfg(feature = "sqlx")]
+mod sqlx;
+
+#[cfg(feature = "rusqlite")]
+mod rusqlite;
+
+#[cfg(all(feature = "introspect", feature = "rusqlite"))]
+mod introspect;
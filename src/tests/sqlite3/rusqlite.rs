@@ -0,0 +1,189 @@
+//! Run migrations on an in-memory rusqlite connection
+#![allow(unused_imports)]
+
+use crate::backend::Sqlite;
+use crate::{Error, Migration, Table, Type};
+use rusqlite::Connection;
+
+fn tables(conn: &Connection) -> Vec<String> {
+    let mut stmt = conn
+        .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name")
+        .unwrap();
+    let rows = stmt.query_map([], |row| row.get(0)).unwrap();
+    rows.map(|r| r.unwrap()).collect()
+}
+
+#[test]
+fn execute() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Text).default("a; b");
+    });
+    m.create_table("posts", |t: &mut Table| {
+        t.add_column("title", Type::Text);
+    });
+
+    m.execute::<_, Sqlite>(&mut conn).unwrap();
+    assert_eq!(tables(&conn), vec!["posts", "users"]);
+
+    let def: String = conn
+        .query_row(
+            "SELECT dflt_value FROM pragma_table_info('users') WHERE name = 'name'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(def, "'a; b'");
+}
+
+#[test]
+fn execute_rolls_back() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Text);
+    });
+    m.drop_table("posts");
+
+    match m.execute::<_, Sqlite>(&mut conn) {
+        Err(Error::Execution(_)) => {}
+        res => panic!("unexpected result {:?}", res),
+    }
+    assert!(tables(&conn).is_empty());
+}
+
+#[test]
+fn execute_versioned() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Text);
+    });
+
+    assert!(m.execute_versioned::<_, Sqlite>("0001", &mut conn).unwrap());
+    assert!(!m.execute_versioned::<_, Sqlite>("0001", &mut conn).unwrap());
+    assert_eq!(tables(&conn), vec!["__barrel_migrations", "users"]);
+    assert_eq!(
+        crate::tracking::applied::<_, Sqlite>(&mut conn).unwrap(),
+        vec!["0001"]
+    );
+}
+
+#[test]
+fn execute_versioned_rolls_back() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    let mut m = Migration::new();
+    m.drop_table("posts");
+
+    assert!(m.execute_versioned::<_, Sqlite>("0001", &mut conn).is_err());
+    assert!(crate::tracking::applied::<_, Sqlite>(&mut conn)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn insert_into() {
+    use crate::Value;
+    let mut conn = Connection::open_in_memory().unwrap();
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Text);
+        t.add_column("admin", Type::Boolean);
+    });
+    m.insert_into(
+        "users",
+        &[("name", "it's me".into()), ("admin", true.into())],
+    );
+    m.execute::<_, Sqlite>(&mut conn).unwrap();
+
+    let row: (String, bool) = conn
+        .query_row("SELECT name, admin FROM users", [], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(row, ("it's me".into(), true));
+}
+
+#[test]
+fn execute_versioned_modified() {
+    let mut conn = Connection::open_in_memory().unwrap();
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Text);
+    });
+    m.execute_versioned::<_, Sqlite>("0001", &mut conn).unwrap();
+
+    m.create_table("posts", |_: &mut Table| {});
+    assert_eq!(
+        m.execute_versioned::<_, Sqlite>("0001", &mut conn),
+        Err(Error::Modified("0001".into()))
+    );
+}
+
+#[test]
+fn runner_rollback_to() {
+    use crate::Runner;
+
+    let mut conn = Connection::open_in_memory().unwrap();
+    let mut runner = Runner::new();
+    for name in &["users", "posts", "tags"] {
+        let mut m = Migration::new();
+        m.create_table(*name, |_: &mut Table| {});
+        runner.add(format!("create_{}", name), m);
+    }
+
+    assert_eq!(runner.run::<_, Sqlite>(&mut conn).unwrap().len(), 3);
+    assert_eq!(
+        runner
+            .rollback_to::<_, Sqlite>("create_users", &mut conn)
+            .unwrap(),
+        vec!["create_tags".to_string(), "create_posts".to_string()]
+    );
+    assert_eq!(tables(&conn), vec!["__barrel_migrations", "users"]);
+
+    /* Nothing left to revert */
+    assert!(runner
+        .rollback_to::<_, Sqlite>("create_users", &mut conn)
+        .unwrap()
+        .is_empty());
+
+    /* Reverted migrations are applied again */
+    assert_eq!(
+        runner.run::<_, Sqlite>(&mut conn).unwrap(),
+        vec!["create_posts".to_string(), "create_tags".to_string()]
+    );
+    assert_eq!(
+        runner.rollback_to::<_, Sqlite>("create_comments", &mut conn),
+        Err(Error::UnknownVersion("create_comments".into()))
+    );
+}
+
+#[test]
+fn change_column_keeps_rows() {
+    use crate::schema::Schema;
+    let mut conn = Connection::open_in_memory().unwrap();
+    let mut create = Migration::new();
+    create.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Text);
+        t.add_column("age", Type::Integer);
+    });
+    create.insert_into("users", &[("name", "me".into()), ("age", 42.into())]);
+    create.execute::<_, Sqlite>(&mut conn).unwrap();
+
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.change_column("age", Type::Varchar(3));
+        t.set_default("age", "'0'");
+    });
+    let m = Schema::from_migrations(&[create]).complete(&m);
+    m.execute::<_, Sqlite>(&mut conn).unwrap();
+
+    assert_eq!(tables(&conn), vec!["users"]);
+    let row: (String, String) = conn
+        .query_row("SELECT name, age FROM users", [], |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(row, ("me".into(), "42".into()));
+}
@@ -2,41 +2,155 @@
 
 #![allow(unused_imports)]
 
-use backend::{SqlGenerator, Sqlite};
-
+use crate::backend::{SqlGenerator, Sqlite};
 
 #[test]
 fn create_table() {
-    let sql = Sqlite::create_table("table_to_create");
+    let sql = Sqlite::default().create_table("table_to_create");
     assert_eq!(String::from("CREATE TABLE \"table_to_create\""), sql);
 }
 
 #[test]
 fn create_table_if_not_exists() {
-    let sql = Sqlite::create_table_if_not_exists("table_to_create");
-    assert_eq!(String::from("CREATE TABLE IF NOT EXISTS \"table_to_create\""), sql);
+    let sql = Sqlite::default().create_table_if_not_exists("table_to_create");
+    assert_eq!(
+        String::from("CREATE TABLE IF NOT EXISTS \"table_to_create\""),
+        sql
+    );
 }
 
 #[test]
 fn drop_table() {
-    let sql = Sqlite::drop_table("table_to_drop");
+    let sql = Sqlite::default().drop_table("table_to_drop");
     assert_eq!(String::from("DROP TABLE \"table_to_drop\""), sql);
 }
 
 #[test]
 fn drop_table_if_exists() {
-    let sql = Sqlite::drop_table_if_exists("table_to_drop");
+    let sql = Sqlite::default().drop_table_if_exists("table_to_drop");
     assert_eq!(String::from("DROP TABLE IF EXISTS \"table_to_drop\""), sql);
 }
 
 #[test]
 fn rename_table() {
-    let sql = Sqlite::rename_table("old_table", "new_table");
-    assert_eq!(String::from("ALTER TABLE \"old_table\" RENAME TO \"new_table\""), sql);
+    let sql = Sqlite::default().rename_table("old_table", "new_table");
+    assert_eq!(
+        String::from("ALTER TABLE \"old_table\" RENAME TO \"new_table\""),
+        sql
+    );
 }
 
 #[test]
 fn alter_table() {
-    let sql = Sqlite::alter_table("table_to_alter");
+    let sql = Sqlite::default().alter_table("table_to_alter");
     assert_eq!(String::from("ALTER TABLE \"table_to_alter\""), sql);
 }
+
+#[test]
+fn drop_column() {
+    let sql = Sqlite::default().drop_column("column_to_drop");
+    assert_eq!(String::from("DROP COLUMN \"column_to_drop\""), sql);
+}
+
+#[test]
+fn rename_column() {
+    let sql = Sqlite::default().rename_column("old_column", "new_column");
+    assert_eq!(
+        String::from("RENAME COLUMN \"old_column\" TO \"new_column\""),
+        sql
+    );
+}
+
+#[test]
+fn auto_increment_start() {
+    use crate::{Migration, Table};
+
+    let mut m = Migration::new();
+    m.create_table("users", |_: &mut Table| {})
+        .auto_increment_start(1000);
+
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from("CREATE TABLE \"users\" (\"id\" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT); INSERT INTO sqlite_sequence (name, seq) VALUES ('users', 999)")
+    );
+}
+
+#[test]
+fn temporary_table() {
+    use crate::{Migration, Table};
+
+    let mut m = Migration::new();
+    m.create_table("scratch", |_: &mut Table| {})
+        .temporary(true);
+
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from("CREATE TEMPORARY TABLE \"scratch\" (\"id\" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT)")
+    );
+}
+
+#[test]
+fn create_partition() {
+    use crate::{Error, Migration};
+
+    let mut m = Migration::new();
+    m.create_partition("events_2024", "events", "FOR VALUES IN (2024)");
+
+    assert_eq!(
+        m.try_make::<Sqlite>(),
+        Err(Error::Unsupported("Sqlite", "Creating a partition".into()))
+    );
+}
+
+#[test]
+fn without_rowid_strict_table() {
+    use crate::{Error, Migration, Table, Type};
+
+    let mut m = Migration::new();
+    m.create_table("tags", |t: &mut Table| {
+        t.add_column("name", Type::Text).primary();
+    })
+    .without_id()
+    .without_rowid(true)
+    .strict(true);
+
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from("CREATE TABLE \"tags\" (\"name\" TEXT PRIMARY KEY) WITHOUT ROWID, STRICT")
+    );
+    assert_eq!(
+        m.try_make_with(&Sqlite::default().version(3, 36)),
+        Err(Error::Unsupported(
+            "Sqlite",
+            "STRICT tables before version 3.37".into()
+        ))
+    );
+
+    let mut m = Migration::new();
+    m.create_table("s", |t: &mut Table| {
+        t.add_column("name", Type::Varchar(255));
+        t.add_column("flag", Type::Boolean);
+        t.add_column("seen", Type::DateTime(None));
+        t.add_column("price", Type::Decimal(10, 2));
+    })
+    .strict(true);
+
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from(
+            "CREATE TABLE \"s\" (\"id\" INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT, \
+             \"name\" TEXT, \"flag\" INTEGER, \"seen\" TEXT, \"price\" ANY) STRICT"
+        )
+    );
+}
+
+#[test]
+fn drop_constraint() {
+    use crate::{Error, Migration};
+    let mut m = Migration::new();
+    m.change_table("orders", |t| t.drop_constraint("fk_orders_user"));
+    assert_eq!(
+        m.try_make::<Sqlite>(),
+        Err(Error::Unsupported("Sqlite", "Dropping a constraint".into()))
+    );
+}
@@ -0,0 +1,68 @@
+//! Run migrations on an in-memory sqlx pool
+#![allow(unused_imports)]
+
+use crate::{Error, Migration, Table, Type};
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+
+/// Every connection to `:memory:` opens its own database
+async fn pool() -> SqlitePool {
+    SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+async fn tables(pool: &SqlitePool) -> Vec<String> {
+    sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name")
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn execute() {
+    let pool = pool().await;
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Text);
+    });
+    m.create_table("posts", |t: &mut Table| {
+        t.add_column("title", Type::Text);
+    });
+
+    m.execute_async(&pool).await.unwrap();
+    assert_eq!(tables(&pool).await, vec!["posts", "users"]);
+}
+
+#[tokio::test]
+async fn execute_rolls_back() {
+    let pool = pool().await;
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Text);
+    });
+    m.drop_table("posts");
+
+    match m.execute_async(&pool).await {
+        Err(Error::Execution(_)) => {}
+        res => panic!("unexpected result {:?}", res),
+    }
+    assert!(tables(&pool).await.is_empty());
+}
+
+#[tokio::test]
+async fn execute_versioned() {
+    let pool = pool().await;
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", Type::Text);
+    });
+
+    assert!(m.execute_versioned_async("it's", &pool).await.unwrap());
+    assert!(!m.execute_versioned_async("it's", &pool).await.unwrap());
+    assert_eq!(
+        crate::tracking::applied_async(&pool).await.unwrap(),
+        vec!["it's"]
+    );
+}
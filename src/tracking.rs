@@ -0,0 +1,124 @@
+//! Keep track of the migrations applied to a database
+//!
+//! Applied migrations are recorded by their version in a
+//! `__barrel_migrations` table, which is created on first use.
+//! `Migration::execute_versioned` uses it to skip migrations which
+//! were applied already:
+//!
+//! ```norun
+//! for (version, m) in &migrations {
+//!     m.execute_versioned::<_, Pg>(version, &mut client)?;
+//! }
+//! ```
+//!
+//! Along with the version, the `checksum` of a migration is stored. If
+//! an applied migration was changed since, `execute_versioned` returns
+//! `Error::Modified` instead of skipping it.
+//!
+//! Reading the applied versions needs an executor which implements
+//! `query_column`, like the ones in the `integrations` module.
+//!
+//! The table is always created with the default configuration of a
+//! generator, so it isn't qualified with a schema.
+
+use crate::backend::SqlGenerator;
+use crate::connectors::{AsyncDatabaseExecutor, DatabaseExecutor, ExecuteFuture};
+use crate::{Error, Migration, Type, Value};
+
+/// The name of the table applied versions are recorded in
+pub const TABLE: &str = "__barrel_migrations";
+
+/// A migration that creates the tracking table, unless it exists
+pub fn create_table() -> Migration {
+    let mut m = Migration::new();
+    m.create_table_if_not_exists(TABLE, |t| {
+        /* Versions are names, not the usual serial key */
+        t.add_column("version", Type::Custom("VARCHAR(255) PRIMARY KEY".into()));
+        t.add_column("checksum", Type::Varchar(16));
+    })
+    .without_id();
+    m
+}
+
+/// The SQL which records a version as applied, with an optional checksum
+pub fn insert_version(version: &str, checksum: Option<u64>) -> String {
+    match checksum {
+        Some(sum) => format!(
+            "INSERT INTO {} (version, checksum) VALUES ({}, '{:016x}')",
+            TABLE,
+            Value::quote(version),
+            sum
+        ),
+        None => format!(
+            "INSERT INTO {} (version) VALUES ({})",
+            TABLE,
+            Value::quote(version)
+        ),
+    }
+}
+
+/// The SQL which removes a version from the applied ones
+pub fn delete_version(version: &str) -> String {
+    format!(
+        "DELETE FROM {} WHERE version = {}",
+        TABLE,
+        Value::quote(version)
+    )
+}
+
+/// The SQL which selects all applied versions
+pub fn select_versions() -> String {
+    format!("SELECT version FROM {}", TABLE)
+}
+
+/// The SQL which selects the checksum of an applied version
+///
+/// Versions recorded without a checksum have an empty one.
+pub fn select_checksum(version: &str) -> String {
+    format!(
+        "SELECT COALESCE(checksum, '') FROM {} WHERE version = {}",
+        TABLE,
+        Value::quote(version)
+    )
+}
+
+/// Check a checksum against the ones read via `select_checksum`
+///
+/// Missing or empty checksums aren't checked.
+pub fn verify(stored: &[String], version: &str, checksum: u64) -> Result<(), Error> {
+    match stored.first() {
+        Some(s) if !s.is_empty() && *s != format!("{:016x}", checksum) => {
+            Err(Error::Modified(version.into()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Append the statement recording a version to the SQL of a migration
+///
+/// Executing both in one batch means they share a transaction
+/// on backends that support transactional schema changes.
+pub fn with_version(sql: &str, version: &str, checksum: Option<u64>) -> String {
+    match sql.is_empty() {
+        true => insert_version(version, checksum),
+        false => format!("{}; {}", sql, insert_version(version, checksum)),
+    }
+}
+
+/// Create the tracking table if necessary and read all applied versions
+pub fn applied<T: DatabaseExecutor + ?Sized, S: SqlGenerator + Default>(
+    runner: &mut T,
+) -> Result<Vec<String>, Error> {
+    runner.batch_execute(&create_table().try_make::<S>()?)?;
+    runner.query_column(&select_versions())
+}
+
+/// Create the tracking table if necessary and read all applied
+/// versions from an async database handle
+pub fn applied_async<T: AsyncDatabaseExecutor>(runner: &T) -> ExecuteFuture<'_, Vec<String>> {
+    let create = create_table().try_make::<T::Generator>();
+    Box::pin(async move {
+        runner.batch_execute(&create?).await?;
+        runner.query_column(&select_versions()).await
+    })
+}
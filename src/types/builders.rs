@@ -1,58 +1,286 @@
 //! Builder API's module
 
-use types::Type;
+use crate::backend::SqlVariant;
+use crate::types::impls::BaseType;
+use crate::types::Type;
+#[cfg(feature = "postgis")]
+use crate::GeometryKind;
+use crate::RangeKind;
 
 /// Creates an auto-incrementing primary key type
-pub fn primary() -> Type<u64> {
-    unimplemented!()
+pub fn primary() -> Type {
+    Type::new(BaseType::Primary)
 }
 
 /// Create a basic integer type
-pub fn integer() -> Type<i64> {
-    unimplemented!()
+pub fn integer() -> Type {
+    Type::new(BaseType::Integer)
 }
 
+/// Create a 16-bit integer type
+pub fn smallint() -> Type {
+    Type::new(BaseType::SmallInt)
+}
+
+/// Create a 64-bit integer type
+pub fn bigint() -> Type {
+    Type::new(BaseType::BigInt)
+}
+
+/// Create an auto-incrementing 32-bit integer type
+pub fn serial() -> Type {
+    Type::new(BaseType::Serial)
+}
+
+/// Create an auto-incrementing 64-bit integer type
+pub fn bigserial() -> Type {
+    Type::new(BaseType::BigSerial)
+}
+
+/// Create a single precision floating point type
+pub fn float() -> Type {
+    Type::new(BaseType::Float)
+}
+
+/// Create a double precision floating point type
+pub fn double() -> Type {
+    Type::new(BaseType::Double)
+}
+
+/// Create a boolean type
+pub fn boolean() -> Type {
+    Type::new(BaseType::Boolean)
+}
+
+/// Create a variable-length string type
+///
+/// Use `size` to set a maximum length
+pub fn varchar() -> Type {
+    Type::new(BaseType::Varchar)
+}
+
+/// Create a fixed-length string type
+pub fn char(size: usize) -> Type {
+    Type::new(BaseType::Char).size(size)
+}
+
+/// Create a case-insensitive string type
+///
+/// On Postgres this needs the `citext` extension
+pub fn citext() -> Type {
+    Type::new(BaseType::Citext)
+}
+
+/// Create a text type
+pub fn text() -> Type {
+    Type::new(BaseType::Text)
+}
+
+/// Create a text type for up to 255 bytes (a `TINYTEXT` on MySQL)
+pub fn tinytext() -> Type {
+    Type::new(BaseType::TinyText)
+}
+
+/// Create a text type for up to 16 MiB (a `MEDIUMTEXT` on MySQL)
+pub fn mediumtext() -> Type {
+    Type::new(BaseType::MediumText)
+}
+
+/// Create a text type for up to 4 GiB (a `LONGTEXT` on MySQL)
+pub fn longtext() -> Type {
+    Type::new(BaseType::LongText)
+}
+
+/// Create a JSON type
+pub fn json() -> Type {
+    Type::new(BaseType::Json)
+}
+
+/// Create a binary JSON type (Postgres `JSONB`)
+pub fn jsonb() -> Type {
+    Type::new(BaseType::Jsonb)
+}
+
+/// Create a key/value type
+///
+/// On Postgres this needs the `hstore` extension
+pub fn hstore() -> Type {
+    Type::new(BaseType::Hstore)
+}
+
+/// Create a full-text search document type (Postgres only)
+pub fn tsvector() -> Type {
+    Type::new(BaseType::TsVector)
+}
+
+/// Create a full-text search query type (Postgres only)
+pub fn tsquery() -> Type {
+    Type::new(BaseType::TsQuery)
+}
+
+/// Create an XML document type
+pub fn xml() -> Type {
+    Type::new(BaseType::Xml)
+}
+
+/// Create a binary type
 ///
-pub fn float() -> Type<f32> {
-    unimplemented!()
+/// Use `size` to set a maximum length in bytes
+pub fn binary() -> Type {
+    Type::new(BaseType::Binary)
+}
+
+/// Create a fixed-length bit string type
+pub fn bit(size: usize) -> Type {
+    Type::new(BaseType::Bit).size(size)
+}
+
+/// Create a bit string type with a maximum length
+pub fn varbit(size: usize) -> Type {
+    Type::new(BaseType::Varbit).size(size)
+}
+
+/// Create a UUID type
+pub fn uuid() -> Type {
+    Type::new(BaseType::Uuid)
+}
+
+/// Create a network host address type
+pub fn inet() -> Type {
+    Type::new(BaseType::Inet)
+}
+
+/// Create a network address type
+pub fn cidr() -> Type {
+    Type::new(BaseType::Cidr)
+}
+
+/// Create a MAC address type
+pub fn macaddr() -> Type {
+    Type::new(BaseType::MacAddr)
+}
+
+/// Create a date type
+pub fn date() -> Type {
+    Type::new(BaseType::Date)
 }
 
+/// Create a time of day type
 ///
-pub fn double() -> Type<f64> {
-    unimplemented!()
+/// Use `fraction_digits` to set the number of fractional second digits
+pub fn time() -> Type {
+    Type::new(BaseType::Time)
 }
 
+/// Create a date and time type, without a time zone
 ///
-pub fn boolean() -> Type<bool> {
-    unimplemented!()
+/// Use `fraction_digits` to set the number of fractional second digits
+pub fn datetime() -> Type {
+    Type::new(BaseType::DateTime)
 }
 
+/// Create a timestamp type, without a time zone
 ///
-pub fn varchar<'inner>() -> Type<&'inner str> {
-    unimplemented!()
+/// The same as `datetime`. Use `fraction_digits` to set the number
+/// of fractional second digits, e.g. `3` for milliseconds.
+pub fn timestamp() -> Type {
+    Type::new(BaseType::DateTime)
 }
 
+/// Create a timestamp type with a time zone
 ///
-pub fn text() -> Type<String> {
-    unimplemented!()
+/// Use `fraction_digits` to set the number of fractional second digits
+pub fn timestamptz() -> Type {
+    Type::new(BaseType::TimestampTz)
 }
 
+/// Create an interval type
+pub fn interval() -> Type {
+    Type::new(BaseType::Interval)
+}
+
+/// Create a decimal type with a precision and scale
+pub fn decimal(precision: usize, scale: usize) -> Type {
+    Type::new(BaseType::Decimal).precision(precision, scale)
+}
+
+/// Create a point type
+pub fn point() -> Type {
+    Type::new(BaseType::Point)
+}
+
+/// Create a PostGIS geometry type with a shape and an optional SRID
+#[cfg(feature = "postgis")]
+pub fn geometry(kind: GeometryKind, srid: Option<u32>) -> Type {
+    let mut t = Type::new(BaseType::Geometry(kind));
+    t.srid = srid;
+    t
+}
+
+/// Create a PostGIS geography type with a shape and an optional SRID
+#[cfg(feature = "postgis")]
+pub fn geography(kind: GeometryKind, srid: Option<u32>) -> Type {
+    let mut t = Type::new(BaseType::Geography(kind));
+    t.srid = srid;
+    t
+}
+
+/// Create a range type of some kind (Postgres only)
+pub fn range(kind: RangeKind) -> Type {
+    Type::new(BaseType::Range(kind))
+}
+
+/// Create a money type
+pub fn money() -> Type {
+    Type::new(BaseType::Money)
+}
+
+/// Create an enum type which only holds one of the given values
 ///
-pub fn json() -> Type<()> {
-    unimplemented!()
+/// Postgres creates the type ahead of the table, named after the
+/// table and column of the enum (e.g. `users_status`)
+pub fn enumeration(values: &[&str]) -> Type {
+    Type::new(BaseType::Enum(
+        values.iter().map(|v| v.to_string()).collect(),
+    ))
 }
 
+/// Create a set type which holds any of the given values
 ///
-pub fn binary<'inner>() -> Type<&'inner [u8]> {
-    unimplemented!()
+/// Only MySQL supports this type
+pub fn set(values: &[&str]) -> Type {
+    Type::new(BaseType::Set(
+        values.iter().map(|v| v.to_string()).collect(),
+    ))
 }
 
+/// Create a foreign key type pointing to a table
+pub fn foreign<S: Into<String>>(table: S) -> Type {
+    Type::new(BaseType::Foreign(table.into()))
+}
+
+/// Create a type which is rendered as the given SQL
+pub fn custom<S: Into<String>>(sql: S) -> Type {
+    Type::new(BaseType::Custom(sql.into()))
+}
+
+/// Create a custom type with the SQL to render on each backend
 ///
-pub fn foreign<'inner, I>() -> Type<&'inner Type<I>> {
-    unimplemented!()
+/// Backends without an entry reject the type
+pub fn custom_per(types: &[(SqlVariant, &str)]) -> Type {
+    Type::new(BaseType::CustomPer(
+        types.iter().map(|&(v, t)| (v, t.to_string())).collect(),
+    ))
 }
 
 /// Create an array of inner types
-pub fn array<I>(inner: Type<I>) -> Type<Type<I>> {
-    unimplemented!()
+///
+/// The size and precision of the inner type are kept. Use
+/// `dimensions` to nest it further
+pub fn array(inner: Type) -> Type {
+    let mut t = Type::new(BaseType::Array(Box::new(inner.get_inner())));
+    t.size = inner.size;
+    t.precision = inner.precision;
+    t.srid = inner.srid;
+    t
 }
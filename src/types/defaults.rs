@@ -0,0 +1,113 @@
+//! Default values of a type column
+
+use crate::table::ColumnDefault;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// A default value, wrapped so that types of all columns can be stored together
+///
+/// Most of the time you don't have to build these yourself, because
+/// `Type::default` takes anything that converts into one.
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WrappedDefault {
+    /// Any whole number
+    Integer(i64),
+    /// Any floating point number
+    Float(f64),
+    /// Some text, quoted when rendered
+    Text(String),
+    /// True or False
+    Boolean(bool),
+    /// A bunch of bytes
+    Binary(Vec<u8>),
+    /// An SQL expression, rendered as is (e.g. `now()`)
+    Expression(String),
+    /// Nothing at all
+    Null,
+}
+
+impl Display for WrappedDefault {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use self::WrappedDefault::*;
+        match *self {
+            Integer(ref val) => write!(f, "{}", val),
+            Float(ref val) => write!(f, "{}", val),
+            Text(ref val) => write!(f, "'{}'", val.replace('\'', "''")),
+            Boolean(ref val) => write!(f, "{}", val),
+            Binary(ref val) => {
+                let hex: String = val.iter().map(|b| format!("{:02x}", b)).collect();
+                write!(f, "X'{}'", hex)
+            }
+            Expression(ref expr) => write!(f, "{}", expr),
+            Null => write!(f, "NULL"),
+        }
+    }
+}
+
+macro_rules! wrap_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for WrappedDefault {
+            fn from(data: $t) -> Self {
+                WrappedDefault::Integer(data as i64)
+            }
+        })*
+    };
+}
+
+wrap_integer!(i8, i16, i32, i64, u8, u16, u32, u64, usize);
+
+impl From<f32> for WrappedDefault {
+    fn from(data: f32) -> Self {
+        WrappedDefault::Float(data as f64)
+    }
+}
+
+impl From<f64> for WrappedDefault {
+    fn from(data: f64) -> Self {
+        WrappedDefault::Float(data)
+    }
+}
+
+impl From<&str> for WrappedDefault {
+    fn from(data: &str) -> Self {
+        WrappedDefault::Text(data.into())
+    }
+}
+
+impl From<String> for WrappedDefault {
+    fn from(data: String) -> Self {
+        WrappedDefault::Text(data)
+    }
+}
+
+impl From<bool> for WrappedDefault {
+    fn from(data: bool) -> Self {
+        WrappedDefault::Boolean(data)
+    }
+}
+
+impl From<&[u8]> for WrappedDefault {
+    fn from(data: &[u8]) -> Self {
+        WrappedDefault::Binary(data.to_vec())
+    }
+}
+
+impl From<Vec<u8>> for WrappedDefault {
+    fn from(data: Vec<u8>) -> Self {
+        WrappedDefault::Binary(data)
+    }
+}
+
+/// Binary values and `NULL` become expressions
+impl From<WrappedDefault> for ColumnDefault {
+    fn from(data: WrappedDefault) -> Self {
+        match data {
+            WrappedDefault::Integer(val) => ColumnDefault::Integer(val),
+            WrappedDefault::Float(val) => ColumnDefault::Float(val),
+            WrappedDefault::Text(val) => ColumnDefault::Text(val),
+            WrappedDefault::Boolean(val) => ColumnDefault::Boolean(val),
+            WrappedDefault::Expression(expr) => ColumnDefault::Expression(expr),
+            other => ColumnDefault::Expression(other.to_string()),
+        }
+    }
+}
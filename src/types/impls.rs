@@ -1,16 +1,42 @@
 //! Implementation specifics for the type system
 
+use crate::backend::SqlVariant;
+use crate::table::{Column, ColumnDefault};
+use crate::types::WrappedDefault;
+#[cfg(feature = "postgis")]
+use crate::GeometryKind;
+use crate::RangeKind;
+
 /// Core type enum, describing the basic type
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) enum BaseType {
     /// Strings
     Text,
+    /// Strings, but only a few words
+    TinyText,
+    /// Strings, a chapter or so
+    MediumText,
+    /// Strings, the whole book
+    LongText,
     /// Like a String but worse
     Varchar,
+    /// Like a Varchar but it always takes up the same space
+    Char,
+    /// Strings that don't care how you shout
+    Citext,
     /// Primary key (utility for incrementing integer – postgres supports this, we just mirror it)
     Primary,
     /// Simple integer
     Integer,
+    /// Integer, but small
+    SmallInt,
+    /// Integer, but big
+    BigInt,
+    /// Integer, but it counts by itself
+    Serial,
+    /// Integer, but big and counts by itself
+    BigSerial,
     /// Floating point number
     Float,
     /// Like Float but ~~double precision~~
@@ -19,10 +45,64 @@ pub(crate) enum BaseType {
     Boolean,
     /// <inconceivable jibberish>
     Binary,
+    /// Ones and zeros, a fixed number of them
+    Bit,
+    /// Ones and zeros, up to some number of them
+    Varbit,
+    /// 128 bits of unique
+    Uuid,
+    /// Where to find a host
+    Inet,
+    /// Where to find a whole bunch of hosts
+    Cidr,
+    /// Where to find a network card
+    MacAddr,
+    /// Structured documents
+    Json,
+    /// Like Json but pre-chewed
+    Jsonb,
+    /// Keys and values, but flat
+    Hstore,
+    /// Words, ready to be found
+    TsVector,
+    /// Words, looking for each other
+    TsQuery,
+    /// Structured documents, but with more angle brackets
+    Xml,
+    /// A day in the calendar
+    Date,
+    /// A time on the clock
+    Time,
+    /// Both of the above
+    DateTime,
+    /// Both of the above, but it knows where it is
+    TimestampTz,
+    /// How long it took
+    Interval,
+    /// Numbers for accountants
+    Decimal,
+    /// Here, exactly
+    Point,
+    /// Shapes on a map
+    #[cfg(feature = "postgis")]
+    Geometry(GeometryKind),
+    /// Shapes on a globe
+    #[cfg(feature = "postgis")]
+    Geography(GeometryKind),
+    /// From here to there
+    Range(RangeKind),
+    /// Numbers for bankers
+    Money,
+    /// Pick one, any one (from the list)
+    Enum(Vec<String>),
+    /// Pick any, as many as you like (MySQL only)
+    Set(Vec<String>),
     /// Foreign key to other table
-    Foreign(&'static str),
+    Foreign(String),
     /// I have no idea what you are – but I *like* it
-    Custom(&'static str),
+    Custom(String),
+    /// I have no idea what you are, but everyone else does
+    CustomPer(Vec<(SqlVariant, String)>),
     /// Any of the above, but **many** of them
     Array(Box<BaseType>),
 }
@@ -43,27 +123,35 @@ pub(crate) enum BaseType {
 /// ```
 ///
 /// Please see the **default vaulues** section in the `types` module docs!
-pub struct Type<T> {
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Type {
     pub nullable: bool,
     pub unique: bool,
     pub increments: bool,
+    pub unsigned: bool,
     pub indexed: bool,
-    pub default: Option<T>,
+    pub default: Option<WrappedDefault>,
     pub size: Option<usize>,
+    pub precision: Option<(usize, usize)>,
+    pub srid: Option<u32>,
     inner: BaseType,
 }
 
 /// This is a public API, be considered about breaking thigns
 #[cfg_attr(rustfmt, rustfmt_skip)]
-impl<T> Type<T> {
+impl Type {
     pub(crate) fn new(inner: BaseType) -> Self {
         Self {
             nullable: false,
             unique: false,
             increments: false,
+            unsigned: false,
             indexed: false,
             default: None,
             size: None,
+            precision: None,
+            srid: None,
             inner,
         }
     }
@@ -93,20 +181,146 @@ impl<T> Type<T> {
         Self { increments: arg, ..self }
     }
     
+    /// Specify if this integer type should be unsigned (MySQL only)
+    pub fn unsigned(self, arg: bool) -> Self {
+        Self { unsigned: arg, ..self }
+    }
+    
     /// Specify if this type should be indexed by your SQL implementation
     pub fn indexed(self, arg: bool) -> Self {
         Self { indexed: arg, ..self }
     }
     
     /// Provide a default value for a type column
-    pub fn default(self, arg: impl Into<T>) -> Self {
+    pub fn default(self, arg: impl Into<WrappedDefault>) -> Self {
         Self { default: Some(arg.into()), ..self }
     }
+
+    /// Provide an SQL expression as the default value of a type column
+    ///
+    /// The expression is rendered as is, e.g. `now()` or `CURRENT_TIMESTAMP`
+    pub fn default_expr(self, arg: impl Into<String>) -> Self {
+        Self { default: Some(WrappedDefault::Expression(arg.into())), ..self }
+    }
     
     /// Specify a size limit (important or varchar & similar)
     pub fn size(self, arg: usize) -> Self {
         Self { size: Some(arg), ..self }
     }
+
+    /// Specify the precision and scale (important for decimals)
+    pub fn precision(self, precision: usize, scale: usize) -> Self {
+        Self { precision: Some((precision, scale)), ..self }
+    }
+
+    /// Specify the number of fractional second digits (for times and
+    /// timestamps), e.g. `3` for milliseconds
+    ///
+    /// This is stored as the `size` of the type.
+    pub fn fraction_digits(self, arg: u8) -> Self {
+        Self { size: Some(arg as usize), ..self }
+    }
+
+    /// Specify the number of dimensions (for arrays)
+    ///
+    /// Types which aren't arrays are left as they are
+    pub fn dimensions(self, arg: usize) -> Self {
+        let elem = match self.inner {
+            BaseType::Array(ref elem) => *elem.clone(),
+            _ => return self,
+        };
+        let inner = (0..arg.max(1)).fold(elem, |t, _| BaseType::Array(Box::new(t)));
+        Self { inner, ..self }
+    }
+
+    /// Specify the spatial reference system (for geometries)
+    pub fn srid(self, arg: u32) -> Self {
+        Self { srid: Some(arg), ..self }
+    }
 }
 
+impl Type {
+    /// Convert a base type, with the size and precision of this type
+    fn convert(&self, inner: BaseType) -> crate::Type {
+        use crate::Type as T;
+        let digits = self.size.map(|s| s as u8);
+        match inner {
+            BaseType::Text => T::Text,
+            BaseType::TinyText => T::TinyText,
+            BaseType::MediumText => T::MediumText,
+            BaseType::LongText => T::LongText,
+            BaseType::Varchar => T::Varchar(self.size.unwrap_or(255)),
+            BaseType::Char => T::Char(self.size.unwrap_or(1)),
+            BaseType::Citext => T::Citext,
+            BaseType::Primary => T::Primary,
+            BaseType::Integer => T::Integer,
+            BaseType::SmallInt => T::SmallInt,
+            BaseType::BigInt => T::BigInt,
+            BaseType::Serial => T::Serial,
+            BaseType::BigSerial => T::BigSerial,
+            BaseType::Float => T::Float,
+            BaseType::Double => T::Double,
+            BaseType::Boolean => T::Boolean,
+            BaseType::Binary => match self.size {
+                Some(size) => T::Varbinary(size),
+                None => T::Binary,
+            },
+            BaseType::Bit => T::Bit(self.size.unwrap_or(1)),
+            BaseType::Varbit => T::Varbit(self.size.unwrap_or(0)),
+            BaseType::Uuid => T::Uuid,
+            BaseType::Inet => T::Inet,
+            BaseType::Cidr => T::Cidr,
+            BaseType::MacAddr => T::MacAddr,
+            BaseType::Json => T::Json,
+            BaseType::Jsonb => T::Jsonb,
+            BaseType::Hstore => T::Hstore,
+            BaseType::TsVector => T::TsVector,
+            BaseType::TsQuery => T::TsQuery,
+            BaseType::Xml => T::Xml,
+            BaseType::Date => T::Date,
+            BaseType::Time => T::Time(digits),
+            BaseType::DateTime => T::DateTime(digits),
+            BaseType::TimestampTz => T::TimestampTz(digits),
+            BaseType::Interval => T::Interval(None),
+            BaseType::Decimal => {
+                let (precision, scale) = self.precision.unwrap_or((10, 0));
+                T::Decimal(precision, scale)
+            }
+            BaseType::Point => T::Point,
+            #[cfg(feature = "postgis")]
+            BaseType::Geometry(kind) => T::Geometry(kind, self.srid),
+            #[cfg(feature = "postgis")]
+            BaseType::Geography(kind) => T::Geography(kind, self.srid),
+            BaseType::Range(kind) => T::Range(kind),
+            BaseType::Money => T::Money,
+            BaseType::Enum(values) => T::Enum(String::new(), values),
+            BaseType::Set(values) => T::Set(values),
+            BaseType::Foreign(table) => T::Foreign(table.into()),
+            BaseType::Custom(sql) => T::Custom(sql),
+            BaseType::CustomPer(types) => T::CustomPer(types),
+            BaseType::Array(inner) => T::Array(Box::new(self.convert(*inner))),
+        }
+    }
+}
 
+/// Only the base type is kept, convert into a `Column` for the rest
+impl From<Type> for crate::Type {
+    fn from(t: Type) -> Self {
+        t.convert(t.get_inner())
+    }
+}
+
+/// Columns are `NOT NULL` unless the type is `nullable`
+impl From<Type> for Column {
+    fn from(t: Type) -> Self {
+        Column {
+            nullable: !t.nullable,
+            unique: t.unique,
+            increments: t.increments,
+            unsigned: t.unsigned,
+            indexed: t.indexed,
+            def: t.default.clone().map(ColumnDefault::from),
+            ..Column::new(t.into())
+        }
+    }
+}
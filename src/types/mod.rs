@@ -17,23 +17,44 @@
 //! - `indexed`: `false`
 //! - `unique`: `false`
 //! - `default`:  `None`
-//! - `size`: `None` (which will error if size is important)
-//! 
+//! - `size`: `None` (a `varchar` is then 255 characters long)
+//! - `precision`: `None` (which will error for decimals)
+//! - `srid`: `None`
+//!
 //! ## Examples
-//! 
+//!
 //! ```norun
 //! extern crate barrel;
 //! use barrel::types::*;
-//! 
+//!
 //! // Make your own Primary key :)
 //! let col = integer().increments(true).unique(true);
 //! ```
+//!
+//! A type is turned into a column when it's added to a table:
+//!
+//! ```norun
+//! m.create_table("users", |t| {
+//!     t.add_column("id", uuid().default_expr("gen_random_uuid()"));
+//! });
+//! ```
 
 mod builders;
-mod impls;
+mod defaults;
+pub(crate) mod impls;
 
 /// Export all builder functions
 pub use self::builders::*;
 
 /// Export only the Type struct
 pub use self::impls::Type;
+
+/// Export the default values of types
+pub use self::defaults::WrappedDefault;
+
+/// The kinds of ranges, as taken by `range`
+pub use crate::RangeKind;
+
+/// The shapes of geometries, as taken by `geometry` and `geography`
+#[cfg(feature = "postgis")]
+pub use crate::GeometryKind;
@@ -0,0 +1,86 @@
+//! Values for rows inserted by a migration
+//!
+//! `Migration::insert_into` takes a list of columns and `Value`s.
+//! Generators turn them into literals for their database, including
+//! escaping text.
+//!
+//! ```
+//! # use barrel::*;
+//! let mut m = Migration::new();
+//! m.insert_into(
+//!     "roles",
+//!     &[("name", "admin".into()), ("level", 10.into()), ("parent", Value::Null)],
+//! );
+//! ```
+
+/// A single value in an `INSERT` statement
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Value {
+    Null,
+    Text(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+}
+
+impl Value {
+    /// Quote a string, escaping embedded quotes
+    pub(crate) fn quote(text: &str) -> String {
+        format!("'{}'", text.replace('\'', "''"))
+    }
+
+    /// Render a literal in standard SQL
+    pub(crate) fn literal(&self) -> String {
+        match *self {
+            Value::Null => "NULL".into(),
+            Value::Text(ref t) => Value::quote(t),
+            Value::Integer(i) => i.to_string(),
+            Value::Float(f) => f.to_string(),
+            Value::Boolean(true) => "TRUE".into(),
+            Value::Boolean(false) => "FALSE".into(),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Value {
+    fn from(data: &'a str) -> Self {
+        Value::Text(data.into())
+    }
+}
+
+impl From<String> for Value {
+    fn from(data: String) -> Self {
+        Value::Text(data)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(data: i64) -> Self {
+        Value::Integer(data)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(data: i32) -> Self {
+        Value::Integer(data as i64)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(data: f64) -> Self {
+        Value::Float(data)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(data: bool) -> Self {
+        Value::Boolean(data)
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(data: Option<T>) -> Self {
+        data.map(Into::into).unwrap_or(Value::Null)
+    }
+}